@@ -1,7 +1,9 @@
 use std::sync::mpsc::Receiver;
-use std::time::SystemTime;
+use std::path::PathBuf;
+use std::time::{Duration, Instant, SystemTime};
 
-use jarvis_core::{audio_buffer::AudioRingBuffer, audio_processing, commands, config, listener, recorder, stt, COMMANDS_LIST, intent, voices, ipc::{self, IpcEvent}, i18n, slots};
+use jarvis_core::{audio_buffer::AudioRingBuffer, audio_processing::{self, ProcessedAudio}, commands, ducking, config, diagnostics, listener, recorder, stt::{self, SpeechAlternative}, commands::JCommand, COMMANDS_LIST, intent, voices, ipc::{self, IpcEvent}, i18n, slots, db::structs::VadSettings, DB, history::{self, HistoryKind}, notify};
+use jarvis_core::commands::confirm::{self, Answer};
 use rand::seq::SliceRandom;
 
 use crate::should_stop;
@@ -13,6 +15,21 @@ enum VadState {
     VoiceActive,
 }
 
+impl VadState {
+    fn as_str(&self) -> &'static str {
+        match self {
+            VadState::WaitingForVoice => "waiting_for_voice",
+            VadState::VoiceActive => "voice_active",
+        }
+    }
+}
+
+// where the answer to a confirmation comes from
+enum Reply<'a> {
+    Voice(&'a mut [i16]),
+    Text(&'a Receiver<String>), // the next text command
+}
+
 pub fn start(text_cmd_rx: Receiver<String>) -> Result<(), ()> {
     main_loop(text_cmd_rx)
 }
@@ -31,8 +48,8 @@ fn main_loop(text_cmd_rx: Receiver<String>) -> Result<(), ()> {
     let mut silence_frames: u32 = 0;
     
     // how many frames of silence before we consider speech ended
-    // 1.5 seconds = 1.5 * (16000 / 512) ≈ 47 frames
-    let silence_threshold: u32 = ((1.5 * sample_rate as f32) / frame_length as f32) as u32;
+    // default 1.5 seconds = 1.5 * (16000 / 512) ≈ 47 frames
+    let silence_threshold: u32 = VadSettings::ms_to_frames(get_vad_settings().wake_silence_ms, frame_length, sample_rate);
     
     voices::play_greet();
 
@@ -57,12 +74,11 @@ fn main_loop(text_cmd_rx: Receiver<String>) -> Result<(), ()> {
         }
 
         if let Ok(text) = text_cmd_rx.try_recv() {
-            process_text_command(&text, &rt);
+            process_text_command(&text, &rt, &text_cmd_rx);
             continue 'wake_word;
         }
 
-        recorder::read_microphone(&mut frame_buffer);
-        let processed = audio_processing::process(&frame_buffer);
+        let processed = capture_frame(&mut frame_buffer, &vad_state);
         
         match vad_state {
             VadState::WaitingForVoice => {
@@ -91,6 +107,7 @@ fn main_loop(text_cmd_rx: Receiver<String>) -> Result<(), ()> {
                     // WAKE WORD DETECTED!
                     info!("Wake word activated!");
                     ipc::send(IpcEvent::WakeWordDetected);
+                    ducking::duck();
                     
                     stt::reset_wake_recognizer();
                     audio_processing::reset();
@@ -98,8 +115,7 @@ fn main_loop(text_cmd_rx: Receiver<String>) -> Result<(), ()> {
                     // brief sniff to keep feeding STT while transitioning
                     let sniff_frames = ((0.3 * sample_rate as f32) / frame_length as f32) as u32;
                     for _ in 0..sniff_frames {
-                        recorder::read_microphone(&mut frame_buffer);
-                        audio_processing::process(&frame_buffer);
+                        capture_frame(&mut frame_buffer, &vad_state);
                         stt::recognize(&frame_buffer, false);
                     }
 
@@ -113,6 +129,7 @@ fn main_loop(text_cmd_rx: Receiver<String>) -> Result<(), ()> {
                     stt::reset_wake_recognizer();
                     stt::reset_speech_recognizer(); // NOW reset, after command is done
                     audio_processing::reset();
+                    ducking::restore();
                     ipc::send(IpcEvent::Idle);
                     
                     continue 'wake_word;
@@ -137,6 +154,11 @@ fn main_loop(text_cmd_rx: Receiver<String>) -> Result<(), ()> {
     }
 
     recorder::stop_recording().ok();
+    ducking::restore();
+
+    for stats in audio_processing::stats() {
+        info!("Audio stage '{}': {} frames, avg {:?}, max {:?}", stats.name, stats.calls, stats.average(), stats.max);
+    }
     ipc::send(IpcEvent::Stopping);
 
     Ok(())
@@ -162,16 +184,24 @@ fn recognize_command(
     let mut first_recognition = prefed_audio;
     
     // longer silence threshold for commands (user might pause to think)
-    // 5 seconds
-    let silence_threshold: u32 = ((5.0 * sample_rate as f32) / frame_length as f32) as u32;
+    // default 5 seconds
+    let vad_settings = get_vad_settings();
+    let silence_threshold: u32 = VadSettings::ms_to_frames(vad_settings.command_silence_ms, frame_length, sample_rate);
+    let command_timeout = Duration::from_millis(vad_settings.command_timeout_ms);
+
+    // partial transcript tracking for early intent
+    let early_intent = DB.get().map(|db| db.read().early_intent).unwrap_or(config::DEFAULT_EARLY_INTENT);
+    let stable_frames: u32 = VadSettings::ms_to_frames(config::EARLY_INTENT_STABLE_MS, frame_length, sample_rate);
+    let mut partial = String::new();
+    let mut partial_age: u32 = 0;
+    let mut partial_checked = false;
     
     loop {
         if crate::should_stop() {
             return;
         }
         
-        recorder::read_microphone(frame_buffer);
-        let processed = audio_processing::process(frame_buffer);
+        let processed = capture_frame(frame_buffer, &vad_state);
         
         match vad_state {
             VadState::WaitingForVoice => {
@@ -196,12 +226,51 @@ fn recognize_command(
             
             VadState::VoiceActive => {
                 // feed to STT
-                if let Some(mut recognized_voice) = stt::recognize(frame_buffer, false) {
+                let mut recognized = stt::recognize_alternatives(frame_buffer);
+
+                if recognized.is_none() {
+                    // stream partial hypothesis
+                    if let Some(text) = stt::take_speech_partial() {
+                        ipc::send(IpcEvent::PartialTranscript { text: text.clone() });
+                        partial = text;
+                        partial_age = 0;
+                        partial_checked = false;
+                    } else {
+                        partial_age += 1;
+                    }
+
+                    // stable partial, try to finish early
+                    if early_intent && !partial_checked && partial_age >= stable_frames && !partial.is_empty() {
+                        partial_checked = true;
+                        if is_early_intent(&partial, rt) {
+                            info!("Early intent on partial: {}", partial);
+                            stt::reset_speech_recognizer();
+                            recognized = Some(vec![SpeechAlternative::new(std::mem::take(&mut partial), 1.0)]);
+                        }
+                    }
+                } else {
+                    partial.clear();
+                }
+
+                if let Some(alternatives) = recognized {
+                    let mut recognized_voice = alternatives[0].text.clone();
                     info!("Recognized voice: {}", recognized_voice);
+                    for alt in alternatives.iter().skip(1) {
+                        debug!("Alternative: {} ({:.2})", alt.text, alt.confidence);
+                    }
                     
                     ipc::send(IpcEvent::SpeechRecognized {
                         text: recognized_voice.clone(),
                     });
+
+                    diagnostics::capture(
+                        diagnostics::SampleKind::Command,
+                        "Vosk",
+                        0.0,
+                        0.0,
+                        0.0,
+                        Some(recognized_voice.clone()),
+                    );
                     
                     recognized_voice = recognized_voice.to_lowercase();
                     
@@ -256,23 +325,35 @@ fn recognize_command(
                     // for tbr in config::ASSISTANT_PHRASES_TBR {
                     //     recognized_voice = recognized_voice.replace(tbr, "");
                     // }
-                    for tbr in config::get_phrases_to_remove(&i18n::get_language()) {
-                        recognized_voice = recognized_voice.replace(tbr, "");
+                    recognized_voice = filter_phrase(&recognized_voice);
+
+                    // the best hypothesis was already cleaned above, the rest get the same treatment.
+                    // too short ones are dropped one by one, a short top guess can still have a usable runner-up
+                    let mut candidates: Vec<SpeechAlternative> = Vec::new();
+                    let cleaned = std::iter::once(recognized_voice).chain(alternatives.iter().skip(1).map(|alt| {
+                        let mut text = alt.text.to_lowercase();
+                        if contains_wake {
+                            for wp in config::get_wake_phrases(&i18n::get_language()) {
+                                text = text.replace(wp, "");
+                            }
+                        }
+                        filter_phrase(&text)
+                    }));
+
+                    for (text, alt) in cleaned.zip(alternatives.iter()) {
+                        if text.len() < 5 {
+                            debug!("Ignoring too short recognition: '{}'", text);
+                        } else if !candidates.iter().any(|c| c.text == text) {
+                            candidates.push(SpeechAlternative::new(text, alt.confidence));
+                        }
                     }
 
-                    recognized_voice = recognized_voice.trim().to_string();
-                    
-                    if recognized_voice.len() < 5 {
-                        debug!("Ignoring too short recognition: '{}'", recognized_voice);
+                    if candidates.is_empty() {
                         continue;
                     }
 
-                    if recognized_voice.is_empty() {
-                        continue;
-                    }
-                    
                     // execute command and check if we should chain
-                    let should_chain = execute_command(&recognized_voice, rt);
+                    let should_chain = execute_command(&candidates, rt, Reply::Voice(frame_buffer));
                     
                     if should_chain {
                         // chain: reset and continue listening
@@ -307,7 +388,7 @@ fn recognize_command(
         
         // timeout
         if let Ok(elapsed) = start.elapsed() {
-            if elapsed > config::CMS_WAIT_DELAY {
+            if elapsed > command_timeout {
                 info!("Command timeout, returning to wake word mode.");
                 return;
             }
@@ -316,12 +397,26 @@ fn recognize_command(
 }
 
 
-fn process_text_command(text: &str, rt: &tokio::runtime::Runtime) {
+fn process_text_command(text: &str, rt: &tokio::runtime::Runtime, text_cmd_rx: &Receiver<String>) {
     info!("Processing text command: {}", text);
     
     ipc::send(IpcEvent::SpeechRecognized { text: text.to_string() });
     
-    let mut filtered = text.to_lowercase();
+    let filtered = filter_phrase(&text.to_lowercase());
+    
+    if filtered.is_empty() {
+        ipc::send(IpcEvent::Idle);
+        return;
+    }
+    
+    // text commands never chain
+    execute_command(&[SpeechAlternative::new(filtered, 1.0)], rt, Reply::Text(text_cmd_rx));
+}
+
+
+// Strip activation phrases from lowercased text
+fn filter_phrase(text: &str) -> String {
+    let mut filtered = text.to_string();
     // for tbr in config::ASSISTANT_PHRASES_TBR {
     //     filtered = filtered.replace(tbr, "");
     // }
@@ -329,40 +424,85 @@ fn process_text_command(text: &str, rt: &tokio::runtime::Runtime) {
         filtered = filtered.replace(tbr, "");
     }
 
-    let filtered = filtered.trim();
-    
-    if filtered.is_empty() {
-        ipc::send(IpcEvent::Idle);
-        return;
+    filtered.trim().to_string()
+}
+
+
+// Check if a partial transcript is confident enough to execute before the user stops talking
+fn is_early_intent(text: &str, rt: &tokio::runtime::Runtime) -> bool {
+    if text.len() < 5 {
+        return false;
+    }
+
+    let Some(commands_list) = COMMANDS_LIST.get() else {
+        return false;
+    };
+
+    match rt.block_on(intent::classify(text)) {
+        Some((intent_id, confidence)) if confidence >= config::EARLY_INTENT_MIN_CONFIDENCE => {
+            // commands with slots may still be waiting for their values
+            intent::get_command_by_intent(commands_list, &intent_id)
+                .is_some_and(|(_, cmd)| cmd.slots.is_empty())
+        }
+        _ => false,
     }
-    
-    // text commands never chain
-    execute_command(filtered, rt);
 }
 
 
-// Execute command, returns true if chaining should continue
-fn execute_command(text: &str, rt: &tokio::runtime::Runtime) -> bool {
-    let commands_list = match COMMANDS_LIST.get() {
-        Some(c) => c,
-        None => {
-            ipc::send(IpcEvent::Error { message: "Commands not loaded".to_string() });
-            ipc::send(IpcEvent::Idle);
-            return false;
+// Score every hypothesis against the commands, pick the best joint stt + match score
+fn resolve_command<'a>(
+    candidates: &'a [SpeechAlternative],
+    rt: &tokio::runtime::Runtime,
+) -> Option<(&'a str, &'static PathBuf, &'static JCommand)> {
+    let commands_list = COMMANDS_LIST.get()?;
+    let mut best: Option<(&str, &PathBuf, &JCommand)> = None;
+    let mut best_score = f64::MIN;
+
+    for candidate in candidates {
+        let text = candidate.text.as_str();
+
+        let matched = if let Some((intent_id, confidence)) = rt.block_on(intent::classify(text)) {
+            info!("Intent recognized: {} (confidence: {:.2})", intent_id, confidence);
+            intent::get_command_by_intent(commands_list, &intent_id)
+                .map(|(path, cmd)| (path, cmd, confidence))
+        } else {
+            info!("Intent not recognized, trying levenshtein fallback...");
+            commands::fetch_command_scored(text, commands_list)
+                .map(|(path, cmd, score)| (path, cmd, score / 100.0))
+        };
+
+        let Some((cmd_path, cmd_config, match_score)) = matched else {
+            continue;
+        };
+
+        let score = config::STT_ALTERNATIVE_WEIGHT * candidate.confidence as f64
+            + (1.0 - config::STT_ALTERNATIVE_WEIGHT) * match_score;
+        debug!("Candidate '{}' -> '{}' (stt: {:.2}, match: {:.2}, joint: {:.2})",
+            text, cmd_config.id, candidate.confidence, match_score, score);
+
+        if score > best_score {
+            best_score = score;
+            best = Some((text, cmd_path, cmd_config));
         }
-    };
-    
-    let cmd_result = if let Some((intent_id, confidence)) = 
-        rt.block_on(intent::classify(text)) 
-    {
-        info!("Intent recognized: {} (confidence: {:.2})", intent_id, confidence);
-        intent::get_command_by_intent(commands_list, &intent_id)
-    } else {
-        info!("Intent not recognized, trying levenshtein fallback...");
-        commands::fetch_command(text, commands_list)
-    };
+    }
+
+    best
+}
+
+
+// Execute command, returns true if chaining should continue
+fn execute_command(candidates: &[SpeechAlternative], rt: &tokio::runtime::Runtime, reply: Reply) -> bool {
+    if COMMANDS_LIST.get().is_none() {
+        ipc::send(IpcEvent::Error { message: "Commands not loaded".to_string() });
+        ipc::send(IpcEvent::Idle);
+        return false;
+    }
     
-    if let Some((cmd_path, cmd_config)) = cmd_result {
+    if let Some((text, cmd_path, cmd_config)) = resolve_command(candidates, rt) {
+        if candidates.first().is_some_and(|c| c.text != text) {
+            info!("Picked alternative hypothesis: '{}'", text);
+        }
+
         info!("Command found: {:?}", cmd_path);
         
         // extract slots if needed
@@ -376,29 +516,58 @@ fn execute_command(text: &str, rt: &tokio::runtime::Runtime) -> bool {
             None
         };
 
-        match commands::execute_command(&cmd_path, &cmd_config, Some(&text), extracted_slots.as_ref()) {
-            Ok(chain) => {
+        // refuse before asking, so a confirmed command isn't turned down by its limits afterwards
+        let admitted = commands::check_admission(cmd_config);
+        if admitted.is_ok() && cmd_config.needs_confirmation() && !confirm_command(cmd_config, reply) {
+            ipc::send(IpcEvent::Idle);
+            return false;
+        }
+
+        match admitted.and_then(|_| commands::execute_command(&cmd_path, &cmd_config, Some(&text), extracted_slots.as_ref())) {
+            Ok(outcome) if outcome.success => {
                 info!("Command executed successfully");
+                history::record(&cmd_config.id, HistoryKind::Executed, Some(text));
                 // voices::play_ok();
                 voices::play_random_from(cmd_config.get_sounds(&i18n::get_language()).as_slice());
                 ipc::send(IpcEvent::CommandExecuted {
                     id: cmd_config.id.clone(),
                     success: true,
+                    exit_code: outcome.exit_code,
+                    output: outcome.output,
                 });
                 ipc::send(IpcEvent::Idle);
-                return chain; // return chain status from command
+                return outcome.chain; // return chain status from command
+            }
+            Ok(outcome) => {
+                let code = outcome.exit_code.map_or("signal".to_string(), |c| c.to_string());
+                error!("Command {} failed with exit code {}", cmd_config.id, code);
+                history::record(&cmd_config.id, HistoryKind::Failed, Some(text));
+                voices::play_error();
+                ipc::send(IpcEvent::CommandExecuted {
+                    id: cmd_config.id.clone(),
+                    success: false,
+                    exit_code: outcome.exit_code,
+                    output: outcome.output,
+                });
+                ipc::send(IpcEvent::Error {
+                    message: format!("Command '{}' failed (exit code {})", cmd_config.id, code),
+                });
             }
             Err(msg) => {
                 error!("Error executing command: {}", msg);
+                history::record(&cmd_config.id, HistoryKind::Failed, Some(text));
                 voices::play_error();
                 ipc::send(IpcEvent::CommandExecuted {
                     id: cmd_config.id.clone(),
                     success: false,
+                    exit_code: None,
+                    output: None,
                 });
                 ipc::send(IpcEvent::Error { message: msg.to_string() });
             }
         }
     } else {
+        let text = candidates.first().map(|c| c.text.as_str()).unwrap_or_default();
         info!("No command found for: {}", text);
         voices::play_not_found();
         ipc::send(IpcEvent::Error { 
@@ -411,6 +580,135 @@ fn execute_command(text: &str, rt: &tokio::runtime::Runtime) -> bool {
 }
 
 
+// Ask "are you sure?" and wait for yes / no, true = go ahead.
+// Unclear replies are ignored, silence until CONFIRM_TIMEOUT_MS cancels.
+fn confirm_command(cmd_config: &JCommand, mut reply: Reply) -> bool {
+    let prompt = confirm::prompt(cmd_config);
+    info!("Command '{}' needs confirmation: {}", cmd_config.id, prompt);
+
+    history::record(&cmd_config.id, HistoryKind::ConfirmationRequested, Some(&prompt));
+    ipc::send(IpcEvent::ConfirmationRequested { id: cmd_config.id.clone(), prompt: prompt.clone() });
+
+    // @TODO: speak the prompt when TTS will be implemented
+    voices::play_reply();
+    notify::show(&i18n::t("app-name"), &prompt);
+
+    let language = i18n::get_language();
+    let deadline = Instant::now() + Duration::from_millis(config::CONFIRM_TIMEOUT_MS);
+    let mut answer = None;
+
+    // yes / no are usually outside the command grammar, listen for them explicitly
+    let voice = matches!(reply, Reply::Voice(_));
+    if voice {
+        stt::set_reply_grammar(Some(&confirm::grammar(&language, &cmd_config.confirm_phrase)));
+    }
+
+    while let Some(text) = next_reply(&mut reply, deadline) {
+        match confirm::classify(&text, &language, &cmd_config.confirm_phrase) {
+            Answer::Yes => { answer = Some((true, text)); break; }
+            Answer::No => { answer = Some((false, text)); break; }
+            Answer::Unclear => debug!("Unclear confirmation reply: '{}'", text),
+        }
+    }
+
+    if voice {
+        stt::set_reply_grammar(None);
+    }
+
+    let (confirmed, text) = match answer {
+        Some((confirmed, text)) => (confirmed, Some(text)),
+        None => (false, None),
+    };
+
+    let kind = match (confirmed, &text) {
+        (true, _) => HistoryKind::Confirmed,
+        (false, Some(_)) => HistoryKind::Cancelled,
+        (false, None) => HistoryKind::TimedOut,
+    };
+    info!("Confirmation for '{}': {:?}", cmd_config.id, kind);
+    history::record(&cmd_config.id, kind, text.as_deref());
+
+    ipc::send(IpcEvent::ConfirmationResolved { id: cmd_config.id.clone(), confirmed, reply: text });
+
+    if !confirmed {
+        notify::show(&i18n::t("app-name"), &i18n::t("confirm-cancelled"));
+    }
+
+    confirmed
+}
+
+// Next recognized phrase, None after the deadline
+fn next_reply(reply: &mut Reply, deadline: Instant) -> Option<String> {
+    match reply {
+        Reply::Text(rx) => {
+            let text = rx.recv_timeout(deadline.saturating_duration_since(Instant::now())).ok()?;
+            ipc::send(IpcEvent::SpeechRecognized { text: text.clone() });
+            Some(text)
+        }
+
+        Reply::Voice(frame_buffer) => {
+            stt::reset_speech_recognizer();
+
+            while Instant::now() < deadline && !crate::should_stop() {
+                capture_frame(frame_buffer, &VadState::VoiceActive);
+
+                let Some(text) = stt::recognize_reply(frame_buffer) else { continue };
+                let text = text.trim().to_string();
+                if text.is_empty() {
+                    continue;
+                }
+
+                info!("Confirmation reply: {}", text);
+                ipc::send(IpcEvent::SpeechRecognized { text: text.clone() });
+                stt::reset_speech_recognizer();
+                return Some(text);
+            }
+
+            None
+        }
+    }
+}
+
+
+// Read one mic frame and run it through the audio pipeline.
+// frame_buffer keeps the raw samples unless recognize_processed_audio is on.
+fn capture_frame(frame_buffer: &mut [i16], vad_state: &VadState) -> ProcessedAudio {
+    recorder::read_microphone(frame_buffer);
+    let processed = audio_processing::process(frame_buffer);
+
+    // level meter for the GUI (raw input level)
+    if ipc::telemetry::is_active() {
+        ipc::telemetry::push(
+            frame_buffer,
+            processed.is_voice,
+            processed.vad_confidence,
+            vad_state.as_str(),
+            listener::take_wake_score(),
+        );
+    }
+
+    // built-in stages keep the frame length, custom ones might not
+    if recognize_processed_audio() && processed.samples.len() == frame_buffer.len() {
+        frame_buffer.copy_from_slice(&processed.samples);
+    }
+
+    diagnostics::push_frame(frame_buffer);
+    processed
+}
+
+fn recognize_processed_audio() -> bool {
+    DB.get()
+        .map(|db| db.read().recognize_processed_audio)
+        .unwrap_or(config::DEFAULT_RECOGNIZE_PROCESSED_AUDIO)
+}
+
+fn get_vad_settings() -> VadSettings {
+    DB.get()
+        .map(|db| db.read().vad_settings.clone())
+        .unwrap_or_default()
+}
+
+
 pub fn close(code: i32) {
     info!("Closing application.");
     voices::play_goodbye();
@@ -6,8 +6,8 @@ use std::sync::mpsc;
 
 // include core
 use jarvis_core::{
-    audio, audio_processing, commands, config, db, listener, recorder, stt, intent,
-    ipc::{self, IpcAction},
+    audio, audio_processing, commands, config, db, diagnostics, ducking, listener, recorder, stt, intent,
+    ipc::{self, IpcAction, IpcEvent},
     i18n, voices,
     APP_CONFIG_DIR, APP_LOG_DIR, COMMANDS_LIST, DB,
 };
@@ -52,6 +52,9 @@ fn main() -> Result<(), String> {
     // init i18n
     i18n::init(&DB.get().unwrap().read().language);
 
+    // init wake-word diagnostics (opt-in)
+    diagnostics::init();
+
     // initialize tray
     // @TODO. macOS currently not supported for tray functionality,
     // due to the separate thread in which tray processing works,
@@ -86,6 +89,9 @@ fn main() -> Result<(), String> {
     info!("Commands initialized. Count: {}, List: {:?}", cmds.len(), commands::list_paths(&cmds));
     COMMANDS_LIST.set(cmds).unwrap();
 
+    // command grammar for vosk, needs the commands (opt-in)
+    refresh_command_grammar();
+
     // init audio
     if audio::init().is_err() {
         // @TODO. Allow continuing even without audio?
@@ -115,9 +121,13 @@ fn main() -> Result<(), String> {
         warn!("Audio processing init failed: {}", e);
     }
 
+    // init ducking of other apps' audio (opt-in)
+    ducking::init();
+
     // init IPC
     info!("Initializing IPC...");
     ipc::init();
+    ipc::telemetry::init(DB.get().unwrap().read().telemetry_hz);
 
     // channel for text commands (manually written in the GUI)
     let (text_cmd_tx, text_cmd_rx) = mpsc::channel::<String>();
@@ -142,6 +152,16 @@ fn main() -> Result<(), String> {
                     error!("Failed to send text command to app: {}", e);
                 }
             }
+            IpcAction::SetCommandEnabled { id, enabled } => {
+                info!("Received command toggle: {} -> {}", id, enabled);
+                match commands::set_enabled(&id, enabled) {
+                    Ok(()) => {
+                        refresh_command_grammar(); // disabled phrases leave the grammar
+                        ipc::send(IpcEvent::CommandEnabledChanged { id, enabled });
+                    }
+                    Err(e) => ipc::send(IpcEvent::Error { message: e }),
+                }
+            }
             IpcAction::Ping => {
                 // handled internally by server
             }
@@ -165,6 +185,13 @@ fn main() -> Result<(), String> {
     Ok(())
 }
 
+// Rebuild the vosk command grammar, a no-op while commands and grammar settings are unchanged
+fn refresh_command_grammar() {
+    if let Some(commands) = COMMANDS_LIST.get() {
+        stt::update_command_grammar(commands);
+    }
+}
+
 pub fn should_stop() -> bool {
     SHOULD_STOP.load(Ordering::SeqCst)
 }
\ No newline at end of file
@@ -1,7 +1,10 @@
-use std::{io::{self, Write}, sync::Arc};
+use std::{io::{self, Write}, sync::Arc, time::Duration};
+use futures_util::{SinkExt, StreamExt};
 use parking_lot::RwLock;
+use serde_json::{json, Value};
+use tokio_tungstenite::tungstenite::Message;
 
-use jarvis_core::{COMMANDS_LIST, DB, JCommandsList, commands, config, db, intent};
+use jarvis_core::{COMMANDS_LIST, DB, JCommandsList, commands, config, db, diagnostics, intent};
 
 fn print_help() {
     println!("
@@ -14,6 +17,10 @@ Commands:
   phrases            - List all training phrases
   hash               - Show commands hash
   reload             - Reload commands from disk
+  enable <id>        - Switch a command on
+  disable <id>       - Switch a command off (kept in settings)
+  samples            - List captured wake-word diagnostic samples
+  mark <id> <label>  - Label a sample (correct, false_accept, miss)
   help               - Show this help
   exit               - Exit the CLI
 ");
@@ -23,6 +30,16 @@ fn list_commands(commands: &Vec<JCommandsList>) {
     println!("\n[ Loaded Commands ]");
     for cmd_list in commands {
         println!("  📁 {}", cmd_list.path.display());
+        if let Some(pack) = &cmd_list.pack {
+            println!("     📦 {} v{} by {}", pack.name, pack.version, pack.author);
+            if !pack.description.is_empty() {
+                println!("        {}", pack.description);
+            }
+            let capabilities: Vec<&str> = pack.capabilities.iter().map(|c| c.as_str()).collect();
+            println!("        capabilities: [{}], languages: [{}], platforms: [{}], min app: {}",
+                capabilities.join(", "), pack.languages.join(", "), pack.platforms.join(", "),
+                if pack.min_app_version.is_empty() { "-" } else { &pack.min_app_version });
+        }
         for cmd in &cmd_list.commands {
             println!("     ├─ id: {}", cmd.id);
             println!("     ├─ action: {}", cmd.action);
@@ -45,6 +62,45 @@ fn list_phrases(commands: &Vec<JCommandsList>) {
     println!();
 }
 
+fn list_samples() {
+    let samples = diagnostics::list_samples();
+    println!("\n[ Diagnostic Samples ] ({})", diagnostics::samples_dir().display());
+    if samples.is_empty() {
+        println!("  (none)");
+    }
+    for sample in samples {
+        println!(
+            "  {} | {:<9} | {:<10} | score {:.3} (threshold {:.3}) | label: {}",
+            sample.id,
+            sample.kind,
+            sample.engine,
+            sample.score,
+            sample.threshold,
+            sample.label.map(|l| l.to_string()).unwrap_or_else(|| "-".into()),
+        );
+        if let Some(text) = sample.text {
+            println!("      text: {}", text);
+        }
+    }
+    println!();
+}
+
+fn mark_sample(arg: &str) {
+    let parts: Vec<&str> = arg.split_whitespace().collect();
+    if parts.len() != 2 {
+        println!("  Usage: mark <id> <correct|false_accept|miss>");
+        return;
+    }
+
+    match parts[1].parse::<diagnostics::SampleLabel>() {
+        Ok(label) => match diagnostics::label_sample(parts[0], label) {
+            Ok(_) => println!("  ✓ {} labeled as {}", parts[0], label),
+            Err(e) => println!("  ✗ {}", e),
+        },
+        Err(e) => println!("  ✗ {}", e),
+    }
+}
+
 async fn classify_text(text: &str) {
     match intent::classify(text).await {
         Some((intent_id, confidence)) => {
@@ -90,6 +146,56 @@ async fn execute_text(commands: &'static Vec<JCommandsList>, text: &str) {
     }
 }
 
+// The running app owns the settings (and the grammar), so ask it first.
+// Without a reachable app the settings file is written directly.
+async fn set_command_enabled(id: &str, enabled: bool) {
+    let word = if enabled { "enabled" } else { "disabled" };
+
+    match send_command_enabled(id, enabled).await {
+        Ok(Some(message)) => println!("  ✗ {}", message),
+        Ok(None) => println!("  ✓ {} {} in the running app", id, word),
+        Err(e) => {
+            log::debug!("IPC: {}, writing settings directly", e);
+            match commands::set_enabled(id, enabled) {
+                Ok(_) => println!("  ✓ {} {} (applied on next app start)", id, word),
+                Err(e) => println!("  ✗ {}", e),
+            }
+        }
+    }
+}
+
+// Ok(None) when the app confirmed, Ok(Some(error)) when it refused, Err when it can't be reached
+async fn send_command_enabled(id: &str, enabled: bool) -> Result<Option<String>, String> {
+    let url = format!("ws://{}:{}", config::IPC_ADDR, config::IPC_PORT);
+    let (mut ws, _) = tokio::time::timeout(Duration::from_secs(2), tokio_tungstenite::connect_async(url.as_str()))
+        .await
+        .map_err(|_| format!("{} timed out", url))?
+        .map_err(|e| e.to_string())?;
+
+    let action = json!({ "action": "set_command_enabled", "id": id, "enabled": enabled });
+    ws.send(Message::Text(action.to_string().into())).await.map_err(|e| e.to_string())?;
+
+    // other events (e.g. telemetry of the GUI) may arrive before the answer
+    let answer = async {
+        while let Some(Ok(Message::Text(text))) = ws.next().await {
+            let Ok(event) = serde_json::from_str::<Value>(&text) else { continue };
+            match event["event"].as_str() {
+                Some("command_enabled_changed") if event["id"] == id => return Some(None),
+                Some("error") => return Some(Some(event["message"].as_str().unwrap_or_default().to_string())),
+                _ => {}
+            }
+        }
+        None
+    };
+
+    let result = tokio::time::timeout(Duration::from_secs(5), answer).await;
+    let _ = ws.close(None).await;
+    match result {
+        Ok(Some(answer)) => Ok(answer),
+        _ => Ok(Some("The app did not answer, check its log".into())),
+    }
+}
+
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // init logging
@@ -180,6 +286,15 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                     execute_text(COMMANDS_LIST.get().unwrap(), arg).await;
                 }
             }
+            "enable" | "disable" => {
+                if arg.is_empty() {
+                    println!("  Usage: {} <command id>", cmd);
+                } else {
+                    set_command_enabled(arg, cmd == "enable").await;
+                }
+            }
+            "samples" => list_samples(),
+            "mark" => mark_sample(arg),
             "reload" => {
                 println!("  Note: Reload requires app restart (statics can't be reset)");
             }
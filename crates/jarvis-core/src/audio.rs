@@ -1,6 +1,8 @@
 mod kira;
 mod rodio;
 
+pub mod reference;
+
 use once_cell::sync::OnceCell;
 use std::cmp::Ordering;
 use std::path::PathBuf;
@@ -75,6 +77,11 @@ pub fn play_sound(filename: &PathBuf) {
     }
 }
 
+// Is the assistant itself making sound right now (self-listening)
+pub fn is_playing() -> bool {
+    reference::is_playing()
+}
+
 pub fn get_sound_directory() -> Option<PathBuf> {
     let db = DB.get()?;
 
@@ -44,6 +44,12 @@ pub fn play_sound(filename: &PathBuf) {
         Ok(sound_data) => {
             // sound_data.duration() can be used in order to sleep, if (for some reason) blocking behaviour is required
 
+            // expose what is being played as echo reference
+            let mono: Vec<f32> = sound_data.frames.iter()
+                .map(|f| (f.left + f.right) * 0.5)
+                .collect();
+            super::reference::register_samples(&mono, sound_data.sample_rate);
+
             // play it (non-blocking)
             if let Some(manager) = MANAGER.get() {
                 if let Ok(mut audio_manager) = manager.lock() {
@@ -0,0 +1,118 @@
+// Reference signal of what the assistant is currently playing.
+// Used by echo cancellation and to gate wake-word detection during playback.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+
+use crate::config;
+
+// reference is kept at the recorder sample rate
+pub const SAMPLE_RATE: u32 = 16000;
+
+struct Playback {
+    started: Instant,
+    // mono, SAMPLE_RATE, -1.0..1.0
+    samples: Option<Arc<Vec<f32>>>,
+    ends: Instant,
+}
+
+static PLAYBACKS: Lazy<Mutex<Vec<Playback>>> = Lazy::new(|| Mutex::new(Vec::new()));
+
+// Register a sound that just started playing (interleaved-free mono samples)
+pub fn register_samples(mono: &[f32], sample_rate: u32) {
+    if mono.is_empty() || sample_rate == 0 {
+        return;
+    }
+
+    let samples = resample_linear(mono, sample_rate, SAMPLE_RATE);
+    let duration = Duration::from_secs_f32(samples.len() as f32 / SAMPLE_RATE as f32);
+    let now = Instant::now();
+
+    let mut playbacks = PLAYBACKS.lock();
+    prune(&mut playbacks, now);
+    playbacks.push(Playback {
+        started: now,
+        samples: Some(Arc::new(samples)),
+        ends: now + duration + Duration::from_millis(config::PLAYBACK_TAIL_MS),
+    });
+}
+
+// Register a sound without access to its samples (only gating will work)
+pub fn register_duration(duration: Duration) {
+    let now = Instant::now();
+
+    let mut playbacks = PLAYBACKS.lock();
+    prune(&mut playbacks, now);
+    playbacks.push(Playback {
+        started: now,
+        samples: None,
+        ends: now + duration + Duration::from_millis(config::PLAYBACK_TAIL_MS),
+    });
+}
+
+// Is assistant audio (or its tail) audible right now
+pub fn is_playing() -> bool {
+    let now = Instant::now();
+    PLAYBACKS.lock().iter().any(|p| p.ends > now)
+}
+
+// Mixed reference for the `len` samples that ended right now.
+// Returns None if nothing with known samples is playing.
+pub fn current_frame(len: usize) -> Option<Vec<f32>> {
+    let now = Instant::now();
+    let mut playbacks = PLAYBACKS.lock();
+    prune(&mut playbacks, now);
+
+    let mut frame: Option<Vec<f32>> = None;
+
+    for playback in playbacks.iter() {
+        let samples = match &playback.samples {
+            Some(s) => s,
+            None => continue,
+        };
+
+        let elapsed = now.duration_since(playback.started).as_secs_f64();
+        let end = (elapsed * SAMPLE_RATE as f64) as i64;
+        let start = end - len as i64;
+
+        let out = frame.get_or_insert_with(|| vec![0.0; len]);
+        for (i, idx) in (start..end).enumerate() {
+            if idx >= 0 && (idx as usize) < samples.len() {
+                out[i] += samples[idx as usize];
+            }
+        }
+    }
+
+    frame
+}
+
+pub fn clear() {
+    PLAYBACKS.lock().clear();
+}
+
+fn prune(playbacks: &mut Vec<Playback>, now: Instant) {
+    playbacks.retain(|p| p.ends > now);
+}
+
+fn resample_linear(input: &[f32], from: u32, to: u32) -> Vec<f32> {
+    if from == to {
+        return input.to_vec();
+    }
+
+    let ratio = from as f64 / to as f64;
+    let out_len = ((input.len() as f64) / ratio) as usize;
+
+    (0..out_len)
+        .map(|i| {
+            let pos = i as f64 * ratio;
+            let idx = pos as usize;
+            let frac = (pos - idx as f64) as f32;
+            let a = input[idx.min(input.len() - 1)];
+            let b = input[(idx + 1).min(input.len() - 1)];
+            a + (b - a) * frac
+        })
+        .collect()
+}
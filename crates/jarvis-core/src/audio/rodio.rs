@@ -10,7 +10,7 @@ use std::io::BufReader;
 use std::path::PathBuf;
 
 // use rodio::{Decoder, OutputStream, OutputStreamHandle, Sink};
-use rodio::{Decoder, OutputStream, Sink};
+use rodio::{Decoder, OutputStream, Sink, Source};
 
 // static STREAM: OnceCell<OutputStream> = OnceCell::new();
 static STREAM_HANDLE: OnceCell<OutputStream> = OnceCell::new();
@@ -53,6 +53,11 @@ pub fn play_sound(filename: &PathBuf, sleep: bool) {
     // Decode that sound file into a source
     let source = Decoder::new(file).unwrap();
 
+    // no sample access here, gate by duration only
+    if let Some(duration) = source.total_duration() {
+        super::reference::register_duration(duration);
+    }
+
     // Play the sound directly on the device
     // STREAM_HANDLE.get().unwrap().play_raw(source.convert_samples());
     SINK.get().unwrap().append(source);
@@ -30,6 +30,11 @@ impl AudioRingBuffer {
         self.buffer.drain(..).collect()
     }
     
+    // Copy all buffered frames into one contiguous vec (buffer is kept)
+    pub fn to_vec(&self) -> Vec<i16> {
+        self.buffer.iter().flatten().copied().collect()
+    }
+    
     // Get frame count
     pub fn len(&self) -> usize {
         self.buffer.len()
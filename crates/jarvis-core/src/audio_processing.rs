@@ -1,14 +1,25 @@
+pub mod stage;
+pub mod pipeline;
+pub mod reframer;
+
+pub mod high_pass;
 pub mod noise_suppression;
 pub mod vad;
 pub mod gain_normalizer;
+pub mod echo_cancellation;
 
 use once_cell::sync::OnceCell;
 use std::sync::Mutex;
 
-use crate::config::structs::{NoiseSuppressionBackend, VadBackend};
-use crate::DB;
+pub use stage::AudioStage;
+pub use pipeline::{AudioPipeline, PipelineSettings, StageStats};
+
+use crate::config::structs::{AudioStageConfig, EchoCancellation};
+use crate::db::structs::Settings;
+use crate::{recorder, DB};
 
-static PROCESSOR: OnceCell<Mutex<AudioProcessor>> = OnceCell::new();
+// pipeline used by the app's main loop
+static PIPELINE: OnceCell<Mutex<AudioPipeline>> = OnceCell::new();
 
 #[derive(Debug, Clone)]
 pub struct ProcessedAudio {
@@ -17,69 +28,19 @@ pub struct ProcessedAudio {
     pub vad_confidence: f32,
 }
 
-struct AudioProcessor {
-    ns_backend: NoiseSuppressionBackend,
-    vad_backend: VadBackend,
-    gain_enabled: bool,
-}
-
-impl AudioProcessor {
-    fn new(ns: NoiseSuppressionBackend, vad: VadBackend, gain: bool) -> Self {
-        // init backends
-        noise_suppression::init(ns);
-        vad::init(vad);
-        if gain {
-            gain_normalizer::init();
-        }
-
-        Self {
-            ns_backend: ns,
-            vad_backend: vad,
-            gain_enabled: gain,
-        }
-    }
-
-    fn process(&mut self, input: &[i16]) -> ProcessedAudio {
-        let mut samples = input.to_vec();
-
-        // step 1: gain normalization (before other processing)
-        if self.gain_enabled {
-            samples = gain_normalizer::normalize(&samples);
-        }
-
-        // step 2: noise suppression
-        samples = noise_suppression::process(&samples);
-
-        // step 3: VAD
-        let (is_voice, confidence) = vad::detect(&samples);
-
-        ProcessedAudio {
-            samples,
-            is_voice,
-            vad_confidence: confidence,
-        }
-    }
-
-    fn reset(&mut self) {
-        noise_suppression::reset();
-        vad::reset();
-        gain_normalizer::reset();
-    }
-}
-
-
-
 pub fn init() -> Result<(), String> {
-    if PROCESSOR.get().is_some() {
+    if PIPELINE.get().is_some() {
         return Ok(());
     }
 
-    let (ns, vad, gain) = get_settings();
-    info!("Initializing audio processing: NS={:?}, VAD={:?}, Gain={}", ns, vad, gain);
+    let (stages, settings) = get_settings();
+    info!("Initializing audio processing: {:?}", stages);
+
+    let pipeline = AudioPipeline::from_config(&stages, &settings);
+    info!("Audio pipeline: {}", pipeline.stage_names().join(" -> "));
 
-    let processor = AudioProcessor::new(ns, vad, gain);
-    PROCESSOR
-        .set(Mutex::new(processor))
+    PIPELINE
+        .set(Mutex::new(pipeline))
         .map_err(|_| "Audio processor already initialized")?;
 
     info!("Audio processing initialized.");
@@ -87,7 +48,7 @@ pub fn init() -> Result<(), String> {
 }
 
 pub fn process(input: &[i16]) -> ProcessedAudio {
-    match PROCESSOR.get() {
+    match PIPELINE.get() {
         Some(p) => p.lock().unwrap().process(input),
         None => ProcessedAudio {
             samples: input.to_vec(),
@@ -98,21 +59,57 @@ pub fn process(input: &[i16]) -> ProcessedAudio {
 }
 
 pub fn reset() {
-    if let Some(p) = PROCESSOR.get() {
+    if let Some(p) = PIPELINE.get() {
         p.lock().unwrap().reset();
     }
 }
 
-fn get_settings() -> (NoiseSuppressionBackend, VadBackend, bool) {
+pub fn stats() -> Vec<StageStats> {
+    match PIPELINE.get() {
+        Some(p) => p.lock().unwrap().stats().to_vec(),
+        None => Vec::new(),
+    }
+}
+
+// Stage order from settings.
+// Explicit `audio_pipeline` wins, otherwise the classic echo -> gain -> denoise -> vad.
+pub fn stages_from_settings(settings: &Settings) -> Vec<AudioStageConfig> {
+    if !settings.audio_pipeline.is_empty() {
+        return settings.audio_pipeline.clone();
+    }
+
+    let mut stages = Vec::new();
+
+    if settings.echo_cancellation == EchoCancellation::Nlms {
+        stages.push(AudioStageConfig::EchoCancellation { mode: settings.echo_cancellation });
+    }
+    if settings.gain_normalizer {
+        stages.push(AudioStageConfig::Gain);
+    }
+    stages.push(AudioStageConfig::Denoise { backend: settings.noise_suppression });
+    stages.push(AudioStageConfig::Vad { backend: settings.vad });
+
+    stages
+}
+
+fn get_settings() -> (Vec<AudioStageConfig>, PipelineSettings) {
     match DB.get() {
         Some(db) => {
+            // AGC can be tuned per microphone (quiet laptop mics etc.)
+            // (resolved before locking, recorder reads settings too)
+            let microphone = recorder::get_audio_device_name(recorder::get_selected_microphone_index());
+
             let settings = db.read();
-            (settings.noise_suppression, settings.vad, settings.gain_normalizer)
+            if settings.agc_profiles.contains_key(&microphone) {
+                info!("Using AGC profile for microphone '{}'", microphone);
+            }
+
+            (stages_from_settings(&settings), PipelineSettings {
+                vad: settings.vad_settings.clone(),
+                agc: settings.agc_for(&microphone),
+                ..Default::default()
+            })
         }
-        None => (
-            crate::config::DEFAULT_NOISE_SUPPRESSION,
-            crate::config::DEFAULT_VAD,
-            crate::config::DEFAULT_GAIN_NORMALIZER,
-        ),
+        None => (stages_from_settings(&Settings::default()), PipelineSettings::default()),
     }
-}
\ No newline at end of file
+}
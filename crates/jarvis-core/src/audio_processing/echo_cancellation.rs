@@ -0,0 +1,83 @@
+mod nlms;
+
+use super::{AudioStage, ProcessedAudio};
+use crate::audio;
+use crate::config::structs::EchoCancellation;
+use crate::DB;
+
+pub struct EchoCancellationStage {
+    nlms: nlms::NlmsCanceller,
+}
+
+impl EchoCancellationStage {
+    pub fn new() -> Self {
+        info!("Echo cancellation: NLMS");
+
+        Self {
+            nlms: nlms::NlmsCanceller::from_config(),
+        }
+    }
+}
+
+impl Default for EchoCancellationStage {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AudioStage for EchoCancellationStage {
+    fn name(&self) -> &str {
+        "echo_cancellation"
+    }
+
+    // remove assistant playback from the mic frame
+    fn process(&mut self, frame: &mut ProcessedAudio) {
+        if let Some(reference) = audio::reference::current_frame(frame.samples.len()) {
+            self.nlms.process(&mut frame.samples, &reference);
+        }
+    }
+
+    fn reset(&mut self) {
+        self.nlms.reset();
+    }
+}
+
+// None if samples are not touched (gate / disabled)
+pub fn create(mode: EchoCancellation) -> Option<Box<dyn AudioStage>> {
+    match mode {
+        EchoCancellation::None => {
+            info!("Echo cancellation: disabled");
+            None
+        }
+        EchoCancellation::Gate => {
+            info!("Echo cancellation: gating wake-word during playback");
+            None
+        }
+        EchoCancellation::Nlms => Some(Box::new(EchoCancellationStage::new())),
+    }
+}
+
+pub fn mode() -> EchoCancellation {
+    match DB.get() {
+        Some(db) => db.read().echo_cancellation,
+        None => crate::config::DEFAULT_ECHO_CANCELLATION,
+    }
+}
+
+// Should wake-word detections be ignored right now
+pub fn wake_gated() -> bool {
+    match mode() {
+        EchoCancellation::None => false,
+        // recognizers only hear the cancelled frame with recognize_processed_audio,
+        // and nlms only works with sample access, gate anything else (e.g. rodio)
+        EchoCancellation::Nlms => audio::is_playing()
+            && !(recognize_processed_audio() && audio::reference::current_frame(1).is_some()),
+        EchoCancellation::Gate => audio::is_playing(),
+    }
+}
+
+fn recognize_processed_audio() -> bool {
+    DB.get()
+        .map(|db| db.read().recognize_processed_audio)
+        .unwrap_or(crate::config::DEFAULT_RECOGNIZE_PROCESSED_AUDIO)
+}
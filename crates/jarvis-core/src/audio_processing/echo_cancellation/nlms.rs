@@ -0,0 +1,79 @@
+#[cfg(test)]
+mod tests;
+
+use crate::config;
+
+// normalized least-mean-squares adaptive filter
+// estimates the speaker -> mic echo path from the playback reference and subtracts it
+pub struct NlmsCanceller {
+    weights: Vec<f32>,
+    // reference history, circular
+    history: Vec<f32>,
+    pos: usize,
+    // running energy of history
+    power: f32,
+    step: f32,
+}
+
+impl NlmsCanceller {
+    pub fn new(taps: usize, step: f32) -> Self {
+        let taps = taps.max(1);
+        Self {
+            weights: vec![0.0; taps],
+            history: vec![0.0; taps],
+            pos: 0,
+            power: 0.0,
+            step,
+        }
+    }
+
+    pub fn from_config() -> Self {
+        Self::new(config::AEC_FILTER_TAPS, config::AEC_STEP_SIZE)
+    }
+
+    // mic: raw samples, reference: same length, -1.0..1.0
+    pub fn process(&mut self, mic: &mut [i16], reference: &[f32]) {
+        let taps = self.weights.len();
+
+        for (n, sample) in mic.iter_mut().enumerate() {
+            let x = reference.get(n).copied().unwrap_or(0.0);
+
+            // push reference sample, keep energy in sync
+            let old = self.history[self.pos];
+            self.power += x * x - old * old;
+            if self.power < 0.0 {
+                self.power = 0.0;
+            }
+            self.history[self.pos] = x;
+
+            // estimated echo
+            let mut y = 0.0f32;
+            for k in 0..taps {
+                let idx = (self.pos + taps - k) % taps;
+                y += self.weights[k] * self.history[idx];
+            }
+
+            let d = *sample as f32 / 32768.0;
+            let e = d - y;
+
+            // adapt only when there is something to learn from
+            if self.power > 1e-6 {
+                let g = self.step * e / (self.power + 1e-6);
+                for k in 0..taps {
+                    let idx = (self.pos + taps - k) % taps;
+                    self.weights[k] += g * self.history[idx];
+                }
+            }
+
+            *sample = (e * 32768.0).clamp(i16::MIN as f32, i16::MAX as f32) as i16;
+            self.pos = (self.pos + 1) % taps;
+        }
+    }
+
+    // forget history but keep the learned echo path (room doesn't change between phrases)
+    pub fn reset(&mut self) {
+        self.history.iter_mut().for_each(|v| *v = 0.0);
+        self.power = 0.0;
+        self.pos = 0;
+    }
+}
@@ -0,0 +1,55 @@
+use crate::audio_processing::echo_cancellation::nlms::NlmsCanceller;
+
+const DELAY: usize = 10;
+const ECHO_GAIN: f32 = 0.6;
+
+// deterministic white noise in -0.5..0.5
+fn noise(len: usize) -> Vec<f32> {
+    let mut state: u32 = 12345;
+    (0..len)
+        .map(|_| {
+            state = state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+            (state >> 8) as f32 / (1u32 << 24) as f32 - 0.5
+        })
+        .collect()
+}
+
+fn rms(samples: &[i16]) -> f32 {
+    (samples.iter().map(|&s| (s as f32).powi(2)).sum::<f32>() / samples.len() as f32).sqrt()
+}
+
+#[test]
+fn test_attenuates_delayed_echo() {
+    let reference = noise(16000 * 2);
+
+    // mic hears the reference DELAY samples later, attenuated
+    let echo: Vec<i16> = (0..reference.len())
+        .map(|n| match n.checked_sub(DELAY) {
+            Some(m) => (reference[m] * ECHO_GAIN * 32768.0) as i16,
+            None => 0,
+        })
+        .collect();
+
+    let mut canceller = NlmsCanceller::new(32, 0.5);
+    let mut output = Vec::new();
+    for (mic, reference) in echo.chunks(512).zip(reference.chunks(512)) {
+        let mut mic = mic.to_vec();
+        canceller.process(&mut mic, reference);
+        output.extend(mic);
+    }
+
+    // converged: more than 20 dB of attenuation over the last second
+    let tail = output.len() - 16000;
+    assert!(rms(&output[tail..]) < rms(&echo[tail..]) / 10.0);
+}
+
+#[test]
+fn test_passes_mic_without_playback() {
+    let mut canceller = NlmsCanceller::new(32, 0.5);
+
+    let voice: Vec<i16> = noise(512).iter().map(|&s| (s * 20000.0) as i16).collect();
+    let mut mic = voice.clone();
+    canceller.process(&mut mic, &[0.0; 512]);
+
+    assert_eq!(mic, voice);
+}
@@ -1,28 +1,36 @@
-mod simple;
+mod agc;
 
-use once_cell::sync::OnceCell;
-use std::sync::Mutex;
+use super::{AudioStage, ProcessedAudio};
+use crate::db::structs::AgcSettings;
 
-static NORMALIZER: OnceCell<Mutex<simple::GainNormalizer>> = OnceCell::new();
+pub struct GainStage {
+    agc: agc::Agc,
+}
 
-pub fn init() {
-    if NORMALIZER.get().is_some() {
-        return;
-    }
+impl GainStage {
+    pub fn new(settings: &AgcSettings, sample_rate: u32) -> Self {
+        info!(
+            "Gain normalizer: AGC (target {}, gain {}..{}, attack {}ms, release {}ms, gate {})",
+            settings.target_rms, settings.min_gain, settings.max_gain,
+            settings.attack_ms, settings.release_ms, settings.gate_threshold
+        );
 
-    NORMALIZER.set(Mutex::new(simple::GainNormalizer::new())).ok();
-    info!("Gain normalizer: enabled");
+        Self {
+            agc: agc::Agc::new(settings, sample_rate),
+        }
+    }
 }
 
-pub fn normalize(input: &[i16]) -> Vec<i16> {
-    match NORMALIZER.get() {
-        Some(n) => n.lock().unwrap().normalize(input),
-        None => input.to_vec(),
+impl AudioStage for GainStage {
+    fn name(&self) -> &str {
+        "gain"
     }
-}
 
-pub fn reset() {
-    if let Some(n) = NORMALIZER.get() {
-        n.lock().unwrap().reset();
+    fn process(&mut self, frame: &mut ProcessedAudio) {
+        frame.samples = self.agc.process(&frame.samples);
     }
-}
\ No newline at end of file
+
+    fn reset(&mut self) {
+        self.agc.reset();
+    }
+}
@@ -0,0 +1,107 @@
+#[cfg(test)]
+mod tests;
+
+use crate::db::structs::AgcSettings;
+
+// Automatic gain control:
+// - RMS envelope follower with separate attack / release time constants
+// - gain is only raised above 1.0 while the envelope is above the noise gate
+// - gain changes are ramped across the frame (no zipper noise)
+// - soft limiter instead of hard clipping on loud onsets
+pub struct Agc {
+    settings: AgcSettings,
+    sample_rate: f32,
+
+    envelope: f32,
+    gain: f32,
+}
+
+impl Agc {
+    pub fn new(settings: &AgcSettings, sample_rate: u32) -> Self {
+        let mut settings = settings.clone();
+        settings.min_gain = settings.min_gain.max(0.0);
+        settings.max_gain = settings.max_gain.max(settings.min_gain);
+        settings.limiter_threshold = settings.limiter_threshold.clamp(0.1, 1.0);
+
+        Self {
+            settings,
+            sample_rate: sample_rate.max(1) as f32,
+
+            envelope: 0.0,
+            gain: 1.0,
+        }
+    }
+
+    pub fn process(&mut self, input: &[i16]) -> Vec<i16> {
+        if input.is_empty() {
+            return Vec::new();
+        }
+
+        let frame_ms = input.len() as f32 * 1000.0 / self.sample_rate;
+        let rms = calculate_rms(input);
+
+        // envelope: fast up, slow down
+        let tau = if rms > self.envelope { self.settings.attack_ms } else { self.settings.release_ms };
+        self.envelope += smoothing(frame_ms, tau) * (rms - self.envelope);
+
+        // noise gate: never amplify silence
+        let desired = if self.envelope < self.settings.gate_threshold.max(1.0) {
+            self.gain.clamp(self.settings.min_gain, 1.0_f32.max(self.settings.min_gain))
+        } else {
+            (self.settings.target_rms / self.envelope).clamp(self.settings.min_gain, self.settings.max_gain)
+        };
+
+        // gain: drop fast (attack), recover slowly (release)
+        let tau = if desired < self.gain { self.settings.attack_ms } else { self.settings.release_ms };
+        let new_gain = self.gain + smoothing(frame_ms, tau) * (desired - self.gain);
+
+        let step = (new_gain - self.gain) / input.len() as f32;
+        let threshold = self.settings.limiter_threshold * i16::MAX as f32;
+
+        let output = input.iter()
+            .enumerate()
+            .map(|(i, &s)| {
+                let gain = self.gain + step * (i + 1) as f32;
+                soft_limit(s as f32 * gain, threshold) as i16
+            })
+            .collect();
+
+        self.gain = new_gain;
+        output
+    }
+
+    pub fn reset(&mut self) {
+        self.envelope = 0.0;
+        self.gain = 1.0;
+    }
+}
+
+// one-pole coefficient for a frame of `frame_ms` and time constant `tau_ms`
+fn smoothing(frame_ms: f32, tau_ms: f32) -> f32 {
+    if tau_ms <= 0.0 {
+        return 1.0;
+    }
+    1.0 - (-frame_ms / tau_ms).exp()
+}
+
+// linear below threshold, tanh knee above (never exceeds full scale)
+fn soft_limit(x: f32, threshold: f32) -> f32 {
+    let full = i16::MAX as f32;
+    let abs = x.abs();
+
+    if abs <= threshold || threshold >= full {
+        return x.clamp(-full, full);
+    }
+
+    let headroom = full - threshold;
+    let limited = threshold + headroom * ((abs - threshold) / headroom).tanh();
+    limited.copysign(x)
+}
+
+fn calculate_rms(samples: &[i16]) -> f32 {
+    let sum: f64 = samples.iter()
+        .map(|&s| (s as f64).powi(2))
+        .sum();
+
+    (sum / samples.len() as f64).sqrt() as f32
+}
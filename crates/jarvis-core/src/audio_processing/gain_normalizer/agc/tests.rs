@@ -0,0 +1,67 @@
+use crate::audio_processing::gain_normalizer::agc::Agc;
+use crate::db::structs::AgcSettings;
+
+// square wave, its RMS equals the amplitude
+fn frame(amplitude: i16) -> Vec<i16> {
+    (0..512).map(|i| if i % 2 == 0 { amplitude } else { -amplitude }).collect()
+}
+
+fn rms(samples: &[i16]) -> f32 {
+    (samples.iter().map(|&s| (s as f32).powi(2)).sum::<f32>() / samples.len() as f32).sqrt()
+}
+
+// target 3000, gain 0.5..3, attack 10ms, release 500ms, gate 150, limiter at 0.8 of full scale
+fn agc() -> Agc {
+    Agc::new(&AgcSettings::default(), 16000)
+}
+
+#[test]
+fn test_converges_to_target() {
+    let mut agc = agc();
+
+    // quiet voice is raised to the target...
+    let output: Vec<Vec<i16>> = (0..200).map(|_| agc.process(&frame(1200))).collect();
+    assert!(rms(&output[0]) < 1500.0, "gain ramps up, no jump");
+    assert!((rms(&output[199]) - 3000.0).abs() < 100.0);
+
+    // ...loud voice is lowered to it
+    let output: Vec<Vec<i16>> = (0..50).map(|_| agc.process(&frame(6000))).collect();
+    assert!((rms(&output[49]) - 3000.0).abs() < 100.0);
+
+    // the gain range is respected
+    let output: Vec<Vec<i16>> = (0..300).map(|_| agc.process(&frame(400))).collect();
+    assert!((rms(&output[299]) - 1200.0).abs() < 30.0);
+}
+
+#[test]
+fn test_does_not_amplify_silence() {
+    let mut agc = agc();
+
+    for _ in 0..300 {
+        assert!(rms(&agc.process(&frame(100))) <= 100.0);
+    }
+}
+
+#[test]
+fn test_limits_loud_onsets() {
+    let mut agc = agc();
+
+    // gain is at its maximum after a quiet stretch
+    for _ in 0..300 {
+        agc.process(&frame(400));
+    }
+
+    // a shout is soft-limited, no wrap-around or flipped samples
+    let input = frame(20000);
+    let output = agc.process(&input);
+    assert!(output.iter().zip(&input).all(|(&o, &i)| o.signum() == i.signum()));
+    assert!(output.iter().all(|&s| s.unsigned_abs() < i16::MAX as u16));
+    assert!(output.iter().any(|&s| s.unsigned_abs() > 26214));
+
+    // and the gain drops within a few frames (attack)
+    let output: Vec<Vec<i16>> = (0..5).map(|_| agc.process(&input)).collect();
+    assert!((rms(&output[4]) - 10000.0).abs() < 500.0);
+
+    agc.reset();
+    assert_eq!(agc.process(&frame(100)), frame(100));
+}
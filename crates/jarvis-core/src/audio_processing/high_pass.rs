@@ -0,0 +1,73 @@
+#[cfg(test)]
+mod tests;
+
+use std::f32::consts::PI;
+
+use super::{AudioStage, ProcessedAudio};
+
+// 2nd order Butterworth high-pass (removes rumble / DC below the voice band)
+pub struct HighPassStage {
+    b0: f32,
+    b1: f32,
+    b2: f32,
+    a1: f32,
+    a2: f32,
+
+    x1: f32,
+    x2: f32,
+    y1: f32,
+    y2: f32,
+}
+
+impl HighPassStage {
+    pub fn new(cutoff_hz: f32, sample_rate: u32) -> Self {
+        let cutoff = cutoff_hz.clamp(1.0, sample_rate as f32 / 2.0 - 1.0);
+        let w0 = 2.0 * PI * cutoff / sample_rate as f32;
+        let alpha = w0.sin() / (2.0 * std::f32::consts::FRAC_1_SQRT_2);
+        let cos = w0.cos();
+        let a0 = 1.0 + alpha;
+
+        info!("High-pass filter: {} Hz", cutoff);
+
+        Self {
+            b0: (1.0 + cos) / 2.0 / a0,
+            b1: -(1.0 + cos) / a0,
+            b2: (1.0 + cos) / 2.0 / a0,
+            a1: -2.0 * cos / a0,
+            a2: (1.0 - alpha) / a0,
+
+            x1: 0.0,
+            x2: 0.0,
+            y1: 0.0,
+            y2: 0.0,
+        }
+    }
+}
+
+impl AudioStage for HighPassStage {
+    fn name(&self) -> &str {
+        "high_pass"
+    }
+
+    fn process(&mut self, frame: &mut ProcessedAudio) {
+        for sample in frame.samples.iter_mut() {
+            let x = *sample as f32;
+            let y = self.b0 * x + self.b1 * self.x1 + self.b2 * self.x2
+                - self.a1 * self.y1 - self.a2 * self.y2;
+
+            self.x2 = self.x1;
+            self.x1 = x;
+            self.y2 = self.y1;
+            self.y1 = y;
+
+            *sample = y.clamp(i16::MIN as f32, i16::MAX as f32) as i16;
+        }
+    }
+
+    fn reset(&mut self) {
+        self.x1 = 0.0;
+        self.x2 = 0.0;
+        self.y1 = 0.0;
+        self.y2 = 0.0;
+    }
+}
@@ -0,0 +1,54 @@
+use std::f32::consts::PI;
+
+use crate::audio_processing::high_pass::HighPassStage;
+use crate::audio_processing::{AudioStage, ProcessedAudio};
+
+fn run(stage: &mut HighPassStage, samples: &[i16]) -> Vec<i16> {
+    let mut output = Vec::new();
+    for chunk in samples.chunks(512) {
+        let mut frame = ProcessedAudio { samples: chunk.to_vec(), is_voice: true, vad_confidence: 1.0 };
+        stage.process(&mut frame);
+        output.extend(frame.samples);
+    }
+    output
+}
+
+fn rms(samples: &[i16]) -> f32 {
+    (samples.iter().map(|&s| (s as f32).powi(2)).sum::<f32>() / samples.len() as f32).sqrt()
+}
+
+#[test]
+fn test_removes_dc_offset() {
+    let mut stage = HighPassStage::new(80.0, 16000);
+
+    let output = run(&mut stage, &[5000; 16000]);
+
+    // settles within a few hundred ms, nothing of the offset left
+    assert!(output[8000..].iter().all(|s| s.abs() <= 2));
+}
+
+#[test]
+fn test_passes_voice_band() {
+    let mut stage = HighPassStage::new(80.0, 16000);
+
+    let tone: Vec<i16> = (0..16000).map(|n| ((2.0 * PI * 1000.0 * n as f32 / 16000.0).sin() * 10000.0) as i16).collect();
+    let output = run(&mut stage, &tone);
+
+    let ratio = rms(&output[8000..]) / rms(&tone[8000..]);
+    assert!((0.95..1.05).contains(&ratio), "1 kHz passed at {}", ratio);
+
+    // DC under the tone is removed, the tone is kept
+    let biased: Vec<i16> = tone.iter().map(|&s| s + 3000).collect();
+    let output = run(&mut stage, &biased);
+    let mean = output[8000..].iter().map(|&s| s as f32).sum::<f32>() / 8000.0;
+    assert!(mean.abs() < 20.0);
+}
+
+#[test]
+fn test_reset_clears_state() {
+    let mut stage = HighPassStage::new(80.0, 16000);
+    run(&mut stage, &[20000; 512]);
+
+    stage.reset();
+    assert!(run(&mut stage, &[0; 512]).iter().all(|&s| s == 0));
+}
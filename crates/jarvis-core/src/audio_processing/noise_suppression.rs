@@ -1,66 +1,25 @@
-mod none;
-
 #[cfg(feature = "nnnoiseless")]
 mod nnnoiseless;
 
-use once_cell::sync::OnceCell;
-use std::sync::Mutex;
-
+use super::AudioStage;
 use crate::config::structs::NoiseSuppressionBackend;
 
-static BACKEND: OnceCell<NoiseSuppressionBackend> = OnceCell::new();
-
-#[cfg(feature = "nnnoiseless")]
-static NNNOISELESS_STATE: OnceCell<Mutex<nnnoiseless::NnnoiselessNS>> = OnceCell::new();
-
-pub fn init(backend: NoiseSuppressionBackend) {
-    if BACKEND.get().is_some() {
-        return;
-    }
-
-    BACKEND.set(backend).ok();
-
+// None if noise suppression is disabled (or not compiled in)
+pub fn create(backend: NoiseSuppressionBackend) -> Option<Box<dyn AudioStage>> {
     match backend {
         NoiseSuppressionBackend::None => {
             info!("Noise suppression: disabled");
+            None
         }
         #[cfg(feature = "nnnoiseless")]
         NoiseSuppressionBackend::Nnnoiseless => {
-            NNNOISELESS_STATE.set(Mutex::new(nnnoiseless::NnnoiselessNS::new())).ok();
             info!("Noise suppression: Nnnoiseless");
+            Some(Box::new(nnnoiseless::NnnoiselessNS::new()))
         }
         #[cfg(not(feature = "nnnoiseless"))]
         NoiseSuppressionBackend::Nnnoiseless => {
-            warn!("Nnnoiseless not compiled in, falling back to None");
-            BACKEND.set(NoiseSuppressionBackend::None).ok();
-        }
-    }
-}
-
-pub fn process(input: &[i16]) -> Vec<i16> {
-    match BACKEND.get() {
-        Some(NoiseSuppressionBackend::None) | None => none::process(input),
-        #[cfg(feature = "nnnoiseless")]
-        Some(NoiseSuppressionBackend::Nnnoiseless) => {
-            if let Some(state) = NNNOISELESS_STATE.get() {
-                state.lock().unwrap().process(input)
-            } else {
-                none::process(input)
-            }
+            warn!("Nnnoiseless not compiled in, noise suppression disabled");
+            None
         }
-        #[cfg(not(feature = "nnnoiseless"))]
-        Some(NoiseSuppressionBackend::Nnnoiseless) => none::process(input),
     }
 }
-
-pub fn reset() {
-    match BACKEND.get() {
-        #[cfg(feature = "nnnoiseless")]
-        Some(NoiseSuppressionBackend::Nnnoiseless) => {
-            if let Some(state) = NNNOISELESS_STATE.get() {
-                state.lock().unwrap().reset();
-            }
-        }
-        _ => {}
-    }
-}
\ No newline at end of file
@@ -1,53 +1,48 @@
 use nnnoiseless::DenoiseState;
+use crate::audio_processing::reframer::Reframer;
+use crate::audio_processing::{AudioStage, ProcessedAudio};
 use crate::config;
 
 pub struct NnnoiselessNS {
     state: Box<DenoiseState<'static>>,
-    buffer: Vec<f32>,
+    // recorder frames (512) -> nnnoiseless frames (480)
+    reframer: Reframer,
 }
 
 impl NnnoiselessNS {
     pub fn new() -> Self {
         Self {
             state: DenoiseState::new(),
-            buffer: Vec::with_capacity(config::NNNOISELESS_FRAME_SIZE * 2),
+            reframer: Reframer::new(config::NNNOISELESS_FRAME_SIZE),
         }
     }
 
-    pub fn process(&mut self, input: &[i16]) -> Vec<i16> {
-        for &sample in input {
-            self.buffer.push(sample as f32);
-        }
-
-        let mut output: Vec<i16> = Vec::with_capacity(input.len());
-
-        while self.buffer.len() >= config::NNNOISELESS_FRAME_SIZE {
-            let mut input_frame = [0.0f32; 480];
-            let mut output_frame = [0.0f32; 480];
-            
-            input_frame.copy_from_slice(&self.buffer[..config::NNNOISELESS_FRAME_SIZE]);
-            self.buffer.drain(..config::NNNOISELESS_FRAME_SIZE);
+    fn denoise(&mut self, input: &[i16]) -> Vec<i16> {
+        let samples: Vec<f32> = input.iter().map(|&s| s as f32).collect();
 
+        let state = &mut self.state;
+        let output = self.reframer.process(&samples, |chunk, out| {
             // process: input -> output (denoised)
-            let _ = self.state.process_frame(&mut output_frame, &input_frame);
-
-            for &sample in &output_frame {
-                let clamped = sample.clamp(i16::MIN as f32, i16::MAX as f32);
-                output.push(clamped as i16);
-            }
-        }
+            let _ = state.process_frame(out, chunk);
+        });
 
-        if output.is_empty() {
-            return input.to_vec();
-        }
+        output.iter()
+            .map(|&s| s.clamp(i16::MIN as f32, i16::MAX as f32) as i16)
+            .collect()
+    }
+}
 
-        output
+impl AudioStage for NnnoiselessNS {
+    fn name(&self) -> &str {
+        "denoise"
     }
 
-    pub fn reset(&mut self) {
-        // self.state = DenoiseState::new();
-        // self.buffer.clear();
+    fn process(&mut self, frame: &mut ProcessedAudio) {
+        frame.samples = self.denoise(&frame.samples);
+    }
 
-        self.buffer.clear();
+    fn reset(&mut self) {
+        // keep the denoiser's noise model, only drop buffered audio
+        self.reframer.reset();
     }
-}
\ No newline at end of file
+}
@@ -1,4 +0,0 @@
-// return unprocessed input
-pub fn process(input: &[i16]) -> Vec<i16> {
-    input.to_vec()
-}
\ No newline at end of file
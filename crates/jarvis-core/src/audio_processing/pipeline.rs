@@ -0,0 +1,157 @@
+#[cfg(test)]
+mod tests;
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+
+use super::{echo_cancellation, noise_suppression, AudioStage, ProcessedAudio};
+use super::gain_normalizer::GainStage;
+use super::high_pass::HighPassStage;
+use super::vad::VadStage;
+use crate::config::structs::AudioStageConfig;
+use crate::db::structs::{AgcSettings, VadSettings};
+
+pub type StageFactory = fn() -> Box<dyn AudioStage>;
+
+// stages provided outside of audio_processing, referenced as `custom` in config
+static CUSTOM_STAGES: Lazy<Mutex<HashMap<String, StageFactory>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+pub fn register_custom_stage(name: &str, factory: StageFactory) {
+    CUSTOM_STAGES.lock().insert(name.to_string(), factory);
+}
+
+// everything stages may need to be built
+#[derive(Debug, Clone)]
+pub struct PipelineSettings {
+    pub sample_rate: u32,
+    pub vad: VadSettings,
+    pub agc: AgcSettings,
+}
+
+impl Default for PipelineSettings {
+    fn default() -> Self {
+        Self {
+            sample_rate: crate::config::AUDIO_PROCESSING_SAMPLE_RATE,
+            vad: VadSettings::default(),
+            agc: AgcSettings::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct StageStats {
+    pub name: String,
+    pub calls: u64,
+    pub total: Duration,
+    pub max: Duration,
+}
+
+impl StageStats {
+    pub fn average(&self) -> Duration {
+        if self.calls == 0 {
+            return Duration::ZERO;
+        }
+        self.total / self.calls as u32
+    }
+}
+
+pub struct AudioPipeline {
+    stages: Vec<Box<dyn AudioStage>>,
+    stats: Vec<StageStats>,
+}
+
+impl AudioPipeline {
+    pub fn new() -> Self {
+        Self {
+            stages: Vec::new(),
+            stats: Vec::new(),
+        }
+    }
+
+    pub fn from_config(stages: &[AudioStageConfig], settings: &PipelineSettings) -> Self {
+        let mut pipeline = Self::new();
+
+        for config in stages {
+            let stage: Option<Box<dyn AudioStage>> = match config {
+                AudioStageConfig::HighPass { cutoff_hz } => Some(Box::new(HighPassStage::new(*cutoff_hz, settings.sample_rate))),
+                AudioStageConfig::Gain => Some(Box::new(GainStage::new(&settings.agc, settings.sample_rate))),
+                AudioStageConfig::Denoise { backend } => noise_suppression::create(*backend),
+                AudioStageConfig::EchoCancellation { mode } => echo_cancellation::create(*mode),
+                AudioStageConfig::Vad { backend } => Some(Box::new(VadStage::new(*backend, &settings.vad))),
+                AudioStageConfig::Custom { name } => match CUSTOM_STAGES.lock().get(name) {
+                    Some(factory) => Some(factory()),
+                    None => {
+                        warn!("Unknown custom audio stage '{}', skipping", name);
+                        None
+                    }
+                },
+            };
+
+            if let Some(stage) = stage {
+                pipeline.push(stage);
+            }
+        }
+
+        pipeline
+    }
+
+    pub fn push(&mut self, stage: Box<dyn AudioStage>) {
+        self.stats.push(StageStats {
+            name: stage.name().to_string(),
+            ..Default::default()
+        });
+        self.stages.push(stage);
+    }
+
+    pub fn process(&mut self, input: &[i16]) -> ProcessedAudio {
+        // no VAD stage = everything is voice
+        let mut frame = ProcessedAudio {
+            samples: input.to_vec(),
+            is_voice: true,
+            vad_confidence: 1.0,
+        };
+
+        for (stage, stats) in self.stages.iter_mut().zip(self.stats.iter_mut()) {
+            let started = Instant::now();
+            stage.process(&mut frame);
+            let elapsed = started.elapsed();
+
+            stats.calls += 1;
+            stats.total += elapsed;
+            stats.max = stats.max.max(elapsed);
+        }
+
+        frame
+    }
+
+    pub fn reset(&mut self) {
+        for stage in self.stages.iter_mut() {
+            stage.reset();
+        }
+    }
+
+    pub fn stage_names(&self) -> Vec<&str> {
+        self.stages.iter().map(|s| s.name()).collect()
+    }
+
+    pub fn stats(&self) -> &[StageStats] {
+        &self.stats
+    }
+
+    pub fn clear_stats(&mut self) {
+        for stats in self.stats.iter_mut() {
+            stats.calls = 0;
+            stats.total = Duration::ZERO;
+            stats.max = Duration::ZERO;
+        }
+    }
+}
+
+impl Default for AudioPipeline {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,117 @@
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+
+use crate::audio_processing::pipeline::{register_custom_stage, AudioPipeline, PipelineSettings};
+use crate::audio_processing::{AudioStage, ProcessedAudio};
+use crate::config::structs::{AudioStageConfig, VadBackend};
+
+struct AddOne;
+
+impl AudioStage for AddOne {
+    fn name(&self) -> &str {
+        "add_one"
+    }
+
+    fn process(&mut self, frame: &mut ProcessedAudio) {
+        frame.samples.iter_mut().for_each(|s| *s += 1);
+    }
+
+    fn reset(&mut self) {}
+}
+
+struct Double;
+
+impl AudioStage for Double {
+    fn name(&self) -> &str {
+        "double"
+    }
+
+    fn process(&mut self, frame: &mut ProcessedAudio) {
+        frame.samples.iter_mut().for_each(|s| *s *= 2);
+    }
+
+    fn reset(&mut self) {}
+}
+
+// records what the pipeline did to it
+struct Recorder {
+    name: &'static str,
+    log: Arc<Mutex<Vec<String>>>,
+}
+
+impl AudioStage for Recorder {
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn process(&mut self, frame: &mut ProcessedAudio) {
+        self.log.lock().push(format!("process {}", self.name));
+        frame.is_voice = false;
+    }
+
+    fn reset(&mut self) {
+        self.log.lock().push(format!("reset {}", self.name));
+    }
+}
+
+#[test]
+fn test_stage_order() {
+    let mut pipeline = AudioPipeline::new();
+    pipeline.push(Box::new(AddOne));
+    pipeline.push(Box::new(Double));
+    assert_eq!(pipeline.process(&[1, 2]).samples, [4, 6]);
+
+    let mut pipeline = AudioPipeline::new();
+    pipeline.push(Box::new(Double));
+    pipeline.push(Box::new(AddOne));
+    assert_eq!(pipeline.process(&[1, 2]).samples, [3, 5]);
+
+    // without a VAD stage everything is voice
+    let frame = AudioPipeline::new().process(&[7]);
+    assert_eq!(frame.samples, [7]);
+    assert!(frame.is_voice);
+}
+
+#[test]
+fn test_from_config_keeps_order() {
+    register_custom_stage("test_add_one", || Box::new(AddOne));
+
+    let stages = [
+        AudioStageConfig::Custom { name: "test_add_one".into() },
+        AudioStageConfig::HighPass { cutoff_hz: 80.0 },
+        AudioStageConfig::Custom { name: "test_missing".into() },
+        AudioStageConfig::Gain,
+        AudioStageConfig::Vad { backend: VadBackend::Energy },
+    ];
+    let pipeline = AudioPipeline::from_config(&stages, &PipelineSettings::default());
+
+    // unknown custom stages are skipped
+    assert_eq!(pipeline.stage_names(), ["add_one", "high_pass", "gain", "vad"]);
+}
+
+#[test]
+fn test_reset_and_stats() {
+    let log = Arc::new(Mutex::new(Vec::new()));
+
+    let mut pipeline = AudioPipeline::new();
+    pipeline.push(Box::new(Recorder { name: "first", log: log.clone() }));
+    pipeline.push(Box::new(Recorder { name: "second", log: log.clone() }));
+
+    for _ in 0..3 {
+        assert!(!pipeline.process(&[0; 512]).is_voice);
+    }
+    pipeline.reset();
+
+    assert_eq!(log.lock().len(), 8);
+    assert_eq!(log.lock()[..2], ["process first", "process second"]);
+    assert_eq!(log.lock()[6..], ["reset first", "reset second"]);
+
+    let stats = pipeline.stats();
+    assert_eq!(stats.iter().map(|s| s.name.as_str()).collect::<Vec<_>>(), ["first", "second"]);
+    assert!(stats.iter().all(|s| s.calls == 3 && s.max <= s.total && s.average() <= s.max));
+
+    pipeline.clear_stats();
+    assert!(pipeline.stats().iter().all(|s| s.calls == 0 && s.total.is_zero()));
+    assert_eq!(pipeline.stats()[0].average(), std::time::Duration::ZERO);
+}
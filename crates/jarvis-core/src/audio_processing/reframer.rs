@@ -0,0 +1,86 @@
+#[cfg(test)]
+mod tests;
+
+use std::collections::VecDeque;
+
+// Adapts arbitrary input sizes (e.g. 512-sample recorder frames)
+// to stages that work on fixed-size chunks (e.g. 480 for nnnoiseless).
+//
+// `process` always returns exactly as many samples as it was given.
+// The output queue is primed with one chunk of silence, so latency is
+// exactly `chunk_size` samples and never grows.
+pub struct Reframer {
+    chunk_size: usize,
+    input: Vec<f32>,
+    output: VecDeque<f32>,
+}
+
+impl Reframer {
+    pub fn new(chunk_size: usize) -> Self {
+        let chunk_size = chunk_size.max(1);
+
+        Self {
+            chunk_size,
+            input: Vec::with_capacity(chunk_size * 2),
+            output: VecDeque::from(vec![0.0; chunk_size]),
+        }
+    }
+
+    pub fn chunk_size(&self) -> usize {
+        self.chunk_size
+    }
+
+    // in samples
+    pub fn latency(&self) -> usize {
+        self.chunk_size
+    }
+
+    // Transforming stages: `f(input_chunk, output_chunk)` is called for every full chunk.
+    pub fn process<F>(&mut self, input: &[f32], mut f: F) -> Vec<f32>
+    where
+        F: FnMut(&[f32], &mut [f32]),
+    {
+        self.input.extend_from_slice(input);
+
+        let mut out_chunk = vec![0.0f32; self.chunk_size];
+        let mut consumed = 0;
+
+        while self.input.len() - consumed >= self.chunk_size {
+            f(&self.input[consumed..consumed + self.chunk_size], &mut out_chunk);
+            self.output.extend(out_chunk.iter().copied());
+            consumed += self.chunk_size;
+        }
+        self.input.drain(..consumed);
+
+        // queue + pending input always hold chunk_size + input.len() samples,
+        // and pending input is < chunk_size, so there is always enough to drain
+        self.output.drain(..input.len()).collect()
+    }
+
+    // Analysis-only stages (VAD): `f(chunk)` is called for every full chunk, no output.
+    // Returns the number of chunks processed.
+    pub fn analyze<F>(&mut self, input: &[f32], mut f: F) -> usize
+    where
+        F: FnMut(&[f32]),
+    {
+        self.input.extend_from_slice(input);
+
+        let mut chunks = 0;
+        let mut consumed = 0;
+
+        while self.input.len() - consumed >= self.chunk_size {
+            f(&self.input[consumed..consumed + self.chunk_size]);
+            consumed += self.chunk_size;
+            chunks += 1;
+        }
+        self.input.drain(..consumed);
+
+        chunks
+    }
+
+    pub fn reset(&mut self) {
+        self.input.clear();
+        self.output.clear();
+        self.output.resize(self.chunk_size, 0.0);
+    }
+}
@@ -0,0 +1,91 @@
+use crate::audio_processing::reframer::Reframer;
+
+fn ramp(len: usize, offset: usize) -> Vec<f32> {
+    (offset..offset + len).map(|i| i as f32).collect()
+}
+
+#[test]
+fn test_output_length_matches_input() {
+    let mut reframer = Reframer::new(480);
+
+    for len in [512, 1, 479, 480, 481, 0, 1024, 7] {
+        let out = reframer.process(&vec![1.0; len], |i, o| o.copy_from_slice(i));
+        assert_eq!(out.len(), len);
+    }
+}
+
+#[test]
+fn test_passthrough_is_sample_exact() {
+    let mut reframer = Reframer::new(480);
+
+    // 512-sample recorder frames through a 480-sample identity stage
+    let input = ramp(512 * 20, 1);
+    let mut output = Vec::new();
+    for frame in input.chunks(512) {
+        output.extend(reframer.process(frame, |i, o| o.copy_from_slice(i)));
+    }
+
+    let latency = reframer.latency();
+    assert_eq!(output.len(), input.len());
+    assert!(output[..latency].iter().all(|&s| s == 0.0));
+    assert_eq!(&output[latency..], &input[..input.len() - latency]);
+}
+
+#[test]
+fn test_passthrough_with_irregular_sizes() {
+    let mut reframer = Reframer::new(64);
+
+    let sizes = [3, 100, 64, 1, 200, 63, 65, 0, 128, 17];
+    let total: usize = sizes.iter().sum();
+    let input = ramp(total, 1);
+
+    let mut output = Vec::new();
+    let mut pos = 0;
+    for size in sizes {
+        output.extend(reframer.process(&input[pos..pos + size], |i, o| o.copy_from_slice(i)));
+        pos += size;
+    }
+
+    assert_eq!(output.len(), total);
+    assert!(output[..64].iter().all(|&s| s == 0.0));
+    assert_eq!(&output[64..], &input[..total - 64]);
+}
+
+#[test]
+fn test_chunks_are_fixed_size_and_contiguous() {
+    let mut reframer = Reframer::new(480);
+    let input = ramp(512 * 15, 0);
+
+    let mut seen = Vec::new();
+    for frame in input.chunks(512) {
+        reframer.process(frame, |i, o| {
+            assert_eq!(i.len(), 480);
+            seen.extend_from_slice(i);
+            o.copy_from_slice(i);
+        });
+    }
+
+    // every chunk continues where the previous one stopped
+    assert_eq!(seen.len(), 480 * 16);
+    assert_eq!(&seen[..], &input[..seen.len()]);
+}
+
+#[test]
+fn test_analyze_counts_chunks() {
+    let mut reframer = Reframer::new(480);
+
+    assert_eq!(reframer.analyze(&[0.0; 512], |c| assert_eq!(c.len(), 480)), 1);
+    assert_eq!(reframer.analyze(&[0.0; 400], |_| {}), 0);
+    assert_eq!(reframer.analyze(&[0.0; 600], |_| {}), 2);
+}
+
+#[test]
+fn test_reset_restores_latency() {
+    let mut reframer = Reframer::new(4);
+
+    reframer.process(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0], |i, o| o.copy_from_slice(i));
+    reframer.reset();
+
+    let out = reframer.process(&[7.0, 8.0, 9.0, 10.0, 11.0], |i, o| o.copy_from_slice(i));
+    assert_eq!(out, vec![0.0, 0.0, 0.0, 0.0, 7.0]);
+}
@@ -0,0 +1,13 @@
+use super::ProcessedAudio;
+
+// A single DSP step of the audio pipeline.
+// Stages own their state, so several pipelines can run side by side.
+pub trait AudioStage: Send {
+    fn name(&self) -> &str;
+
+    // transform samples in place and/or annotate the frame (e.g. VAD)
+    fn process(&mut self, frame: &mut ProcessedAudio);
+
+    // drop internal state between utterances
+    fn reset(&mut self);
+}
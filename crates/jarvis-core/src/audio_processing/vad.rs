@@ -4,69 +4,141 @@ mod energy;
 #[cfg(feature = "nnnoiseless")]
 mod nnnoiseless;
 
-use once_cell::sync::OnceCell;
-use std::sync::Mutex;
+#[cfg(all(feature = "ort", feature = "ndarray"))]
+mod silero;
 
+use super::{AudioStage, ProcessedAudio};
 use crate::config::structs::VadBackend;
+use crate::db::structs::VadSettings;
 
-static BACKEND: OnceCell<VadBackend> = OnceCell::new();
+#[allow(unused_imports)]
+use crate::{config, i18n, notify};
+#[allow(unused_imports)]
+use crate::models::{self, ModelKind};
 
-#[cfg(feature = "nnnoiseless")]
-static NNNOISELESS_STATE: OnceCell<Mutex<nnnoiseless::NnnoiselessVAD>> = OnceCell::new();
+pub struct VadStage {
+    backend: VadBackend,
 
-pub fn init(backend: VadBackend) {
-    if BACKEND.get().is_some() {
-        return;
-    }
+    // energy VAD is also the fallback for every other backend
+    energy: energy::EnergyVAD,
 
-    BACKEND.set(backend).ok();
+    #[cfg(feature = "nnnoiseless")]
+    nnnoiseless: Option<nnnoiseless::NnnoiselessVAD>,
 
-    match backend {
-        VadBackend::None => {
-            info!("VAD: disabled");
-        }
-        VadBackend::Energy => {
-            info!("VAD: Energy-based");
-        }
-        #[cfg(feature = "nnnoiseless")]
-        VadBackend::Nnnoiseless => {
-            NNNOISELESS_STATE.set(Mutex::new(nnnoiseless::NnnoiselessVAD::new())).ok();
-            info!("VAD: Nnnoiseless");
-        }
-        #[cfg(not(feature = "nnnoiseless"))]
-        VadBackend::Nnnoiseless => {
-            warn!("Nnnoiseless not compiled in, falling back to Energy");
-            BACKEND.set(VadBackend::Energy).ok();
-        }
-    }
+    #[cfg(all(feature = "ort", feature = "ndarray"))]
+    silero: Option<silero::SileroVAD>,
 }
 
-// Returns (is_voice, confidence)
-pub fn detect(input: &[i16]) -> (bool, f32) {
-    match BACKEND.get() {
-        Some(VadBackend::None) | None => none::detect(input),
-        Some(VadBackend::Energy) => energy::detect(input),
-        #[cfg(feature = "nnnoiseless")]
-        Some(VadBackend::Nnnoiseless) => {
-            if let Some(state) = NNNOISELESS_STATE.get() {
-                state.lock().unwrap().detect(input)
-            } else {
-                energy::detect(input)
+impl VadStage {
+    pub fn new(backend: VadBackend, settings: &VadSettings) -> Self {
+        let mut stage = Self {
+            backend,
+            energy: energy::EnergyVAD::new(settings),
+
+            #[cfg(feature = "nnnoiseless")]
+            nnnoiseless: None,
+
+            #[cfg(all(feature = "ort", feature = "ndarray"))]
+            silero: None,
+        };
+
+        match backend {
+            VadBackend::None => {
+                info!("VAD: disabled");
+            }
+            VadBackend::Energy => {
+                info!(
+                    "VAD: Energy-based (min threshold {}, ratio {}, attack {}, hangover {})",
+                    settings.energy_min_threshold, settings.energy_ratio,
+                    settings.attack_frames, settings.hangover_frames
+                );
+            }
+            #[cfg(feature = "nnnoiseless")]
+            VadBackend::Nnnoiseless => {
+                stage.nnnoiseless = Some(nnnoiseless::NnnoiselessVAD::new());
+                info!("VAD: Nnnoiseless");
+            }
+            #[cfg(not(feature = "nnnoiseless"))]
+            VadBackend::Nnnoiseless => {
+                warn!("Nnnoiseless not compiled in, falling back to Energy");
+                stage.backend = VadBackend::Energy;
             }
+            #[cfg(all(feature = "ort", feature = "ndarray"))]
+            VadBackend::Silero => {
+                match models::resolve(ModelKind::Vad, &i18n::get_language()) {
+                    Some(entry) => {
+                        let model_path = entry.dir().join(config::SILERO_VAD_MODEL_FILE);
+                        match models::verify(entry).and_then(|_| silero::SileroVAD::new(&model_path)) {
+                            Ok(vad) => {
+                                stage.silero = Some(vad);
+                                info!("VAD: Silero ({})", model_path.display());
+                            }
+                            Err(e) => {
+                                warn!("Silero VAD init failed, falling back to Energy: {}", e);
+                            }
+                        }
+                    }
+                    None => {
+                        // the model is not shipped, tell the user why Silero is not used
+                        warn!("Silero VAD model is not installed, falling back to Energy");
+                        notify::show(&i18n::t("app-name"), &i18n::t("vad-silero-missing"));
+                    }
+                }
+            }
+            #[cfg(not(all(feature = "ort", feature = "ndarray")))]
+            VadBackend::Silero => {
+                warn!("Silero VAD (onnxruntime) not compiled in, falling back to Energy");
+                stage.backend = VadBackend::Energy;
+            }
+        }
+
+        stage
+    }
+
+    // Returns (is_voice, confidence)
+    pub fn detect(&mut self, input: &[i16]) -> (bool, f32) {
+        match self.backend {
+            VadBackend::None => none::detect(input),
+            VadBackend::Energy => self.energy.detect(input),
+            #[cfg(feature = "nnnoiseless")]
+            VadBackend::Nnnoiseless => match self.nnnoiseless.as_mut() {
+                Some(vad) => vad.detect(input),
+                None => self.energy.detect(input),
+            },
+            #[cfg(all(feature = "ort", feature = "ndarray"))]
+            VadBackend::Silero => match self.silero.as_mut() {
+                Some(vad) => vad.detect(input),
+                None => self.energy.detect(input),
+            },
+            #[allow(unreachable_patterns)]
+            _ => self.energy.detect(input),
         }
-        #[cfg(not(feature = "nnnoiseless"))]
-        Some(VadBackend::Nnnoiseless) => energy::detect(input),
     }
 }
 
-pub fn reset() {
-    match BACKEND.get() {
+impl AudioStage for VadStage {
+    fn name(&self) -> &str {
+        "vad"
+    }
+
+    fn process(&mut self, frame: &mut ProcessedAudio) {
+        let (is_voice, confidence) = self.detect(&frame.samples);
+        frame.is_voice = is_voice;
+        frame.vad_confidence = confidence;
+    }
+
+    fn reset(&mut self) {
         #[cfg(feature = "nnnoiseless")]
-        Some(VadBackend::Nnnoiseless) => {
-            if let Some(state) = NNNOISELESS_STATE.get() {
-                state.lock().unwrap().reset();
-            }
+        if let Some(vad) = self.nnnoiseless.as_mut() {
+            vad.reset();
         }
-        _ => {}
+
+        #[cfg(all(feature = "ort", feature = "ndarray"))]
+        if let Some(vad) = self.silero.as_mut() {
+            vad.reset();
+        }
+
+        debug!("VAD noise floor: {:.1}", self.energy.noise_floor());
+        self.energy.reset();
     }
-}
\ No newline at end of file
+}
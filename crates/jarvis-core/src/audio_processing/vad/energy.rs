@@ -1,14 +1,102 @@
-use crate::config;
+#[cfg(test)]
+mod tests;
 
-// Simple energy-based VAD
-pub fn detect(input: &[i16]) -> (bool, f32) {
-    let rms = calculate_rms(input);
-    let is_voice = rms > config::VAD_ENERGY_THRESHOLD;
-    
-    // normalize confidence to 0-1 range (rough approximation)
-    let confidence = (rms / (config::VAD_ENERGY_THRESHOLD * 2.0)).min(1.0);
-    
-    (is_voice, confidence)
+use crate::db::structs::VadSettings;
+
+// Energy-based VAD with an adaptive noise floor.
+// Speech is declared when frame RMS exceeds `noise_floor * energy_ratio`
+// (but never below `energy_min_threshold`) for `attack_frames` frames in a row,
+// and is held for `hangover_frames` after the energy drops.
+pub struct EnergyVAD {
+    min_threshold: f32,
+    ratio: f32,
+    adapt: f32,
+    attack_frames: u32,
+    hangover_frames: u32,
+
+    noise_floor: f32,
+    voiced_run: u32,
+    hangover_left: u32,
+    active: bool,
+}
+
+impl EnergyVAD {
+    pub fn new(settings: &VadSettings) -> Self {
+        let ratio = settings.energy_ratio.max(1.0);
+
+        Self {
+            min_threshold: settings.energy_min_threshold.max(0.0),
+            ratio,
+            adapt: settings.noise_floor_adapt.clamp(0.0, 1.0),
+            attack_frames: settings.attack_frames.max(1),
+            hangover_frames: settings.hangover_frames,
+
+            noise_floor: settings.energy_min_threshold / ratio,
+            voiced_run: 0,
+            hangover_left: 0,
+            active: false,
+        }
+    }
+
+    pub fn detect(&mut self, input: &[i16]) -> (bool, f32) {
+        let rms = calculate_rms(input);
+        let threshold = self.threshold();
+        let above = rms > threshold;
+
+        self.update_noise_floor(rms, above);
+
+        if above {
+            self.voiced_run += 1;
+        } else {
+            self.voiced_run = 0;
+        }
+
+        if self.active {
+            if above {
+                self.hangover_left = self.hangover_frames;
+            } else if self.hangover_left > 0 {
+                self.hangover_left -= 1;
+            } else {
+                self.active = false;
+            }
+        } else if self.voiced_run >= self.attack_frames {
+            self.active = true;
+            self.hangover_left = self.hangover_frames;
+        }
+
+        // normalize confidence to 0-1 range (rough approximation)
+        let confidence = (rms / (threshold * 2.0)).min(1.0);
+
+        (self.active, confidence)
+    }
+
+    pub fn reset(&mut self) {
+        self.voiced_run = 0;
+        self.hangover_left = 0;
+        self.active = false;
+        // noise floor is kept, the room doesn't change between utterances
+    }
+
+    pub fn noise_floor(&self) -> f32 {
+        self.noise_floor
+    }
+
+    fn threshold(&self) -> f32 {
+        (self.noise_floor * self.ratio).max(self.min_threshold)
+    }
+
+    fn update_noise_floor(&mut self, rms: f32, above: bool) {
+        if !above {
+            // track the background in both directions
+            self.noise_floor += self.adapt * (rms - self.noise_floor);
+        } else if rms < self.noise_floor {
+            self.noise_floor = rms;
+        } else {
+            // creep up slowly during "speech", so a new constant noise source
+            // (fan, AC) doesn't keep the VAD open forever
+            self.noise_floor += self.adapt * 0.05 * (rms - self.noise_floor);
+        }
+    }
 }
 
 fn calculate_rms(samples: &[i16]) -> f32 {
@@ -21,4 +109,4 @@ fn calculate_rms(samples: &[i16]) -> f32 {
         .sum();
     
     (sum / samples.len() as f64).sqrt() as f32
-}
\ No newline at end of file
+}
@@ -0,0 +1,84 @@
+use crate::audio_processing::vad::energy::EnergyVAD;
+use crate::db::structs::VadSettings;
+
+// square wave, its RMS equals the amplitude
+fn frame(amplitude: i16) -> Vec<i16> {
+    (0..512).map(|i| if i % 2 == 0 { amplitude } else { -amplitude }).collect()
+}
+
+fn settings(attack_frames: u32, hangover_frames: u32) -> VadSettings {
+    VadSettings {
+        energy_min_threshold: 100.0,
+        energy_ratio: 3.0,
+        noise_floor_adapt: 0.1,
+        attack_frames,
+        hangover_frames,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_attack_and_hangover() {
+    let mut vad = EnergyVAD::new(&settings(3, 5));
+
+    for _ in 0..10 {
+        assert!(!vad.detect(&frame(0)).0);
+    }
+
+    // a blip shorter than the attack is ignored
+    assert!(!vad.detect(&frame(1000)).0);
+    assert!(!vad.detect(&frame(1000)).0);
+    assert!(!vad.detect(&frame(0)).0);
+
+    assert!(!vad.detect(&frame(1000)).0);
+    assert!(!vad.detect(&frame(1000)).0);
+    assert!(vad.detect(&frame(1000)).0);
+
+    // held for the hangover, then released
+    for _ in 0..5 {
+        assert!(vad.detect(&frame(0)).0);
+    }
+    assert!(!vad.detect(&frame(0)).0);
+
+    // speech during the hangover keeps it open
+    for _ in 0..3 {
+        vad.detect(&frame(1000));
+    }
+    for _ in 0..4 {
+        vad.detect(&frame(0));
+    }
+    assert!(vad.detect(&frame(1000)).0);
+    for _ in 0..5 {
+        assert!(vad.detect(&frame(0)).0);
+    }
+
+    vad.reset();
+    assert!(!vad.detect(&frame(0)).0);
+}
+
+#[test]
+fn test_adaptive_noise_floor() {
+    let mut vad = EnergyVAD::new(&settings(2, 5));
+
+    // constant fan noise well above the minimum threshold: detected at first,
+    // then the floor catches up and it stops counting as speech
+    let states: Vec<bool> = (0..400).map(|_| vad.detect(&frame(300)).0).collect();
+    assert!(states[2..10].iter().all(|&active| active));
+    assert!(states[300..].iter().all(|&active| !active));
+    assert!((vad.noise_floor() - 300.0).abs() < 10.0);
+
+    // a voice over the noise is still detected
+    vad.detect(&frame(3000));
+    assert!(vad.detect(&frame(3000)).0);
+
+    // the floor follows the room down again
+    vad.reset();
+    for _ in 0..200 {
+        vad.detect(&frame(20));
+    }
+    assert!(vad.noise_floor() < 30.0);
+
+    // but never below the minimum threshold
+    assert!(!vad.detect(&frame(90)).0);
+    assert!(!vad.detect(&frame(90)).0);
+}
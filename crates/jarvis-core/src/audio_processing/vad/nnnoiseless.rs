@@ -1,51 +1,45 @@
 use nnnoiseless::DenoiseState;
+use crate::audio_processing::reframer::Reframer;
 use crate::config;
 
 pub struct NnnoiselessVAD {
     state: Box<DenoiseState<'static>>,
-    buffer: Vec<f32>,
+    // recorder frames (512) -> nnnoiseless frames (480)
+    reframer: Reframer,
+    last_prob: f32,
 }
 
 impl NnnoiselessVAD {
     pub fn new() -> Self {
         Self {
             state: DenoiseState::new(),
-            buffer: Vec::with_capacity(config::NNNOISELESS_FRAME_SIZE * 2),
+            reframer: Reframer::new(config::NNNOISELESS_FRAME_SIZE),
+            last_prob: 0.5,
         }
     }
 
     pub fn detect(&mut self, input: &[i16]) -> (bool, f32) {
-        for &sample in input {
-            self.buffer.push(sample as f32);
-        }
+        let samples: Vec<f32> = input.iter().map(|&s| s as f32).collect();
 
+        let state = &mut self.state;
+        let mut output_frame = [0.0f32; config::NNNOISELESS_FRAME_SIZE];
         let mut total_vad = 0.0f32;
-        let mut frame_count = 0u32;
-
-        while self.buffer.len() >= config::NNNOISELESS_FRAME_SIZE {
-            let mut input_frame = [0.0f32; 480];
-            let mut output_frame = [0.0f32; 480];
-            
-            input_frame.copy_from_slice(&self.buffer[..config::NNNOISELESS_FRAME_SIZE]);
-            self.buffer.drain(..config::NNNOISELESS_FRAME_SIZE);
-
-            let vad_prob = self.state.process_frame(&mut output_frame, &input_frame);
-            total_vad += vad_prob;
-            frame_count += 1;
-        }
 
-        if frame_count == 0 {
-            return (true, 0.5);
-        }
+        let frame_count = self.reframer.analyze(&samples, |chunk| {
+            total_vad += state.process_frame(&mut output_frame, chunk);
+        });
 
-        let avg_vad = total_vad / frame_count as f32;
-        let is_voice = avg_vad >= config::VAD_NNNOISELESS_THRESHOLD;
+        // not enough samples for a full frame yet - keep previous decision
+        if frame_count > 0 {
+            self.last_prob = total_vad / frame_count as f32;
+        }
 
-        (is_voice, avg_vad)
+        (self.last_prob >= config::VAD_NNNOISELESS_THRESHOLD, self.last_prob)
     }
 
     pub fn reset(&mut self) {
         self.state = DenoiseState::new();
-        self.buffer.clear();
+        self.reframer.reset();
+        self.last_prob = 0.5;
     }
-}
\ No newline at end of file
+}
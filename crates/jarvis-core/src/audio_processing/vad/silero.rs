@@ -0,0 +1,119 @@
+// Silero VAD (v5 ONNX model) running through onnxruntime
+// https://github.com/snakers4/silero-vad
+
+use std::path::Path;
+
+use ndarray::{arr0, Array2, Array3};
+use ort::session::Session;
+use ort::value::Tensor;
+
+use crate::config;
+
+// model works on 512-sample windows at 16 kHz (same as our recorder frames)
+const WINDOW_SIZE: usize = 512;
+// v5 expects the last 64 samples of the previous window prepended
+const CONTEXT_SIZE: usize = 64;
+const STATE_SHAPE: (usize, usize, usize) = (2, 1, 128);
+const SAMPLE_RATE: i64 = 16000;
+
+pub struct SileroVAD {
+    session: Session,
+    // recurrent state, carried across frames
+    state: Array3<f32>,
+    context: Vec<f32>,
+    buffer: Vec<f32>,
+    last_prob: f32,
+}
+
+// ort session is only accessed behind a Mutex
+unsafe impl Send for SileroVAD {}
+
+impl SileroVAD {
+    pub fn new(model_path: &Path) -> Result<Self, String> {
+        let session = Session::builder()
+            .map_err(|e| format!("Failed to create ort session builder: {}", e))?
+            .commit_from_file(model_path)
+            .map_err(|e| format!("Failed to load Silero VAD model {}: {}", model_path.display(), e))?;
+
+        Ok(Self {
+            session,
+            state: Array3::zeros(STATE_SHAPE),
+            context: vec![0.0; CONTEXT_SIZE],
+            buffer: Vec::with_capacity(WINDOW_SIZE * 2),
+            last_prob: 0.0,
+        })
+    }
+
+    pub fn detect(&mut self, input: &[i16]) -> (bool, f32) {
+        // model expects normalized f32 samples
+        self.buffer.extend(input.iter().map(|&s| s as f32 / 32768.0));
+
+        let mut max_prob: Option<f32> = None;
+
+        while self.buffer.len() >= WINDOW_SIZE {
+            let window: Vec<f32> = self.buffer.drain(..WINDOW_SIZE).collect();
+
+            match self.infer(&window) {
+                Ok(prob) => {
+                    max_prob = Some(max_prob.map_or(prob, |p| p.max(prob)));
+                }
+                Err(e) => {
+                    error!("Silero VAD inference failed: {}", e);
+                }
+            }
+        }
+
+        // not enough samples for a full window yet - keep previous decision
+        if let Some(prob) = max_prob {
+            self.last_prob = prob;
+        }
+
+        (self.last_prob >= config::VAD_SILERO_THRESHOLD, self.last_prob)
+    }
+
+    fn infer(&mut self, window: &[f32]) -> Result<f32, String> {
+        let mut samples = Vec::with_capacity(CONTEXT_SIZE + WINDOW_SIZE);
+        samples.extend_from_slice(&self.context);
+        samples.extend_from_slice(window);
+
+        let input = Array2::from_shape_vec((1, CONTEXT_SIZE + WINDOW_SIZE), samples)
+            .map_err(|e| format!("input shape: {}", e))?;
+
+        let t_input = Tensor::from_array(input).map_err(|e| format!("tensor: {}", e))?;
+        let t_state = Tensor::from_array(self.state.clone()).map_err(|e| format!("tensor: {}", e))?;
+        let t_sr = Tensor::from_array(arr0(SAMPLE_RATE)).map_err(|e| format!("tensor: {}", e))?;
+
+        let (prob, new_state) = {
+            let outputs = self.session.run(
+                ort::inputs! {
+                    "input" => t_input,
+                    "state" => t_state,
+                    "sr" => t_sr,
+                }
+            ).map_err(|e| format!("ort inference error: {}", e))?;
+
+            let (_, prob) = outputs["output"]
+                .try_extract_tensor::<f32>()
+                .map_err(|e| format!("Failed to extract output: {}", e))?;
+
+            let (_, state) = outputs["stateN"]
+                .try_extract_tensor::<f32>()
+                .map_err(|e| format!("Failed to extract state: {}", e))?;
+
+            (prob.first().copied().unwrap_or(0.0), state.to_vec())
+        };
+
+        self.state = Array3::from_shape_vec(STATE_SHAPE, new_state)
+            .map_err(|e| format!("state shape: {}", e))?;
+        self.context.copy_from_slice(&window[WINDOW_SIZE - CONTEXT_SIZE..]);
+
+        Ok(prob)
+    }
+
+    pub fn reset(&mut self) {
+        self.state = Array3::zeros(STATE_SHAPE);
+        self.context.iter_mut().for_each(|s| *s = 0.0);
+        self.buffer.clear();
+        self.last_prob = 0.0;
+    }
+}
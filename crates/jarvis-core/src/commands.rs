@@ -1,18 +1,35 @@
+#[cfg(test)]
+mod tests;
+
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::fs;
-use std::time::Duration;
-use std::process::{Child, Command};
+use std::io::Read;
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+use std::process::{Child, Command, Stdio};
 
 use seqdiff::ratio;
 
 mod structs;
 pub use structs::*;
 
-use crate::{config, i18n, APP_DIR};
+pub mod template;
+pub mod confirm;
+mod sequence;
+mod limits;
+pub mod pack;
+mod dbus;
+
+#[cfg(feature = "http")]
+mod http;
+use template::{Escape, TemplateContext};
+
+use crate::input::{self, InputStep};
+use crate::{config, db, i18n, notify, APP_DIR, DB};
 
 #[cfg(feature = "lua")]
-use crate::lua::{self, SandboxLevel, CommandContext};
+use crate::lua::{self, Capabilities, SandboxLevel, CommandContext};
 
 pub fn parse_commands() -> Result<Vec<JCommandsList>, String> {
     let mut commands: Vec<JCommandsList> = Vec::new();
@@ -37,7 +54,7 @@ pub fn parse_commands() -> Result<Vec<JCommandsList>, String> {
             }
         };
 
-        let file: JCommandsList = match toml::from_str(&content) {
+        let mut file: JCommandsList = match toml::from_str(&content) {
             Ok(f) => f,
             Err(e) => {
                 warn!("Failed to parse {}: {}", toml_file.display(), e);
@@ -45,12 +62,28 @@ pub fn parse_commands() -> Result<Vec<JCommandsList>, String> {
             }
         };
 
+        let mut pack_commands = for_platform(file.commands, std::env::consts::OS, &cmd_path);
+
+        if let Some(manifest) = file.pack.as_mut() {
+            let app_version = config::APP_VERSION.unwrap_or_default();
+            if let Err(reason) = pack::validate(manifest, &cmd_path, std::env::consts::OS, app_version) {
+                warn!("Skipping pack {}: {}", cmd_path.display(), reason);
+                continue;
+            }
+
+            info!("Pack '{}' v{} by {}, capabilities: {:?}", manifest.name, manifest.version, manifest.author, manifest.capabilities);
+            pack_commands = pack::apply(manifest, pack_commands);
+        }
+
         commands.push(JCommandsList {
+            commands: pack_commands,
+            pack: file.pack,
             path: cmd_path,
-            commands: file.commands,
         });
     }
 
+    sequence::validate(&mut commands);
+
     if commands.is_empty() {
         Err("No commands found".into())
     } else {
@@ -59,6 +92,44 @@ pub fn parse_commands() -> Result<Vec<JCommandsList>, String> {
     }
 }
 
+// Apply per-OS overrides and drop commands that can't run here, so they never match
+fn for_platform(commands: Vec<JCommand>, os: &str, pack: &Path) -> Vec<JCommand> {
+    commands.into_iter()
+        .filter_map(|mut cmd| {
+            cmd.apply_platform(os);
+
+            match cmd.unsupported_reason(os) {
+                Some(reason) => {
+                    warn!("Skipping command '{}' in {}: {}", cmd.id, pack.display(), reason);
+                    None
+                }
+                None => Some(cmd),
+            }
+        })
+        .collect()
+}
+
+// Switched off commands (settings.disabled_commands) are left out of matching and classifier training
+pub fn is_enabled(id: &str) -> bool {
+    !DB.get().is_some_and(|db| db.read().disabled_commands.iter().any(|d| d == id))
+}
+
+// Switch a command on / off and persist it, the classifier is retrained on the next start
+pub fn set_enabled(id: &str, enabled: bool) -> Result<(), String> {
+    let db = DB.get().ok_or("Settings are not loaded")?;
+
+    let snapshot = {
+        let mut settings = db.write();
+        settings.disabled_commands.retain(|d| d != id);
+        if !enabled {
+            settings.disabled_commands.push(id.to_string());
+        }
+        settings.clone()
+    };
+
+    info!("Command '{}' {}", id, if enabled { "enabled" } else { "disabled" });
+    db::save_settings(&snapshot).map_err(|e| format!("Failed to save settings: {}", e))
+}
 
 pub fn commands_hash(commands: &[JCommandsList]) -> String {
     use sha2::{Sha256, Digest};
@@ -71,7 +142,7 @@ pub fn commands_hash(commands: &[JCommandsList]) -> String {
 
     // collect all command ids and phrases for current language, sorted
     let mut all_data: Vec<(&str, _)> = commands.iter()
-        .flat_map(|ac| ac.commands.iter().map(|c| (c.id.as_str(), c.get_phrases(&lang))))
+        .flat_map(|ac| ac.commands.iter().filter(|c| is_enabled(&c.id)).map(|c| (c.id.as_str(), c.get_phrases(&lang))))
         .collect();
     all_data.sort_by_key(|(id, _)| *id);
     
@@ -90,6 +161,14 @@ pub fn fetch_command<'a>(
     phrase: &str,
     commands: &'a [JCommandsList],
 ) -> Option<(&'a PathBuf, &'a JCommand)> {
+    fetch_command_scored(phrase, commands).map(|(path, cmd, _)| (path, cmd))
+}
+
+// Same as fetch_command, but also returns the match score (0.0 - 100.0)
+pub fn fetch_command_scored<'a>(
+    phrase: &str,
+    commands: &'a [JCommandsList],
+) -> Option<(&'a PathBuf, &'a JCommand, f64)> {
     let lang = i18n::get_language();
 
     let phrase = phrase.trim().to_lowercase();
@@ -104,7 +183,7 @@ pub fn fetch_command<'a>(
     let mut best_score = config::CMD_RATIO_THRESHOLD;
 
     for cmd_list in commands {
-        for cmd in &cmd_list.commands {
+        for cmd in cmd_list.commands.iter().filter(|c| is_enabled(&c.id)) {
             let cmd_phrases = cmd.get_phrases(&lang);
             
             for cmd_phrase in cmd_phrases.iter() {
@@ -124,7 +203,7 @@ pub fn fetch_command<'a>(
                 // early exit on perfect match
                 if score >= 99.0 {
                     debug!("Perfect match: '{}' -> '{}'", phrase, cmd_phrase_lower);
-                    return Some((&cmd_list.path, cmd));
+                    return Some((&cmd_list.path, cmd, score));
                 }
                 
                 if score > best_score {
@@ -141,7 +220,7 @@ pub fn fetch_command<'a>(
         debug!("No match for '{}' (best: {:.1}%)", phrase, best_score);
     }
     
-    result
+    result.map(|(path, cmd)| (path, cmd, best_score))
 }
 
 
@@ -179,37 +258,203 @@ fn word_overlap_score(input_words: &[&str], cmd_words: &[&str]) -> f64 {
 
 
 pub fn execute_exe(exe: &str, args: &[String]) -> std::io::Result<Child> {
-    Command::new(exe).args(args).spawn()
+    exe_command(exe, args).spawn()
 }
 
 pub fn execute_cli(cmd: &str, args: &[String]) -> std::io::Result<Child> {
-    debug!("Spawning: cmd /C {} {:?}", cmd, args);
+    cli_command(cmd, args).spawn()
+}
+
+fn exe_command(exe: &str, args: &[String]) -> Command {
+    let mut command = Command::new(exe);
+    command.args(args);
+    command
+}
+
+fn cli_command(cmd: &str, args: &[String]) -> Command {
+    debug!("Spawning: {} {:?}", cmd, args);
+    shell_command(cmd, args)
+}
+
+// cmd parses the /C line itself, so it's passed raw: Command::arg would add
+// msvc-style \" escapes around the already quoted args, which cmd keeps as is
+#[cfg(target_os = "windows")]
+fn shell_command(cmd: &str, args: &[String]) -> Command {
+    use std::os::windows::process::CommandExt;
+
+    let mut line = cmd.to_string();
+    for arg in args {
+        line.push(' ');
+        line.push_str(arg);
+    }
+
+    let mut command = Command::new("cmd");
+    command.arg("/C").raw_arg(line);
+    command
+}
+
+// the first arg after the script becomes $0, a placeholder keeps the args at $1..
+#[cfg(not(target_os = "windows"))]
+fn shell_command(cmd: &str, args: &[String]) -> Command {
+    let mut command = Command::new("sh");
+    command.arg("-c").arg(cmd).arg("sh").args(args);
+    command
+}
+
+// Spawn a cli/ahk process, waiting for its exit code and output when the command asks for it
+fn run_process(mut command: Command, cmd_config: &JCommand) -> Result<CommandOutcome, String> {
+    let wait = cmd_config.wait || cmd_config.output != OutputMode::None;
+
+    if !wait {
+        let mut child = command.spawn().map_err(|e| e.to_string())?;
+
+        // reap in the background, so finished processes don't stay zombies
+        let id = cmd_config.id.clone();
+        std::thread::spawn(move || match child.wait() {
+            Ok(status) => debug!("Command '{}' process exited: {}", id, status),
+            Err(e) => warn!("Command '{}' process wait failed: {}", id, e),
+        });
 
-    if cfg!(target_os = "windows") {
-        Command::new("cmd").arg("/C").arg(cmd).args(args).spawn()
+        return Ok(CommandOutcome::ok(true));
+    }
+
+    let mut child = command
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| e.to_string())?;
+
+    // drain the pipes aside, a full pipe would block the child until the timeout
+    let stdout = child.stdout.take().map(read_pipe);
+    let stderr = child.stderr.take().map(read_pipe);
+
+    let timeout = Duration::from_millis(match cmd_config.timeout {
+        0 => config::DEFAULT_PROCESS_TIMEOUT,
+        t => t,
+    });
+    let started = Instant::now();
+
+    let status = loop {
+        match child.try_wait().map_err(|e| e.to_string())? {
+            Some(status) => break status,
+            None if started.elapsed() >= timeout => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(format!("Command '{}' timed out after {} ms", cmd_config.id, timeout.as_millis()));
+            }
+            None => std::thread::sleep(Duration::from_millis(20)),
+        }
+    };
+
+    // a backgrounded grandchild may keep the pipe open, don't wait for it forever
+    let collect = |rx: Option<mpsc::Receiver<String>>| {
+        rx.and_then(|rx| rx.recv_timeout(Duration::from_millis(500)).ok())
+            .map(|s| s.trim().to_string())
+            .unwrap_or_default()
+    };
+    let stdout = collect(stdout);
+    let stderr = collect(stderr);
+
+    let exit_code = status.code();
+    let success = match exit_code {
+        Some(code) if cmd_config.success_codes.is_empty() => code == 0,
+        Some(code) => cmd_config.success_codes.contains(&code),
+        None => false, // killed by a signal
+    };
+
+    let output = if !success && !stderr.is_empty() { stderr } else { stdout };
+    let output = truncate_output(&output);
+
+    if success {
+        if let Some(text) = &output {
+            deliver_output(cmd_config, text);
+        }
     } else {
-        Command::new("sh").arg("-c").arg(cmd).args(args).spawn()
+        warn!("Command '{}' exited with {}: {}", cmd_config.id, status, output.as_deref().unwrap_or(""));
+    }
+
+    Ok(CommandOutcome { success, chain: true, exit_code, output })
+}
+
+fn read_pipe(mut pipe: impl Read + Send + 'static) -> mpsc::Receiver<String> {
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        let mut buf = Vec::new();
+        let _ = pipe.read_to_end(&mut buf);
+        let _ = tx.send(String::from_utf8_lossy(&buf).to_string());
+    });
+    rx
+}
+
+fn truncate_output(output: &str) -> Option<String> {
+    if output.is_empty() {
+        return None;
+    }
+
+    match output.char_indices().nth(config::PROCESS_OUTPUT_MAX_CHARS) {
+        Some((cut, _)) => Some(format!("{}...", &output[..cut])),
+        None => Some(output.to_string()),
+    }
+}
+
+fn deliver_output(cmd_config: &JCommand, text: &str) {
+    let title = if cmd_config.description.is_empty() { &cmd_config.id } else { &cmd_config.description };
+
+    match cmd_config.output {
+        OutputMode::None => {}
+        OutputMode::Notify => notify::show(title, text),
+        // @TODO: speak it when TTS will be implemented, shown as a notification until then
+        OutputMode::Speak => {
+            info!("SPEAK: {}", text);
+            notify::show(title, text);
+        }
     }
 }
 
-pub fn execute_command(cmd_path: &PathBuf, cmd_config: &JCommand, phrase: Option<&str>, slots: Option<&HashMap<String, SlotValue>>) -> Result<bool, String> {
+// Would the command run right now (enabled, within cooldown / max_per_minute), without counting a run.
+// Checked before asking for confirmation.
+pub fn check_admission(cmd_config: &JCommand) -> Result<(), String> {
+    if !is_enabled(&cmd_config.id) {
+        return Err(format!("Command '{}' is disabled", cmd_config.id));
+    }
+
+    limits::peek(cmd_config)
+}
+
+pub fn execute_command(cmd_path: &PathBuf, cmd_config: &JCommand, phrase: Option<&str>, slots: Option<&HashMap<String, SlotValue>>) -> Result<CommandOutcome, String> {
+    // sequence steps can still point at a disabled command
+    if !is_enabled(&cmd_config.id) {
+        return Err(format!("Command '{}' is disabled", cmd_config.id));
+    }
+
+    limits::admit(cmd_config)?;
+
     // execute command by the type
     match cmd_config.cmd_type.as_str() {
 
         // BRUH
-        "voice" => Ok(true),
+        "voice" => Ok(CommandOutcome::ok(true)),
         
         // LUA command
         #[cfg(feature = "lua")]
         "lua" => {
-            execute_lua_command(cmd_path, cmd_config, phrase, slots)
+            execute_lua_command(cmd_path, cmd_config, phrase, slots).map(CommandOutcome::ok)
         }
 
         // AutoHotkey command
         // @TODO: Consider adding ahk source files execution?
         "ahk" => {
-            let exe_path_absolute = Path::new(&cmd_config.exe_path);
-            let exe_path_local = cmd_path.join(&cmd_config.exe_path);
+            let language = i18n::get_language();
+            let ctx = TemplateContext { phrase: phrase.unwrap_or(""), language: &language, slots };
+
+            // no shell in between, values go in verbatim
+            let exe = template::interpolate(&cmd_config.exe_path, &ctx, Escape::None);
+            let args: Vec<String> = cmd_config.exe_args.iter()
+                .map(|a| template::interpolate(a, &ctx, Escape::None))
+                .collect();
+
+            let exe_path_absolute = Path::new(&exe);
+            let exe_path_local = cmd_path.join(&exe);
 
             let exe_path = if exe_path_absolute.exists() {
                 exe_path_absolute
@@ -217,19 +462,55 @@ pub fn execute_command(cmd_path: &PathBuf, cmd_config: &JCommand, phrase: Option
                 exe_path_local.as_path()
             };
 
-            execute_exe(exe_path.to_str().unwrap(), &cmd_config.exe_args)
-                .map(|_| true)
-                .map_err(|e| format!("AHK process spawn error: {}", e))
+            run_process(exe_command(exe_path.to_str().unwrap(), &args), cmd_config)
+                .map_err(|e| format!("AHK process error: {}", e))
         }
         
         // CLI command type
         // @TODO: Consider security restrictions
         "cli" => {
-            execute_cli(&cmd_config.cli_cmd, &cmd_config.cli_args)
-                .map(|_| true)
+            let language = i18n::get_language();
+            let ctx = TemplateContext { phrase: phrase.unwrap_or(""), language: &language, slots };
+
+            // sh gets the args as $1.., cmd gets them quoted on its /C line
+            let args_escape = if cfg!(target_os = "windows") { Escape::Cmd } else { Escape::None };
+
+            let cli_cmd = template::interpolate(&cmd_config.cli_cmd, &ctx, Escape::shell());
+            let args: Vec<String> = cmd_config.cli_args.iter()
+                .map(|a| template::interpolate(a, &ctx, args_escape))
+                .collect();
+
+            run_process(cli_command(&cli_cmd, &args), cmd_config)
                 .map_err(|e| format!("CLI command error: {}", e))
         }
         
+        // HTTP request / webhook
+        #[cfg(feature = "http")]
+        "http" => http::execute(cmd_config, phrase, slots),
+
+        // key presses / typing, text may contain {placeholders}
+        "keys" => {
+            let language = i18n::get_language();
+            let ctx = TemplateContext { phrase: phrase.unwrap_or(""), language: &language, slots };
+
+            let steps: Vec<InputStep> = cmd_config.keys.iter()
+                .map(|step| match step {
+                    InputStep::Text { text } => InputStep::Text { text: template::interpolate(text, &ctx, Escape::None) },
+                    other => other.clone(),
+                })
+                .collect();
+
+            input::run(&steps, input::backend())
+                .map(|_| CommandOutcome::ok(true))
+                .map_err(|e| format!("Keys command error: {}", e))
+        }
+
+        // D-Bus method call / property read, or a media player action
+        "dbus" => dbus::execute(cmd_config, phrase, slots),
+
+        // other commands in a row
+        "sequence" => sequence::execute(cmd_config, phrase, slots),
+
         // TERMINATOR command (T1000)
         "terminate" => {
             std::thread::sleep(Duration::from_secs(2));
@@ -237,7 +518,7 @@ pub fn execute_command(cmd_path: &PathBuf, cmd_config: &JCommand, phrase: Option
         }
         
         // STOP CHANING
-        "stop_chaining" => Ok(false),
+        "stop_chaining" => Ok(CommandOutcome::ok(false)),
 
         // other
         _ => {
@@ -272,8 +553,11 @@ fn execute_lua_command(
         return Err(format!("Lua script not found: {}", script_path.display()));
     }
     
-    // parse sandbox level
-    let sandbox = SandboxLevel::from_str(&cmd_config.sandbox);
+    // declared pack capabilities, or the legacy sandbox level
+    let sandbox = match &cmd_config.pack_capabilities {
+        Some(declared) => Capabilities::declared(declared),
+        None => SandboxLevel::from_str(&cmd_config.sandbox).into(),
+    };
 
     // create context
     let context = CommandContext {
@@ -285,7 +569,10 @@ fn execute_lua_command(
     };
     
     // get timeout
-    let timeout = Duration::from_millis(cmd_config.timeout);
+    let timeout = Duration::from_millis(match cmd_config.timeout {
+        0 => config::DEFAULT_LUA_TIMEOUT,
+        t => t,
+    });
     
     info!("Executing Lua command: {} (sandbox: {:?}, timeout: {:?})", 
           cmd_config.id, sandbox, timeout);
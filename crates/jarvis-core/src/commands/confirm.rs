@@ -0,0 +1,76 @@
+#[cfg(test)]
+mod tests;
+
+use fluent_bundle::{FluentArgs, FluentValue};
+
+use super::JCommand;
+use crate::{config, i18n};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Answer {
+    Yes,
+    No,
+    Unclear, // keep waiting
+}
+
+// Classify a reply to "are you sure?".
+// "no" wins over "yes" ("no, yes" is not a confirmation), a confirm_phrase replaces the yes-grammar.
+pub fn classify(reply: &str, lang: &str, confirm_phrase: &str) -> Answer {
+    let reply = normalize(reply);
+
+    if config::get_cancel_phrases(lang).iter().any(|p| contains_phrase(&reply, p)) {
+        return Answer::No;
+    }
+
+    let confirmed = if confirm_phrase.trim().is_empty() {
+        config::get_confirm_phrases(lang).iter().any(|p| contains_phrase(&reply, p))
+    } else {
+        contains_phrase(&reply, &normalize(confirm_phrase))
+    };
+
+    if confirmed { Answer::Yes } else { Answer::Unclear }
+}
+
+// Vocabulary for the reply window: yes / no phrases (or the confirm_phrase instead of yes) and [unk].
+// The command grammar doesn't know these words, so the reply gets its own recognizer.
+pub fn grammar(lang: &str, confirm_phrase: &str) -> Vec<String> {
+    let mut entries = vec!["[unk]".to_string()];
+
+    if confirm_phrase.trim().is_empty() {
+        entries.extend(config::get_confirm_phrases(lang).iter().map(|p| normalize(p)));
+    } else {
+        entries.push(normalize(confirm_phrase));
+    }
+    entries.extend(config::get_cancel_phrases(lang).iter().map(|p| normalize(p)));
+
+    entries
+}
+
+// "Run «Shutdown»? Say "yes" or "no"."
+pub fn prompt(cmd: &JCommand) -> String {
+    let name = if cmd.description.is_empty() { &cmd.id } else { &cmd.description };
+
+    let mut args = FluentArgs::new();
+    args.set("command", FluentValue::from(name.as_str()));
+
+    if cmd.confirm_phrase.is_empty() {
+        i18n::t_with_args("confirm-prompt", Some(&args))
+    } else {
+        args.set("phrase", FluentValue::from(cmd.confirm_phrase.as_str()));
+        i18n::t_with_args("confirm-phrase-prompt", Some(&args))
+    }
+}
+
+// lowercase words separated by single spaces, punctuation dropped
+fn normalize(text: &str) -> String {
+    text.to_lowercase()
+        .split(|c: char| !(c.is_alphanumeric() || c == '\''))
+        .filter(|w| !w.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+// whole-word match, "no" must not match "know"
+fn contains_phrase(text: &str, phrase: &str) -> bool {
+    !phrase.is_empty() && format!(" {} ", text).contains(&format!(" {} ", phrase))
+}
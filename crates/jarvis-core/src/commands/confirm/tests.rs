@@ -0,0 +1,38 @@
+use super::*;
+
+#[test]
+fn classifies_replies() {
+    assert_eq!(classify("Yes, do it", "en", ""), Answer::Yes);
+    assert_eq!(classify("no", "en", ""), Answer::No);
+    assert_eq!(classify("yes... no, cancel", "en", ""), Answer::No);
+    assert_eq!(classify("don't do that", "en", ""), Answer::No);
+    assert_eq!(classify("what's the weather", "en", ""), Answer::Unclear);
+    assert_eq!(classify("noted", "en", ""), Answer::Unclear);
+
+    assert_eq!(classify("да, выполняй", "ru", ""), Answer::Yes);
+    assert_eq!(classify("нет", "ru", ""), Answer::No);
+    assert_eq!(classify("так", "ua", ""), Answer::Yes);
+    assert_eq!(classify("ні, не треба", "ua", ""), Answer::No);
+}
+
+#[test]
+fn confirm_phrase_replaces_yes() {
+    assert_eq!(classify("yes", "en", "shut it down"), Answer::Unclear);
+    assert_eq!(classify("Shut it down!", "en", "shut it down"), Answer::Yes);
+    assert_eq!(classify("no, don't shut it down", "en", "shut it down"), Answer::No);
+}
+
+#[test]
+fn reply_grammar() {
+    let entries = grammar("en", "");
+    assert_eq!(entries[0], "[unk]");
+    assert!(entries.iter().any(|e| e == "yes"));
+    assert!(entries.iter().any(|e| e == "do it"));
+    assert!(entries.iter().any(|e| e == "no"));
+    assert!(entries.iter().any(|e| e == "don't"));
+
+    let entries = grammar("en", "Shut it down!");
+    assert!(entries.iter().any(|e| e == "shut it down"));
+    assert!(!entries.iter().any(|e| e == "yes"));
+    assert!(entries.iter().any(|e| e == "cancel"));
+}
@@ -0,0 +1,65 @@
+use std::collections::HashMap;
+
+use serde_json::Value;
+
+use super::template::{self, Escape, TemplateContext};
+use super::{deliver_output, truncate_output, CommandOutcome, JCommand, SlotValue};
+use crate::dbus::{self, mpris, Bus};
+use crate::i18n;
+
+pub fn execute(
+    cmd_config: &JCommand,
+    phrase: Option<&str>,
+    slots: Option<&HashMap<String, SlotValue>>,
+) -> Result<CommandOutcome, String> {
+    let text = if !cmd_config.mpris.is_empty() {
+        execute_mpris(&cmd_config.mpris)?
+    } else {
+        let call = cmd_config.dbus.as_ref()
+            .ok_or_else(|| format!("Command '{}' has neither [dbus] nor mpris", cmd_config.id))?;
+
+        let language = i18n::get_language();
+        let ctx = TemplateContext { phrase: phrase.unwrap_or(""), language: &language, slots };
+        let bus = Bus::parse(&call.bus);
+
+        let reply = if !call.property.is_empty() {
+            dbus::get_property(&bus, &call.dest, &call.path, &call.property)?
+        } else {
+            // args are typed values, placeholders need no quoting
+            let args: Vec<Value> = call.args.iter().map(|a| interpolate_arg(a, &ctx)).collect();
+            dbus::call(&bus, &call.dest, &call.path, &call.method, &call.signature, &args)?
+        };
+
+        dbus::to_text(&reply)
+    };
+
+    let output = truncate_output(text.trim());
+    if let Some(text) = &output {
+        deliver_output(cmd_config, text);
+    }
+
+    Ok(CommandOutcome { success: true, chain: true, exit_code: None, output })
+}
+
+// placeholders in strings, also inside arrays and dicts
+fn interpolate_arg(arg: &Value, ctx: &TemplateContext) -> Value {
+    match arg {
+        Value::String(s) => Value::String(template::interpolate(s, ctx, Escape::None)),
+        Value::Array(items) => Value::Array(items.iter().map(|i| interpolate_arg(i, ctx)).collect()),
+        Value::Object(map) => Value::Object(map.iter().map(|(k, v)| (k.clone(), interpolate_arg(v, ctx))).collect()),
+        other => other.clone(),
+    }
+}
+
+// player action, or "now_playing" which returns "Artist - Title"
+fn execute_mpris(action: &str) -> Result<String, String> {
+    let bus = Bus::Session;
+
+    if action == "now_playing" {
+        return mpris::now_playing(&bus).map(|np| np.describe());
+    }
+
+    let action = mpris::Action::parse(action)
+        .ok_or_else(|| format!("Unknown media player action '{}'", action))?;
+    mpris::control(&bus, action).map(|_| String::new())
+}
@@ -0,0 +1,100 @@
+#[cfg(test)]
+mod tests;
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use serde_json::Value;
+
+use super::template::{self, Escape, TemplateContext};
+use super::{deliver_output, truncate_output, CommandOutcome, JCommand, SlotValue};
+use crate::{http_client, i18n};
+
+pub fn execute(
+    cmd_config: &JCommand,
+    phrase: Option<&str>,
+    slots: Option<&HashMap<String, SlotValue>>,
+) -> Result<CommandOutcome, String> {
+    let language = i18n::get_language();
+    let ctx = TemplateContext { phrase: phrase.unwrap_or(""), language: &language, slots };
+
+    let method = if cmd_config.method.is_empty() { "GET" } else { cmd_config.method.as_str() };
+    let method = reqwest::Method::from_bytes(method.to_uppercase().as_bytes())
+        .map_err(|_| format!("Invalid HTTP method '{}'", cmd_config.method))?;
+    let url = template::interpolate(&cmd_config.url, &ctx, Escape::Url);
+
+    let mut request = http_client::shared()?.request(method.clone(), &url);
+
+    for (name, value) in &cmd_config.headers {
+        request = request.header(name, template::interpolate(value, &ctx, Escape::None));
+    }
+
+    if !cmd_config.body.is_empty() {
+        // slot values must not break out of json strings
+        let is_json = cmd_config.headers.iter()
+            .any(|(k, v)| k.eq_ignore_ascii_case("content-type") && v.contains("json"));
+        let escape = if is_json { Escape::Json } else { Escape::None };
+        request = request.body(template::interpolate(&cmd_config.body, &ctx, escape));
+    }
+
+    if cmd_config.timeout > 0 {
+        request = request.timeout(Duration::from_millis(cmd_config.timeout));
+    }
+
+    debug!("HTTP {} {}", method, url);
+    let response = request.send()
+        .map_err(|e| format!("HTTP request to {} failed: {}", url, e))?;
+
+    let status = response.status().as_u16();
+    let text = response.text().unwrap_or_default();
+
+    let success = if cmd_config.expect_status.is_empty() {
+        (200..300).contains(&status)
+    } else {
+        cmd_config.expect_status.contains(&status)
+    };
+
+    let output = if !success {
+        warn!("Command '{}': HTTP {} from {}", cmd_config.id, status, url);
+        truncate_output(text.trim())
+    } else if cmd_config.response_path.is_empty() {
+        None
+    } else {
+        let value = serde_json::from_str::<Value>(&text)
+            .ok()
+            .and_then(|json| json_path(&json, &cmd_config.response_path).map(value_to_string));
+        if value.is_none() {
+            warn!("Command '{}': '{}' not found in the response", cmd_config.id, cmd_config.response_path);
+        }
+        value.and_then(|v| truncate_output(&v))
+    };
+
+    if success {
+        if let Some(text) = &output {
+            deliver_output(cmd_config, text);
+        }
+    }
+
+    Ok(CommandOutcome { success, chain: true, exit_code: Some(status as i32), output })
+}
+
+// Minimal JSONPath: "$.data.items[0].name", "data.items.0.name" or "$['data']['name']"
+fn json_path<'a>(value: &'a Value, path: &str) -> Option<&'a Value> {
+    let path = path.trim().trim_start_matches('$');
+
+    path.split(['.', '['])
+        .map(|part| part.trim_end_matches(']').trim_matches(|c| c == '\'' || c == '"'))
+        .filter(|part| !part.is_empty())
+        .try_fold(value, |current, key| match current {
+            Value::Array(items) => items.get(key.parse::<usize>().ok()?),
+            Value::Object(map) => map.get(key),
+            _ => None,
+        })
+}
+
+fn value_to_string(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
@@ -0,0 +1,109 @@
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::sync::mpsc;
+
+use serde_json::json;
+
+use crate::commands::http::{execute, json_path};
+use crate::commands::{JCommand, SlotValue};
+
+// Answers one request with `status` and `body`, sends the raw request back
+fn mock_server(status: &'static str, body: &'static str) -> (String, mpsc::Receiver<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = format!("http://{}", listener.local_addr().unwrap());
+    let (tx, rx) = mpsc::channel();
+
+    std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut request = Vec::new();
+        let mut buf = [0u8; 4096];
+
+        // headers, then as much body as content-length says
+        loop {
+            let n = stream.read(&mut buf).unwrap();
+            request.extend_from_slice(&buf[..n]);
+            let text = String::from_utf8_lossy(&request).to_string();
+            if let Some(end) = text.find("\r\n\r\n") {
+                let length = text.lines()
+                    .find_map(|l| l.to_lowercase().strip_prefix("content-length:").map(|v| v.trim().parse::<usize>().unwrap()))
+                    .unwrap_or(0);
+                if request.len() >= end + 4 + length {
+                    break;
+                }
+            }
+            if n == 0 {
+                break;
+            }
+        }
+
+        let response = format!(
+            "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            status, body.len(), body
+        );
+        stream.write_all(response.as_bytes()).unwrap();
+        tx.send(String::from_utf8_lossy(&request).to_string()).unwrap();
+    });
+
+    (addr, rx)
+}
+
+fn command(toml: &str) -> JCommand {
+    toml::from_str(&format!("id = \"test\"\ntype = \"http\"\n{}", toml)).unwrap()
+}
+
+#[test]
+fn sends_templated_request_and_reads_response_path() {
+    let (addr, request) = mock_server("200 OK", r#"{"state": {"temp": 21.5}}"#);
+    let cmd = command(&format!(r#"
+url = "{}/rooms/{{slot:room}}"
+method = "post"
+headers = {{ "Content-Type" = "application/json" }}
+body = '{{"room": "{{slot:room}}"}}'
+response_path = "$.state.temp"
+"#, addr));
+
+    let slots = std::collections::HashMap::from([("room".to_string(), SlotValue::Text("living \"room\"".into()))]);
+    let outcome = execute(&cmd, Some("temperature"), Some(&slots)).unwrap();
+
+    assert!(outcome.success);
+    assert_eq!(outcome.exit_code, Some(200));
+    assert_eq!(outcome.output.as_deref(), Some("21.5"));
+
+    let request = request.recv().unwrap();
+    assert!(request.starts_with("POST /rooms/living%20%22room%22 HTTP/1.1"));
+    assert!(request.ends_with(r#"{"room": "living \"room\""}"#));
+}
+
+#[test]
+fn keeps_base_url_from_env() {
+    let (addr, request) = mock_server("200 OK", "{}");
+    std::env::set_var("JARVIS_HTTP_TEST_URL", &addr);
+    let cmd = command(r#"url = "{env:JARVIS_HTTP_TEST_URL}/api/states/{slot:room}""#);
+
+    let slots = std::collections::HashMap::from([("room".to_string(), SlotValue::Text("living room".into()))]);
+    assert!(execute(&cmd, None, Some(&slots)).unwrap().success);
+
+    assert!(request.recv().unwrap().starts_with("GET /api/states/living%20room HTTP/1.1"));
+}
+
+#[test]
+fn unexpected_status_fails() {
+    let (addr, _request) = mock_server("404 Not Found", r#"{"error": "no such light"}"#);
+    let cmd = command(&format!("url = \"{}/lights/1\"\nexpect_status = [204]", addr));
+
+    let outcome = execute(&cmd, None, None).unwrap();
+    assert!(!outcome.success);
+    assert_eq!(outcome.exit_code, Some(404));
+    assert!(outcome.output.unwrap().contains("no such light"));
+}
+
+#[test]
+fn resolves_json_paths() {
+    let value = json!({"data": {"items": [{"name": "one"}, {"name": "two"}]}});
+
+    assert_eq!(json_path(&value, "$.data.items[1].name"), Some(&json!("two")));
+    assert_eq!(json_path(&value, "data.items.0.name"), Some(&json!("one")));
+    assert_eq!(json_path(&value, "$['data']['items'][0]['name']"), Some(&json!("one")));
+    assert_eq!(json_path(&value, "$.data.missing"), None);
+    assert_eq!(json_path(&value, "$"), Some(&value));
+}
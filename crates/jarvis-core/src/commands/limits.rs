@@ -0,0 +1,65 @@
+#[cfg(test)]
+mod tests;
+
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
+
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+
+use super::JCommand;
+
+// recent run times per command id
+static RUNS: Lazy<Mutex<HashMap<String, VecDeque<Instant>>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+// Enforce `cooldown` and `max_per_minute`, so a mis-heard phrase repeating can't fire a command in a loop.
+// Records the run when it's allowed.
+pub fn admit(cmd: &JCommand) -> Result<(), String> {
+    if cmd.cooldown == 0 && cmd.max_per_minute == 0 {
+        return Ok(());
+    }
+
+    let mut runs = RUNS.lock();
+    check(runs.entry(cmd.id.clone()).or_default(), cmd, Instant::now())
+}
+
+// Same as admit, but without recording a run
+pub fn peek(cmd: &JCommand) -> Result<(), String> {
+    if cmd.cooldown == 0 && cmd.max_per_minute == 0 {
+        return Ok(());
+    }
+
+    let mut runs = RUNS.lock();
+    refusal(runs.entry(cmd.id.clone()).or_default(), cmd, Instant::now())
+}
+
+fn check(runs: &mut VecDeque<Instant>, cmd: &JCommand, now: Instant) -> Result<(), String> {
+    refusal(runs, cmd, now)?;
+    runs.push_back(now);
+    Ok(())
+}
+
+fn refusal(runs: &mut VecDeque<Instant>, cmd: &JCommand, now: Instant) -> Result<(), String> {
+    let cooldown = Duration::from_millis(cmd.cooldown);
+    let window = cooldown.max(Duration::from_secs(60));
+
+    while runs.front().is_some_and(|t| now.duration_since(*t) >= window) {
+        runs.pop_front();
+    }
+
+    if let Some(last) = runs.back() {
+        let elapsed = now.duration_since(*last);
+        if elapsed < cooldown {
+            return Err(format!("Command '{}' is on cooldown for {:.1}s", cmd.id, (cooldown - elapsed).as_secs_f32()));
+        }
+    }
+
+    if cmd.max_per_minute > 0 {
+        let last_minute = runs.iter().filter(|t| now.duration_since(**t) < Duration::from_secs(60)).count();
+        if last_minute >= cmd.max_per_minute as usize {
+            return Err(format!("Command '{}' reached its limit of {} runs per minute", cmd.id, cmd.max_per_minute));
+        }
+    }
+
+    Ok(())
+}
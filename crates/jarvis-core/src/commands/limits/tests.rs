@@ -0,0 +1,52 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use super::{check, refusal};
+use crate::commands::JCommand;
+
+fn command(cooldown: u64, max_per_minute: u32) -> JCommand {
+    serde_json::from_value(serde_json::json!({
+        "id": "lights", "type": "cli", "cooldown": cooldown, "max_per_minute": max_per_minute,
+    })).unwrap()
+}
+
+#[test]
+fn enforces_cooldown() {
+    let cmd = command(2000, 0);
+    let mut runs = VecDeque::new();
+    let start = Instant::now();
+
+    assert!(check(&mut runs, &cmd, start).is_ok());
+    assert!(check(&mut runs, &cmd, start + Duration::from_millis(500)).is_err());
+    assert!(check(&mut runs, &cmd, start + Duration::from_millis(2000)).is_ok());
+}
+
+#[test]
+fn enforces_max_per_minute() {
+    let cmd = command(0, 3);
+    let mut runs = VecDeque::new();
+    let start = Instant::now();
+
+    for i in 0..3 {
+        assert!(check(&mut runs, &cmd, start + Duration::from_secs(i)).is_ok());
+    }
+    assert!(check(&mut runs, &cmd, start + Duration::from_secs(10)).is_err());
+
+    // the first run left the window
+    assert!(check(&mut runs, &cmd, start + Duration::from_secs(60)).is_ok());
+    assert!(check(&mut runs, &cmd, start + Duration::from_secs(61)).is_ok());
+    assert!(check(&mut runs, &cmd, start + Duration::from_secs(61)).is_err());
+}
+
+#[test]
+fn refusal_does_not_record() {
+    let cmd = command(2000, 0);
+    let mut runs = VecDeque::new();
+    let start = Instant::now();
+
+    assert!(refusal(&mut runs, &cmd, start).is_ok());
+    assert!(runs.is_empty());
+
+    assert!(check(&mut runs, &cmd, start).is_ok());
+    assert!(refusal(&mut runs, &cmd, start + Duration::from_millis(500)).is_err());
+}
@@ -0,0 +1,100 @@
+#[cfg(test)]
+mod tests;
+
+use std::path::Path;
+
+use super::{Capability, JCommand, PackManifest};
+use crate::i18n;
+
+const KNOWN_PLATFORMS: &[&str] = &["windows", "linux", "macos"];
+
+// Check the [pack] table, Err = the pack can't be used here
+pub fn validate(manifest: &mut PackManifest, dir: &Path, os: &str, app_version: &str) -> Result<(), String> {
+    if manifest.name.trim().is_empty() {
+        manifest.name = dir.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+    }
+
+    if !manifest.version.is_empty() && parse_version(&manifest.version).is_none() {
+        return Err(format!("invalid version '{}'", manifest.version));
+    }
+
+    if !manifest.min_app_version.is_empty() {
+        let required = parse_version(&manifest.min_app_version)
+            .ok_or_else(|| format!("invalid min_app_version '{}'", manifest.min_app_version))?;
+
+        // unknown app version (dev builds) passes
+        if parse_version(app_version).is_some_and(|current| current < required) {
+            return Err(format!("needs app version {} or newer, running {}", manifest.min_app_version, app_version));
+        }
+    }
+
+    if let Some(unknown) = manifest.platforms.iter().find(|p| !KNOWN_PLATFORMS.contains(&p.as_str())) {
+        return Err(format!("unknown platform '{}'", unknown));
+    }
+    if !manifest.platforms.is_empty() && !manifest.platforms.iter().any(|p| p == os) {
+        return Err(format!("only available on {}", manifest.platforms.join(", ")));
+    }
+
+    // phrases fall back to another language, so these only warn
+    for lang in &manifest.languages {
+        if !i18n::SUPPORTED_LANGUAGES.contains(&lang.as_str()) {
+            warn!("Pack '{}' lists unsupported language '{}'", manifest.name, lang);
+        }
+    }
+    let current = i18n::get_language();
+    if !manifest.languages.is_empty() && !manifest.languages.contains(&current) {
+        warn!("Pack '{}' has no phrases for '{}' ({})", manifest.name, current, manifest.languages.join(", "));
+    }
+
+    Ok(())
+}
+
+// Capability a command type needs declared in its pack.
+// Key presses can type into a terminal and a D-Bus method call can do anything the user can,
+// so both count as exec. Property reads and media player actions stay free.
+pub fn required_capability(cmd: &JCommand) -> Option<Capability> {
+    match cmd.cmd_type.as_str() {
+        "cli" | "ahk" | "keys" => Some(Capability::Exec),
+        "http" => Some(Capability::Network),
+        "dbus" if cmd.mpris.is_empty() && cmd.dbus.as_ref().is_none_or(|c| c.property.is_empty()) => Some(Capability::Exec),
+        _ => None,
+    }
+}
+
+// Drop commands needing undeclared capabilities, the rest get the pack's grants
+pub fn apply(manifest: &PackManifest, commands: Vec<JCommand>) -> Vec<JCommand> {
+    commands.into_iter()
+        .filter_map(|mut cmd| {
+            if let Some(needed) = required_capability(&cmd) {
+                if !manifest.capabilities.contains(&needed) {
+                    warn!("Skipping command '{}' of pack '{}': '{}' commands need the '{}' capability",
+                        cmd.id, manifest.name, cmd.cmd_type, needed.as_str());
+                    return None;
+                }
+            }
+
+            if !cmd.sandbox.is_empty() {
+                warn!("Command '{}': sandbox is ignored, pack '{}' declares its capabilities", cmd.id, manifest.name);
+            }
+
+            cmd.pack_capabilities = Some(manifest.capabilities.clone());
+            Some(cmd)
+        })
+        .collect()
+}
+
+// "1.2" / "1.2.3" / "v1.2.3-beta" -> (1, 2, 3)
+pub fn parse_version(version: &str) -> Option<(u64, u64, u64)> {
+    let version = version.trim().trim_start_matches('v');
+    let core = version.split(['-', '+']).next()?;
+
+    let mut parts = core.split('.').map(|p| p.parse::<u64>());
+    let major = parts.next()?.ok()?;
+    let minor = parts.next().unwrap_or(Ok(0)).ok()?;
+    let patch = parts.next().unwrap_or(Ok(0)).ok()?;
+
+    if parts.next().is_some() {
+        return None;
+    }
+    Some((major, minor, patch))
+}
@@ -0,0 +1,115 @@
+use std::path::Path;
+
+use super::*;
+use crate::commands::JCommandsList;
+
+const PACK: &str = r#"
+[pack]
+version = "1.2.0"
+author = "someone"
+min_app_version = "0.5"
+languages = ["en", "ru"]
+capabilities = ["network"]
+
+[[commands]]
+id = "weather"
+type = "http"
+url = "https://example.com"
+
+[[commands]]
+id = "shell"
+type = "cli"
+cli_cmd = "echo hi"
+
+[[commands]]
+id = "script"
+type = "lua"
+sandbox = "full"
+"#;
+
+fn manifest(pack: &str) -> PackManifest {
+    let file: JCommandsList = toml::from_str(pack).unwrap();
+    file.pack.unwrap()
+}
+
+#[test]
+fn parses_versions() {
+    assert_eq!(parse_version("1.2.3"), Some((1, 2, 3)));
+    assert_eq!(parse_version("v0.5"), Some((0, 5, 0)));
+    assert_eq!(parse_version("2.0.0-beta.1"), Some((2, 0, 0)));
+    assert_eq!(parse_version("1.x"), None);
+    assert_eq!(parse_version("1.2.3.4"), None);
+}
+
+#[test]
+fn validates_manifest() {
+    let mut pack = manifest(PACK);
+    assert!(validate(&mut pack, Path::new("packs/weather"), "linux", "0.6.1").is_ok());
+    assert_eq!(pack.name, "weather");
+
+    assert!(validate(&mut manifest(PACK), Path::new("w"), "linux", "0.4.9").is_err());
+    // dev builds without a version pass
+    assert!(validate(&mut manifest(PACK), Path::new("w"), "linux", "").is_ok());
+
+    let mut bad = manifest(PACK);
+    bad.version = "one".into();
+    assert!(validate(&mut bad, Path::new("w"), "linux", "1.0.0").is_err());
+
+    let mut windows_only = manifest(PACK);
+    windows_only.platforms = vec!["windows".into()];
+    assert!(validate(&mut windows_only, Path::new("w"), "linux", "1.0.0").is_err());
+    windows_only.platforms = vec!["dos".into()];
+    assert!(validate(&mut windows_only, Path::new("w"), "windows", "1.0.0").is_err());
+
+    assert!(toml::from_str::<JCommandsList>("commands = []\n[pack]\ncapabilities = [\"root\"]").is_err());
+}
+
+#[test]
+fn grants_declared_capabilities() {
+    let file: JCommandsList = toml::from_str(PACK).unwrap();
+    let pack = file.pack.unwrap();
+
+    let commands = apply(&pack, file.commands);
+    let ids: Vec<&str> = commands.iter().map(|c| c.id.as_str()).collect();
+
+    // cli needs exec, which the pack didn't declare
+    assert_eq!(ids, ["weather", "script"]);
+    assert_eq!(commands[1].pack_capabilities, Some(vec![Capability::Network]));
+}
+
+#[test]
+fn keys_and_dbus_calls_need_exec() {
+    let file: JCommandsList = toml::from_str(r#"
+[[commands]]
+id = "type"
+type = "keys"
+keys = ["ctrl+c"]
+
+[[commands]]
+id = "suspend"
+type = "dbus"
+[commands.dbus]
+bus = "system"
+dest = "org.freedesktop.login1"
+path = "/org/freedesktop/login1"
+method = "org.freedesktop.login1.Manager.Suspend"
+args = [true]
+
+[[commands]]
+id = "battery"
+type = "dbus"
+[commands.dbus]
+bus = "system"
+dest = "org.freedesktop.UPower"
+path = "/org/freedesktop/UPower/devices/DisplayDevice"
+property = "org.freedesktop.UPower.Device.Percentage"
+
+[[commands]]
+id = "next_track"
+type = "dbus"
+mpris = "next"
+"#).unwrap();
+
+    let needed: Vec<Option<Capability>> = file.commands.iter().map(required_capability).collect();
+    assert_eq!(needed, [Some(Capability::Exec), Some(Capability::Exec), None, None]);
+}
@@ -0,0 +1,176 @@
+#[cfg(test)]
+mod tests;
+
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::time::Duration;
+
+use super::{CommandOutcome, FailurePolicy, JCommand, JCommandsList, SlotValue, StepCondition};
+use crate::COMMANDS_LIST;
+
+#[cfg(feature = "jarvis_app")]
+use crate::ipc::{self, IpcEvent};
+
+// Drop sequences that could never run (unknown steps, cycles), repeats until nothing else breaks
+pub fn validate(commands: &mut [JCommandsList]) {
+    loop {
+        let broken = find_broken(commands);
+        if broken.is_empty() {
+            break;
+        }
+
+        for (id, reason) in &broken {
+            warn!("Skipping sequence '{}': {}", id, reason);
+        }
+        for list in commands.iter_mut() {
+            list.commands.retain(|c| !broken.contains_key(&c.id));
+        }
+    }
+}
+
+// Broken sequence id -> reason
+fn find_broken(commands: &[JCommandsList]) -> HashMap<String, String> {
+    let by_id: HashMap<&str, &JCommand> = commands.iter()
+        .flat_map(|l| l.commands.iter())
+        .map(|c| (c.id.as_str(), c))
+        .collect();
+
+    let mut broken = HashMap::new();
+
+    for cmd in by_id.values().filter(|c| c.cmd_type == "sequence") {
+        if cmd.steps.is_empty() {
+            broken.insert(cmd.id.clone(), "no steps".to_string());
+        } else if let Some(step) = cmd.steps.iter().find(|s| !by_id.contains_key(s.command.as_str())) {
+            broken.insert(cmd.id.clone(), format!("unknown step '{}'", step.command));
+        } else if let Some(step) = cmd.steps.iter().find(|s| by_id[s.command.as_str()].needs_confirmation()) {
+            // steps run without asking, the confirmation has to be on the sequence itself
+            if !cmd.needs_confirmation() {
+                broken.insert(cmd.id.clone(), format!("step '{}' needs confirmation, set confirm on the sequence", step.command));
+            }
+        }
+    }
+
+    // depth-first walk over sequence -> step edges, a step already on the path is a cycle
+    let mut done: HashSet<&str> = HashSet::new();
+    let mut ids: Vec<&str> = by_id.keys().copied().collect();
+    ids.sort(); // stable reports
+
+    for id in ids {
+        let mut path = Vec::new();
+        walk(id, &by_id, &mut path, &mut done, &mut broken);
+    }
+
+    broken
+}
+
+fn walk<'a>(
+    id: &'a str,
+    by_id: &HashMap<&'a str, &'a JCommand>,
+    path: &mut Vec<&'a str>,
+    done: &mut HashSet<&'a str>,
+    broken: &mut HashMap<String, String>,
+) {
+    if let Some(pos) = path.iter().position(|p| *p == id) {
+        let cycle = path[pos..].iter().chain([&id]).copied().collect::<Vec<_>>().join(" -> ");
+        for member in &path[pos..] {
+            broken.entry(member.to_string()).or_insert_with(|| format!("cycle {}", cycle));
+        }
+        return;
+    }
+    if done.contains(id) {
+        return;
+    }
+
+    let Some(cmd) = by_id.get(id) else { return };
+    if cmd.cmd_type == "sequence" {
+        path.push(id);
+        for step in &cmd.steps {
+            walk(step.command.as_str(), by_id, path, done, broken);
+        }
+        path.pop();
+    }
+
+    done.insert(id);
+}
+
+pub fn execute(
+    cmd_config: &JCommand,
+    phrase: Option<&str>,
+    slots: Option<&HashMap<String, SlotValue>>,
+) -> Result<CommandOutcome, String> {
+    let commands = COMMANDS_LIST.get().ok_or("Commands not loaded")?;
+
+    let mut outcome = CommandOutcome::ok(true);
+    let mut previous_ok = true;
+
+    for (index, step) in cmd_config.steps.iter().enumerate() {
+        let run = match step.when {
+            StepCondition::Always => true,
+            StepCondition::Success => previous_ok,
+            StepCondition::Failure => !previous_ok,
+        } && (step.if_slot.is_empty() || slots.is_some_and(|s| s.contains_key(&step.if_slot)));
+
+        if !run {
+            debug!("Sequence '{}': skipping step {} ({})", cmd_config.id, index, step.command);
+            report(cmd_config, index, &step.command, true, true);
+            continue;
+        }
+
+        if step.delay > 0 {
+            std::thread::sleep(Duration::from_millis(step.delay));
+        }
+
+        let (path, cmd) = find(commands, &step.command)
+            .ok_or_else(|| format!("Sequence '{}': unknown step '{}'", cmd_config.id, step.command))?;
+
+        info!("Sequence '{}': step {} ({})", cmd_config.id, index, step.command);
+        let step_slots = if step.pass_slots { slots } else { None };
+        let result = super::execute_command(path, cmd, phrase, step_slots);
+
+        previous_ok = matches!(&result, Ok(o) if o.success);
+        report(cmd_config, index, &step.command, previous_ok, false);
+
+        match result {
+            Ok(step_outcome) => {
+                outcome.success &= step_outcome.success;
+                outcome.chain = step_outcome.chain;
+                outcome.exit_code = step_outcome.exit_code.or(outcome.exit_code);
+                outcome.output = step_outcome.output.or(outcome.output);
+            }
+            Err(e) => {
+                warn!("Sequence '{}': step {} failed: {}", cmd_config.id, step.command, e);
+                outcome.success = false;
+            }
+        }
+
+        if !previous_ok && cmd_config.on_failure == FailurePolicy::Stop {
+            warn!("Sequence '{}' stopped at step {} ({})", cmd_config.id, index, step.command);
+            break;
+        }
+
+        // "stop_chaining" ends the sequence as well
+        if !outcome.chain {
+            break;
+        }
+    }
+
+    Ok(outcome)
+}
+
+fn find<'a>(commands: &'a [JCommandsList], id: &str) -> Option<(&'a PathBuf, &'a JCommand)> {
+    commands.iter()
+        .flat_map(|l| l.commands.iter().map(move |c| (&l.path, c)))
+        .find(|(_, c)| c.id == id)
+}
+
+#[allow(unused_variables)]
+fn report(cmd_config: &JCommand, index: usize, step: &str, success: bool, skipped: bool) {
+    #[cfg(feature = "jarvis_app")]
+    ipc::send(IpcEvent::SequenceStep {
+        id: cmd_config.id.clone(),
+        index,
+        command: step.to_string(),
+        success,
+        skipped,
+    });
+}
@@ -0,0 +1,101 @@
+use crate::commands::sequence::{find_broken, validate};
+use crate::commands::JCommandsList;
+
+const PACK: &str = r#"
+[[commands]]
+id = "lights"
+type = "voice"
+
+[[commands]]
+id = "music"
+type = "voice"
+
+[[commands]]
+id = "morning"
+type = "sequence"
+steps = [
+    { command = "lights" },
+    { command = "music", delay = 500, when = "success" },
+]
+
+[[commands]]
+id = "ping"
+type = "sequence"
+steps = [{ command = "pong" }]
+
+[[commands]]
+id = "pong"
+type = "sequence"
+steps = [{ command = "ping" }]
+
+[[commands]]
+id = "uses_loop"
+type = "sequence"
+steps = [{ command = "lights" }, { command = "ping" }]
+
+[[commands]]
+id = "typo"
+type = "sequence"
+steps = [{ command = "light" }]
+"#;
+
+fn pack() -> Vec<JCommandsList> {
+    vec![toml::from_str(PACK).unwrap()]
+}
+
+#[test]
+fn detects_cycles_and_unknown_steps() {
+    let broken = find_broken(&pack());
+
+    assert!(broken["ping"].contains("cycle"));
+    assert!(broken["pong"].contains("cycle"));
+    assert!(broken["typo"].contains("unknown step 'light'"));
+    assert!(!broken.contains_key("morning"));
+    assert!(!broken.contains_key("uses_loop")); // fine until the loop is removed
+}
+
+#[test]
+fn validate_drops_everything_unrunnable() {
+    let mut commands = pack();
+    validate(&mut commands);
+
+    let ids: Vec<&str> = commands[0].commands.iter().map(|c| c.id.as_str()).collect();
+    assert_eq!(ids, ["lights", "music", "morning"]);
+}
+
+#[test]
+fn self_reference_is_a_cycle() {
+    let commands: Vec<JCommandsList> = vec![toml::from_str(r#"
+[[commands]]
+id = "again"
+type = "sequence"
+steps = [{ command = "again" }]
+"#).unwrap()];
+
+    assert!(find_broken(&commands)["again"].contains("again -> again"));
+}
+
+#[test]
+fn confirm_steps_need_a_confirmed_sequence() {
+    let commands: Vec<JCommandsList> = vec![toml::from_str(r#"
+[[commands]]
+id = "shutdown"
+type = "cli"
+confirm = true
+
+[[commands]]
+id = "good_night"
+type = "sequence"
+steps = [{ command = "shutdown" }]
+
+[[commands]]
+id = "good_night_confirmed"
+type = "sequence"
+confirm = true
+steps = [{ command = "shutdown" }]
+"#).unwrap()];
+
+    let broken = find_broken(&commands);
+    assert!(broken["good_night"].contains("step 'shutdown' needs confirmation"));
+    assert!(!broken.contains_key("good_night_confirmed"));
+}
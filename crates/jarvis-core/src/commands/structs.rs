@@ -2,27 +2,82 @@ use std::{collections::HashMap, path::PathBuf, sync::Arc};
 use serde::{Serialize, Deserialize};
 use parking_lot::RwLock;
 
+use crate::input::InputStep;
+use crate::dbus::DbusCall;
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct JCommandsList {
     #[serde(skip)]
     pub path: PathBuf,
 
+    // optional [pack] table, see pack.rs
+    #[serde(default)]
+    pub pack: Option<PackManifest>,
+
     pub commands: Vec<JCommand>,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct PackManifest {
+    // defaults to the folder name
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub version: String, // "1.2.0"
+    #[serde(default)]
+    pub author: String,
+    #[serde(default)]
+    pub description: String,
+    // the pack is skipped on older apps
+    #[serde(default)]
+    pub min_app_version: String,
+    // languages the phrases are written for, empty = any
+    #[serde(default)]
+    pub languages: Vec<String>,
+    // "windows", "linux", "macos", empty = any
+    #[serde(default)]
+    pub platforms: Vec<String>,
+    // what the pack's commands may do, Lua scripts get exactly these
+    #[serde(default)]
+    pub capabilities: Vec<Capability>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum Capability {
+    Network,
+    FsWrite,
+    Exec,
+    Clipboard,
+}
+
+impl Capability {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Capability::Network => "network",
+            Capability::FsWrite => "fs-write",
+            Capability::Exec => "exec",
+            Capability::Clipboard => "clipboard",
+        }
+    }
+}
+
 
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct JCommand {
     pub id: String,
 
-    // Available command types are: "lua", "ahk", "cli", "voice", "terminate", "stop_chaining"
+    // Available command types are: "lua", "ahk", "cli", "http", "keys", "dbus", "sequence", "voice", "terminate", "stop_chaining"
     #[serde(rename = "type")]
     pub cmd_type: String,
     
     #[serde(default)]
     pub description: String,
     
+    // exe_path, exe_args, cli_cmd and cli_args may contain {phrase}, {lang}, {slot:name}, {env:NAME}
+    // (see template.rs), values are shell-quoted where a shell is involved
+
     // for "ahk" type
     #[serde(default)]
     pub exe_path: String,
@@ -35,6 +90,50 @@ pub struct JCommand {
     #[serde(default)]
     pub cli_args: Vec<String>,
     
+    // for "cli" and "ahk" types: wait for the process to exit and check its exit code,
+    // `timeout` then limits the run time (0 = DEFAULT_PROCESS_TIMEOUT)
+    #[serde(default)]
+    pub wait: bool,
+    // exit codes treated as success, empty = [0]
+    #[serde(default)]
+    pub success_codes: Vec<i32>,
+    // what to do with stdout (or the http response value), anything but "none" implies wait
+    #[serde(default)]
+    pub output: OutputMode,
+
+    // for "http" type, url / headers / body may contain {placeholders} (see template.rs)
+    #[serde(default)]
+    pub url: String,
+    #[serde(default)]
+    pub method: String, // empty = GET
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+    #[serde(default)]
+    pub body: String,
+    // statuses treated as success, empty = any 2xx
+    #[serde(default)]
+    pub expect_status: Vec<u16>,
+    // value to take from a json response, e.g. "$.state.temperature", goes to `output`
+    #[serde(default)]
+    pub response_path: String,
+
+    // for "keys" type: chords ("ctrl+c", "volume_up"), { text = "..." } and { delay = ms }
+    #[serde(default)]
+    pub keys: Vec<InputStep>,
+
+    // for "dbus" type: a method call or property read, [commands.dbus] dest / path / method / args
+    #[serde(default)]
+    pub dbus: Option<DbusCall>,
+    // or a media player action: "play_pause", "play", "pause", "stop", "next", "previous", "now_playing"
+    #[serde(default)]
+    pub mpris: String,
+
+    // for "sequence" type: other commands run in order
+    #[serde(default)]
+    pub steps: Vec<SequenceStep>,
+    #[serde(default)]
+    pub on_failure: FailurePolicy,
+
     // #[serde(default)]
     // pub sounds: Vec<String>,
 
@@ -42,12 +141,27 @@ pub struct JCommand {
     #[serde(default)]
     pub script: String,
 
+    // ask "are you sure?" before running, cancelled on "no" or CONFIRM_TIMEOUT_MS of silence
+    #[serde(default)]
+    pub confirm: bool,
+    // phrase the user must say to confirm instead of "yes", implies confirm
+    #[serde(default)]
+    pub confirm_phrase: String,
+
+    // ms before the command may run again, 0 = no cooldown
+    #[serde(default)]
+    pub cooldown: u64,
+    // runs allowed within a minute, 0 = unlimited
+    #[serde(default)]
+    pub max_per_minute: u32,
+
     // Lua sandbox level: "minimal", "standard", "full"
-    // basically this is an access level
+    // basically this is an access level, ignored when the pack declares capabilities in [pack]
     #[serde(default)]
     pub sandbox: String,
 
-    // Script timeout in milliseconds (default 10000 = 10s)
+    // Time limit in milliseconds, 0 = the default of the command type:
+    // lua scripts DEFAULT_LUA_TIMEOUT (10s), waited cli/ahk processes DEFAULT_PROCESS_TIMEOUT (30s)
     #[serde(default)]
     pub timeout: u64,
 
@@ -63,6 +177,22 @@ pub struct JCommand {
     #[serde(default)]
     pub slots: HashMap<String, SlotDefinition>,
 
+    // Platforms the command works on ("windows", "linux", "macos"), empty = any
+    #[serde(default)]
+    pub platforms: Vec<String>,
+
+    // Per-OS overrides, e.g. [commands.linux] cli_cmd = "gnome-calculator"
+    #[serde(default)]
+    pub windows: Option<PlatformOverride>,
+    #[serde(default)]
+    pub linux: Option<PlatformOverride>,
+    #[serde(default)]
+    pub macos: Option<PlatformOverride>,
+
+    // capabilities of the pack manifest, set by the loader
+    #[serde(skip, default)]
+    pub pack_capabilities: Option<Vec<Capability>>,
+
     // CACHE
     #[serde(skip, default)]
     sounds_cache: RwLock<HashMap<String, Arc<Vec<String>>>>,
@@ -86,7 +216,31 @@ impl Clone for JCommand {
             cli_cmd: self.cli_cmd.clone(),
             cli_args: self.cli_args.clone(),
 
+            wait: self.wait,
+            success_codes: self.success_codes.clone(),
+            output: self.output,
+
+            url: self.url.clone(),
+            method: self.method.clone(),
+            headers: self.headers.clone(),
+            body: self.body.clone(),
+            expect_status: self.expect_status.clone(),
+            response_path: self.response_path.clone(),
+
+            keys: self.keys.clone(),
+
+            dbus: self.dbus.clone(),
+            mpris: self.mpris.clone(),
+
+            steps: self.steps.clone(),
+            on_failure: self.on_failure,
+
             script: self.script.clone(),
+            confirm: self.confirm,
+            confirm_phrase: self.confirm_phrase.clone(),
+            cooldown: self.cooldown,
+            max_per_minute: self.max_per_minute,
+
             sandbox: self.sandbox.clone(),
             timeout: self.timeout.clone(),
 
@@ -95,6 +249,13 @@ impl Clone for JCommand {
 
             slots: self.slots.clone(),
 
+            platforms: self.platforms.clone(),
+            windows: self.windows.clone(),
+            linux: self.linux.clone(),
+            macos: self.macos.clone(),
+
+            pack_capabilities: self.pack_capabilities.clone(),
+
             // empty caches for cloned instance
             sounds_cache: RwLock::new(HashMap::new()),
             phrases_cache: RwLock::new(HashMap::new()),
@@ -138,6 +299,51 @@ impl JCommand {
     }
 
 
+    pub fn needs_confirmation(&self) -> bool {
+        self.confirm || !self.confirm_phrase.is_empty()
+    }
+
+    // Merge the override for `os` into the command fields
+    pub fn apply_platform(&mut self, os: &str) {
+        let over = match os {
+            "windows" => self.windows.take(),
+            "linux" => self.linux.take(),
+            "macos" => self.macos.take(),
+            _ => None,
+        };
+        let Some(over) = over else { return };
+
+        if let Some(v) = over.cmd_type { self.cmd_type = v; }
+        if let Some(v) = over.exe_path { self.exe_path = v; }
+        if let Some(v) = over.exe_args { self.exe_args = v; }
+        if let Some(v) = over.cli_cmd { self.cli_cmd = v; }
+        if let Some(v) = over.cli_args { self.cli_args = v; }
+        if let Some(v) = over.script { self.script = v; }
+
+        // an override is an implementation for that platform
+        if !self.platforms.is_empty() && !self.platforms.iter().any(|p| p == os) {
+            self.platforms.push(os.to_string());
+        }
+    }
+
+    // Why the command can't run on `os`, call after apply_platform
+    pub fn unsupported_reason(&self, os: &str) -> Option<String> {
+        if !self.platforms.is_empty() && !self.platforms.iter().any(|p| p == os) {
+            return Some(format!("only available on {}", self.platforms.join(", ")));
+        }
+
+        // AutoHotkey executables
+        if self.cmd_type == "ahk" && os != "windows" {
+            return Some("AutoHotkey commands need Windows".into());
+        }
+
+        if self.cmd_type == "dbus" && os != "linux" {
+            return Some("D-Bus commands need Linux".into());
+        }
+
+        None
+    }
+
     // shared fallback
     fn resolve_localized(&self, map: &HashMap<String, Vec<String>>, lang: &str) -> Vec<String> {
         // exact match
@@ -157,6 +363,85 @@ impl JCommand {
     }
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputMode {
+    #[default]
+    None,
+    Speak,
+    Notify,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SequenceStep {
+    pub command: String, // id of the command to run
+    // ms to wait before the step
+    #[serde(default)]
+    pub delay: u64,
+    // give the step the slots extracted for the sequence
+    #[serde(default = "default_pass_slots")]
+    pub pass_slots: bool,
+    #[serde(default)]
+    pub when: StepCondition,
+    // run only if this slot was extracted
+    #[serde(default)]
+    pub if_slot: String,
+}
+
+fn default_pass_slots() -> bool {
+    true
+}
+
+// Relative to the previous step that ran
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum StepCondition {
+    #[default]
+    Always,
+    Success,
+    Failure,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum FailurePolicy {
+    #[default]
+    Stop,
+    Continue,
+}
+
+// What actually happened when a command ran
+#[derive(Debug, Clone)]
+pub struct CommandOutcome {
+    pub success: bool,
+    pub chain: bool,
+    pub exit_code: Option<i32>, // only for waited processes, http status for "http" commands
+    pub output: Option<String>, // captured stdout (or stderr on failure)
+}
+
+impl CommandOutcome {
+    pub fn ok(chain: bool) -> Self {
+        Self { success: true, chain, exit_code: None, output: None }
+    }
+}
+
+// Fields replaced on a specific OS, everything else is inherited from the command
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct PlatformOverride {
+    #[serde(rename = "type", default)]
+    pub cmd_type: Option<String>,
+    #[serde(default)]
+    pub exe_path: Option<String>,
+    #[serde(default)]
+    pub exe_args: Option<Vec<String>>,
+    #[serde(default)]
+    pub cli_cmd: Option<String>,
+    #[serde(default)]
+    pub cli_args: Option<Vec<String>>,
+    #[serde(default)]
+    pub script: Option<String>,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct SlotDefinition {
     // Entity label for GLiNER (e.g. "city name", "song title", "number")
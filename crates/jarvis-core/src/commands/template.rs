@@ -0,0 +1,132 @@
+#[cfg(test)]
+mod tests;
+
+use std::collections::HashMap;
+
+use super::SlotValue;
+
+// Values available to {placeholders} in command fields
+pub struct TemplateContext<'a> {
+    pub phrase: &'a str,
+    pub language: &'a str,
+    pub slots: Option<&'a HashMap<String, SlotValue>>,
+}
+
+// How substituted values are quoted
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Escape {
+    None,  // passed as argv, no shell involved
+    Posix, // sh -c
+    Cmd,   // cmd /C
+    Url,   // percent-encoded, for url paths and queries
+    Json,  // inside a json string literal
+}
+
+impl Escape {
+    // quoting for the shell used by execute_cli
+    pub fn shell() -> Escape {
+        if cfg!(target_os = "windows") {
+            Escape::Cmd
+        } else {
+            Escape::Posix
+        }
+    }
+}
+
+// Replace {phrase}, {lang}, {slot:name} and {env:NAME} in a command field.
+// Unknown placeholders are kept as is, so shell braces like ${HOME} or awk '{print $1}' survive.
+pub fn interpolate(template: &str, ctx: &TemplateContext, escape: Escape) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+
+        let value = after.find('}')
+            .map(|end| &after[..end])
+            .filter(|name| !name.contains('{'))
+            .and_then(|name| resolve(name, ctx).map(|v| (name, v)));
+
+        match value {
+            Some((name, v)) => {
+                out.push_str(&quote(&v, escape_for(name, escape)));
+                rest = &after[name.len() + 1..];
+            }
+            None => {
+                out.push('{');
+                rest = after;
+            }
+        }
+    }
+
+    out.push_str(rest);
+    out
+}
+
+// {env:} and {lang} go into urls as written, e.g. url = "{env:HA_URL}/api/states"
+fn escape_for(name: &str, escape: Escape) -> Escape {
+    let spoken = name == "phrase" || name.starts_with("slot:");
+    if escape == Escape::Url && !spoken {
+        Escape::None
+    } else {
+        escape
+    }
+}
+
+fn resolve(name: &str, ctx: &TemplateContext) -> Option<String> {
+    if let Some(slot) = name.strip_prefix("slot:") {
+        let value = ctx.slots.and_then(|s| s.get(slot.trim()));
+        if value.is_none() {
+            debug!("Slot '{}' is not filled, substituting empty value", slot);
+        }
+        return Some(value.map(slot_to_string).unwrap_or_default());
+    }
+
+    if let Some(var) = name.strip_prefix("env:") {
+        return Some(std::env::var(var.trim()).unwrap_or_default());
+    }
+
+    match name {
+        "phrase" => Some(ctx.phrase.to_string()),
+        "lang" => Some(ctx.language.to_string()),
+        _ => None,
+    }
+}
+
+fn slot_to_string(value: &SlotValue) -> String {
+    match value {
+        SlotValue::Text(s) => s.clone(),
+        SlotValue::Number(n) => n.to_string(), // 5.0 prints as "5"
+    }
+}
+
+fn quote(value: &str, escape: Escape) -> String {
+    match escape {
+        Escape::None => value.to_string(),
+
+        // single quotes disable everything, a quote itself is closed, escaped and reopened
+        Escape::Posix => format!("'{}'", value.replace('\'', r"'\''")),
+
+        // cmd has no way to escape " or %VAR% inside quotes, so those are dropped
+        Escape::Cmd => {
+            let cleaned: String = value.chars()
+                .filter(|c| !matches!(c, '"' | '%' | '\r' | '\n'))
+                .collect();
+            format!("\"{}\"", cleaned)
+        }
+
+        Escape::Url => value.bytes()
+            .map(|b| match b {
+                b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => (b as char).to_string(),
+                _ => format!("%{:02X}", b),
+            })
+            .collect(),
+
+        // the template provides the quotes
+        Escape::Json => {
+            let quoted = serde_json::to_string(value).unwrap_or_default();
+            quoted[1..quoted.len() - 1].to_string()
+        }
+    }
+}
@@ -0,0 +1,69 @@
+use std::collections::HashMap;
+
+use crate::commands::template::{interpolate, Escape, TemplateContext};
+use crate::commands::SlotValue;
+
+fn slots() -> HashMap<String, SlotValue> {
+    HashMap::from([
+        ("city".to_string(), SlotValue::Text("New York".into())),
+        ("count".to_string(), SlotValue::Number(5.0)),
+        ("evil".to_string(), SlotValue::Text("x'; rm -rf ~; echo '".into())),
+    ])
+}
+
+#[test]
+fn substitutes_placeholders() {
+    let slots = slots();
+    let ctx = TemplateContext { phrase: "weather in new york", language: "en", slots: Some(&slots) };
+
+    assert_eq!(
+        interpolate("{lang}: {slot:city} x{slot:count} ({phrase})", &ctx, Escape::None),
+        "en: New York x5 (weather in new york)"
+    );
+    assert_eq!(interpolate("[{slot:missing}]", &ctx, Escape::None), "[]");
+
+    std::env::set_var("JARVIS_TEMPLATE_TEST", "value");
+    assert_eq!(interpolate("{env:JARVIS_TEMPLATE_TEST}", &ctx, Escape::None), "value");
+}
+
+#[test]
+fn keeps_unknown_braces() {
+    let ctx = TemplateContext { phrase: "hi", language: "en", slots: None };
+
+    assert_eq!(
+        interpolate("awk '{print $1}' ${HOME} {{phrase}} {", &ctx, Escape::None),
+        "awk '{print $1}' ${HOME} {hi} {"
+    );
+}
+
+#[test]
+fn escapes_for_shells() {
+    let slots = slots();
+    let ctx = TemplateContext { phrase: "", language: "en", slots: Some(&slots) };
+
+    assert_eq!(
+        interpolate("echo {slot:evil}", &ctx, Escape::Posix),
+        r"echo 'x'\''; rm -rf ~; echo '\'''"
+    );
+    assert_eq!(interpolate("start {slot:city}", &ctx, Escape::Cmd), "start \"New York\"");
+
+    let slots = HashMap::from([("q".to_string(), SlotValue::Text("a\" & del %PATH%".into()))]);
+    let ctx = TemplateContext { phrase: "", language: "en", slots: Some(&slots) };
+    assert_eq!(interpolate("{slot:q}", &ctx, Escape::Cmd), "\"a & del PATH\"");
+}
+
+#[test]
+fn escapes_for_urls_and_json() {
+    let slots = HashMap::from([("q".to_string(), SlotValue::Text("rock & \"roll\"/ü".into()))]);
+    let ctx = TemplateContext { phrase: "", language: "en", slots: Some(&slots) };
+
+    assert_eq!(interpolate("/search?q={slot:q}", &ctx, Escape::Url), "/search?q=rock%20%26%20%22roll%22%2F%C3%BC");
+
+    // only spoken values are encoded, a base url from the environment is kept as is
+    std::env::set_var("JARVIS_TEMPLATE_URL", "http://ha.local:8123");
+    assert_eq!(
+        interpolate("{env:JARVIS_TEMPLATE_URL}/{lang}/search?q={slot:q}", &ctx, Escape::Url),
+        "http://ha.local:8123/en/search?q=rock%20%26%20%22roll%22%2F%C3%BC"
+    );
+    assert_eq!(interpolate(r#"{"q": "{slot:q}"}"#, &ctx, Escape::Json), r#"{"q": "rock & \"roll\"/ü"}"#);
+}
@@ -0,0 +1,111 @@
+use std::path::Path;
+
+use crate::commands::{cli_command, for_platform, run_process, JCommand, JCommandsList};
+
+const PACK: &str = r#"
+[[commands]]
+id = "calc"
+type = "cli"
+cli_cmd = "calc"
+[commands.linux]
+cli_cmd = "gnome-calculator"
+[commands.macos]
+type = "cli"
+cli_cmd = "open"
+cli_args = ["-a", "Calculator"]
+
+[[commands]]
+id = "browser"
+type = "ahk"
+exe_path = "ahk/Run browser.exe"
+[commands.linux]
+type = "cli"
+cli_cmd = "xdg-open http://"
+
+[[commands]]
+id = "close_browser"
+type = "ahk"
+exe_path = "ahk/Close browser.exe"
+
+[[commands]]
+id = "shutdown"
+type = "cli"
+cli_cmd = "shutdown /s"
+platforms = ["windows"]
+"#;
+
+fn ids(os: &str) -> Vec<String> {
+    let file: JCommandsList = toml::from_str(PACK).unwrap();
+    for_platform(file.commands, os, Path::new("pack"))
+        .into_iter()
+        .map(|c| c.id)
+        .collect()
+}
+
+#[test]
+fn applies_platform_overrides() {
+    let file: JCommandsList = toml::from_str(PACK).unwrap();
+    let cmds = for_platform(file.commands, "macos", Path::new("pack"));
+
+    let calc = cmds.iter().find(|c| c.id == "calc").unwrap();
+    assert_eq!(calc.cli_cmd, "open");
+    assert_eq!(calc.cli_args, ["-a", "Calculator"]);
+
+    let file: JCommandsList = toml::from_str(PACK).unwrap();
+    let cmds = for_platform(file.commands, "linux", Path::new("pack"));
+
+    let browser = cmds.iter().find(|c| c.id == "browser").unwrap();
+    assert_eq!(browser.cmd_type, "cli");
+    assert_eq!(browser.exe_path, "ahk/Run browser.exe"); // untouched fields are inherited
+}
+
+#[test]
+fn skips_commands_without_implementation() {
+    assert_eq!(ids("windows"), ["calc", "browser", "close_browser", "shutdown"]);
+    assert_eq!(ids("linux"), ["calc", "browser"]);
+    assert_eq!(ids("macos"), ["calc"]);
+}
+
+#[cfg(unix)]
+#[test]
+fn reports_process_outcome() {
+    let cmd: JCommand = toml::from_str("id = \"t\"\ntype = \"cli\"\nwait = true\ntimeout = 300\nsuccess_codes = [0, 3]").unwrap();
+
+    let outcome = run_process(cli_command("echo hello", &[]), &cmd).unwrap();
+    assert!(outcome.success);
+    assert_eq!(outcome.output.as_deref(), Some("hello"));
+
+    let outcome = run_process(cli_command("exit 3", &[]), &cmd).unwrap();
+    assert!(outcome.success);
+
+    let outcome = run_process(cli_command("echo broken >&2; exit 1", &[]), &cmd).unwrap();
+    assert!(!outcome.success);
+    assert_eq!(outcome.exit_code, Some(1));
+    assert_eq!(outcome.output.as_deref(), Some("broken"));
+
+    assert!(run_process(cli_command("sleep 5", &[]), &cmd).unwrap_err().contains("timed out"));
+}
+
+#[cfg(unix)]
+#[test]
+fn passes_cli_args_as_positional() {
+    let cmd: JCommand = toml::from_str("id = \"t\"\ntype = \"cli\"\nwait = true").unwrap();
+
+    let args = ["it's".to_string(), "a b".to_string()];
+    let outcome = run_process(cli_command("printf '%s|' \"$1\" \"$2\"", &args), &cmd).unwrap();
+    assert_eq!(outcome.output.as_deref(), Some("it's|a b|"));
+}
+
+#[cfg(windows)]
+#[test]
+fn passes_cli_args_quoted_for_cmd() {
+    use crate::commands::template::{self, Escape, TemplateContext};
+
+    let cmd: JCommand = toml::from_str("id = \"t\"\ntype = \"cli\"\nwait = true").unwrap();
+    let ctx = TemplateContext { phrase: "a & b", language: "en", slots: None };
+
+    // cmd keeps the quotes for echo, the & stays inside them
+    let args = [template::interpolate("{phrase}", &ctx, Escape::Cmd)];
+    let outcome = run_process(cli_command("echo", &args), &cmd).unwrap();
+    assert_eq!(outcome.output.as_deref(), Some("\"a & b\""));
+}
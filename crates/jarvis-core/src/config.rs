@@ -21,6 +21,9 @@ use crate::IntentRecognitionEngine;
 use crate::SlotExtractionEngine;
 use crate::config::structs::NoiseSuppressionBackend;
 use crate::config::structs::VadBackend;
+use crate::config::structs::EchoCancellation;
+use crate::config::structs::CommandGrammarMode;
+use crate::config::structs::{EmbeddingPooling, EmbeddingQuantization};
 use crate::{APP_CONFIG_DIR, APP_DIRS, APP_LOG_DIR};
 
 #[allow(dead_code)]
@@ -94,6 +97,7 @@ pub const TRAY_TOOLTIP: &str = "Jarvis Voice Assistant";
 
 // RUSPOTTER
 pub const RUSPOTTER_MIN_SCORE: f32 = 0.62;
+pub const RUSPOTTER_NEAR_MISS_SCORE: f32 = 0.45; // lower bound for diagnostics near-miss capture
 
 #[cfg(feature="jarvis_app")]
 pub const RUSTPOTTER_DEFAULT_CONFIG: Lazy<RustpotterConfig> = Lazy::new(|| {
@@ -137,12 +141,18 @@ pub const KEYWORDS_PATH: &str = "resources/picovoice/keywords/";
 pub const DEFAULT_KEYWORD: &str = "jarvis_windows.ppn";
 pub const DEFAULT_SENSITIVITY: f32 = 1.0;
 
+// MODELS REGISTRY
+pub const MODELS_PATH: &str = "resources/models";
+pub const MODELS_MANIFEST: &str = "models.toml"; // inside MODELS_PATH
+pub const MODEL_DOWNLOAD_CONNECT_TIMEOUT_SECS: u64 = 15; // the body itself has no time limit, models are large
+
 // VOSK
 // pub const VOSK_MODEL_PATH: &str = const_concat!(PUBLIC_PATH, "/vosk/model_small");
 pub const VOSK_MODELS_PATH: &str = "resources/vosk";
-pub const VOSK_MODEL_PATH: &str = "resources/vosk/model_small";
+pub const VOSK_DEFAULT_SAMPLE_RATE: u32 = 16000;
 pub const VOSK_FETCH_PHRASE: &str = "джарвис";
 pub const VOSK_MIN_RATIO: f64 = 70.0;
+pub const VOSK_NEAR_MISS_RATIO: f64 = 50.0; // lower bound for diagnostics near-miss capture
 
 // 0.7 lenient, expect false positives
 // 0.8 balanced
@@ -153,10 +163,25 @@ pub const VOSK_WAKE_CONFIDENCE: f32 = 0.9;
 pub const VOSK_SPEECH_RECOGNIZER_MAX_ALTERNATIVES: u16 = 3;
 pub const VOSK_SPEECH_RECOGNIZER_WORDS: bool = false;
 pub const VOSK_SPEECH_PARTIAL_WORDS: bool = false;
+pub const DEFAULT_COMMAND_GRAMMAR_MODE: CommandGrammarMode = CommandGrammarMode::Off;
+pub const VOSK_ALTERNATIVES_SCORE_SCALE: f32 = 10.0; // softmax temperature for n-best lattice scores
+
+// n-best joint scoring, share of stt confidence vs command match score
+pub const STT_ALTERNATIVE_WEIGHT: f64 = 0.3;
 
 // IRE (intents recognition)
 pub const INTENT_CLASSIFIER_MIN_CONFIDENCE: f64 = 0.75;
 
+// embedding classifier model defaults (MiniLM-style sentence transformers)
+pub const DEFAULT_EMBEDDING_POOLING: EmbeddingPooling = EmbeddingPooling::Mean;
+pub const DEFAULT_EMBEDDING_QUANTIZATION: EmbeddingQuantization = EmbeddingQuantization::None;
+pub const DEFAULT_EMBEDDING_OUTPUT_KEY: &str = "last_hidden_state";
+
+// early intent: execute on a stable partial transcript, before end-of-utterance silence
+pub const DEFAULT_EARLY_INTENT: bool = false;
+pub const EARLY_INTENT_MIN_CONFIDENCE: f64 = 0.9;
+pub const EARLY_INTENT_STABLE_MS: u64 = 300;  // partial must stay unchanged this long
+
 // SLOTS EXTRACTION
 pub const DEFAULT_SLOT_EXTRACTION_ENGINE: SlotExtractionEngine = SlotExtractionEngine::None;
 
@@ -169,22 +194,81 @@ pub const DEFAULT_VAD: VadBackend = VadBackend::Energy;
 pub const DEFAULT_GAIN_NORMALIZER: bool = false;
 
 // VAD settings
-pub const VAD_ENERGY_THRESHOLD: f32 = 100.0;  // RMS threshold for energy-based VAD
+pub const VAD_ENERGY_THRESHOLD: f32 = 100.0;  // minimum RMS threshold for energy-based VAD
+pub const VAD_ENERGY_RATIO: f32 = 3.0;  // speech when RMS > noise floor * ratio
+pub const VAD_NOISE_FLOOR_ADAPT: f32 = 0.05;  // noise floor adaptation rate per silent frame
+pub const VAD_ATTACK_FRAMES: u32 = 2;  // frames above threshold before speech start (~64ms)
 pub const VAD_NNNOISELESS_THRESHOLD: f32 = 0.8;  // probability threshold for nnnoiseless
-pub const VAD_SILENCE_FRAMES: u32 = 15;  // frames of silence before speech end (~480ms)
+pub const VAD_SILERO_THRESHOLD: f32 = 0.5;  // speech probability threshold for silero
+pub const SILERO_VAD_MODEL_FILE: &str = "silero_vad.onnx";  // inside the "vad" model folder from models.toml
+pub const VAD_SILENCE_FRAMES: u32 = 15;  // frames of silence before speech end (~480ms), energy VAD hangover
+pub const VAD_WAKE_SILENCE_MS: u64 = 1500;  // silence before dropping back to wake-word wait
+pub const VAD_COMMAND_SILENCE_MS: u64 = 5000;  // silence before giving up on a command (user might pause to think)
+
+// echo cancellation (self-listening suppression)
+pub const DEFAULT_ECHO_CANCELLATION: EchoCancellation = EchoCancellation::Gate;
+pub const PLAYBACK_TAIL_MS: u64 = 300;  // room reverb + output latency after playback ends
+pub const AEC_FILTER_TAPS: usize = 1024;  // ~64ms echo path at 16kHz
+pub const AEC_STEP_SIZE: f32 = 0.1;  // NLMS adaptation rate
+
+// ducking (other apps' volume while listening)
+pub const DEFAULT_DUCKING_ENABLED: bool = false;
+pub const DUCKING_LEVEL: f32 = 0.3;
+
+// high-pass filter (rumble / DC removal)
+pub const HIGH_PASS_CUTOFF_HZ: f32 = 80.0;
+
+// audio processing pipeline runs on recorder frames
+pub const AUDIO_PROCESSING_SAMPLE_RATE: u32 = 16000;
+pub const DEFAULT_RECOGNIZE_PROCESSED_AUDIO: bool = false;  // recognizers hear the raw frames by default
 
 // gain normalizer settings
 pub const GAIN_TARGET_RMS: f32 = 3000.0;  // target RMS level
 pub const GAIN_MIN: f32 = 0.5;  // minimum gain multiplier
 pub const GAIN_MAX: f32 = 3.0;  // maximum gain multiplier
+pub const AGC_ATTACK_MS: f32 = 10.0;  // how fast gain drops on loud onsets
+pub const AGC_RELEASE_MS: f32 = 500.0;  // how fast gain recovers afterwards
+pub const AGC_GATE_THRESHOLD: f32 = 150.0;  // RMS below which audio is treated as silence (not amplified)
+pub const AGC_LIMITER_THRESHOLD: f32 = 0.8;  // soft limiter knee, fraction of full scale
 
 // nnnoiseless frame size (fixed by library)
 pub const NNNOISELESS_FRAME_SIZE: usize = 480;
 
+// IPC (websocket between the app, GUI and CLI)
+pub const IPC_ADDR: &str = "127.0.0.1";
+pub const IPC_PORT: u16 = 9712;
+
+// IPC audio level telemetry (events per second, 0 = off)
+pub const DEFAULT_TELEMETRY_HZ: u32 = 10;
+
 // LUA
 pub const DEFAULT_LUA_SANDBOX: &str = "standard";
 pub const DEFAULT_LUA_TIMEOUT: u64 = 10000; // ms
 
+// CLI / AHK COMMANDS
+pub const DEFAULT_PROCESS_TIMEOUT: u64 = 30000; // ms, used when a waited command has no timeout
+pub const PROCESS_OUTPUT_MAX_CHARS: usize = 500; // spoken / notified output is cut to this
+
+// HTTP (Lua http API and "http" commands)
+pub const HTTP_TIMEOUT_SECS: u64 = 30;
+pub const HTTP_USER_AGENT: &str = concat!("jarvis/", env!("CARGO_PKG_VERSION"));
+
+// D-BUS ("dbus" commands and Lua dbus API, Linux only)
+pub const DBUS_TIMEOUT_SECS: u64 = 5;
+
+// CONFIRMATION (commands with confirm = true)
+pub const CONFIRM_TIMEOUT_MS: u64 = 6000; // no answer = cancelled
+
+// HISTORY
+pub const HISTORY_PATH: &str = "history.jsonl"; // extended from APP_CONFIG_DIR
+pub const HISTORY_MAX_ENTRIES: usize = 200; // kept in memory
+
+// WAKE-WORD DIAGNOSTICS
+pub const DEFAULT_WAKE_DIAGNOSTICS: bool = false;
+pub const DIAGNOSTICS_PATH: &str = "diagnostics"; // extended from APP_CONFIG_DIR
+pub const DIAGNOSTICS_PREROLL_SECONDS: f32 = 3.0;
+pub const DIAGNOSTICS_MIN_INTERVAL_MS: u64 = 1000; // per sample kind
+
 // ETC
 pub const CMD_RATIO_THRESHOLD: f64 = 75f64;
 pub const CMS_WAIT_DELAY: std::time::Duration = std::time::Duration::from_secs(15);
@@ -242,6 +326,25 @@ pub fn get_phrases_to_remove(lang: &str) -> &'static [&'static str] {
     }
 }
 
+// yes / no mini-grammar for confirmations
+pub fn get_confirm_phrases(lang: &str) -> &'static [&'static str] {
+    match lang {
+        "ru" => &["да", "ага", "конечно", "подтверждаю", "выполняй", "давай"],
+        "ua" => &["так", "да", "звісно", "підтверджую", "виконуй", "давай"],
+        "en" => &["yes", "yeah", "yep", "sure", "confirm", "do it", "go ahead"],
+        _ => &["yes"],
+    }
+}
+
+pub fn get_cancel_phrases(lang: &str) -> &'static [&'static str] {
+    match lang {
+        "ru" => &["нет", "не надо", "не нужно", "отмена", "отмени", "стоп"],
+        "ua" => &["ні", "не треба", "не потрібно", "відміна", "скасуй", "стоп"],
+        "en" => &["no", "nope", "don't", "do not", "cancel", "stop"],
+        _ => &["no"],
+    }
+}
+
 pub fn get_wake_grammar(lang: &str) -> &'static [&'static str] {
     match lang {
         "ru" => &[
@@ -14,6 +14,19 @@ pub enum IntentRecognitionEngine {
     EmbeddingClassifier,
 }
 
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq)]
+pub enum EmbeddingPooling {
+    Mean,
+    Cls,
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq)]
+pub enum EmbeddingQuantization {
+    None,
+    Static,
+    Dynamic,
+}
+
 #[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq)]
 pub enum NoiseSuppressionBackend {
     None,
@@ -25,6 +38,26 @@ pub enum VadBackend {
     None,
     Energy,
     Nnnoiseless,
+    Silero,
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq)]
+pub enum EchoCancellation {
+    None,
+    Gate,
+    Nlms,
+}
+
+// one step of the audio processing pipeline, in order of appearance
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+#[serde(tag = "stage", rename_all = "snake_case")]
+pub enum AudioStageConfig {
+    HighPass { cutoff_hz: f32 },
+    Gain,
+    Denoise { backend: NoiseSuppressionBackend },
+    EchoCancellation { mode: EchoCancellation },
+    Vad { backend: VadBackend },
+    Custom { name: String },
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -32,6 +65,14 @@ pub enum SpeechToTextEngine {
     Vosk,
 }
 
+// recognizer restricted to the vocabulary of loaded commands
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq)]
+pub enum CommandGrammarMode {
+    Off,
+    Alongside, // grammar hypothesis is added to the free-form alternatives
+    Only,      // grammar recognizer replaces the free-form one
+}
+
 #[derive(PartialEq, Debug)]
 pub enum RecorderType {
     Cpal,
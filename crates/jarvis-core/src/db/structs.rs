@@ -1,11 +1,16 @@
 use crate::config;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 use crate::config::structs::SpeechToTextEngine;
 use crate::config::structs::WakeWordEngine;
 use crate::config::structs::IntentRecognitionEngine;
 use crate::config::structs::NoiseSuppressionBackend;
 use crate::config::structs::VadBackend;
+use crate::config::structs::EchoCancellation;
+use crate::config::structs::CommandGrammarMode;
+use crate::config::structs::{EmbeddingPooling, EmbeddingQuantization};
+use crate::config::structs::AudioStageConfig;
 use crate::config::structs::SlotExtractionEngine;
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -16,22 +21,87 @@ pub struct Settings {
     pub wake_word_engine: WakeWordEngine,
     pub intent_recognition_engine: IntentRecognitionEngine,
 
+    #[serde(default)]
+    pub embedding: EmbeddingSettings,
+
     pub slot_extraction_engine: SlotExtractionEngine,
     pub gliner_model: String,
 
     pub speech_to_text_engine: SpeechToTextEngine,
     pub vosk_model: String,
 
+    // vosk recognizer biased towards the loaded command phrases
+    #[serde(default = "default_command_grammar")]
+    pub command_grammar: CommandGrammarMode,
+
+    // run intent on stable partials and execute confident commands early
+    #[serde(default)]
+    pub early_intent: bool,
+
     // audio processing
     pub noise_suppression: NoiseSuppressionBackend,
     pub vad: VadBackend,
     pub gain_normalizer: bool,
 
+    #[serde(default)]
+    pub agc: AgcSettings,
+
+    // per-microphone AGC overrides, keyed by device name
+    #[serde(default)]
+    pub agc_profiles: HashMap<String, AgcSettings>,
+
+    #[serde(default)]
+    pub vad_settings: VadSettings,
+
+    // self-listening suppression while assistant audio plays
+    #[serde(default = "default_echo_cancellation")]
+    pub echo_cancellation: EchoCancellation,
+
+    // explicit stage order, empty = built from the settings above
+    #[serde(default)]
+    pub audio_pipeline: Vec<AudioStageConfig>,
+
+    // wake-word / STT get the pipeline output instead of the raw microphone frames
+    #[serde(default)]
+    pub recognize_processed_audio: bool,
+
+    // save wake-word detections / near-misses for tuning
+    #[serde(default)]
+    pub wake_diagnostics: bool,
+
+    // audio level / VAD telemetry rate for the GUI, 0 = off
+    #[serde(default = "default_telemetry_hz")]
+    pub telemetry_hz: u32,
+
+    // lower other apps' volume while listening for a command
+    #[serde(default)]
+    pub ducking: DuckingSettings,
+
+    // base url to download registry models from, empty = manifest default
+    #[serde(default)]
+    pub models_mirror: String,
+
+    // ids of switched off commands, kept here so packs stay untouched
+    #[serde(default)]
+    pub disabled_commands: Vec<String>,
+
     pub language: String,
 
     pub api_keys: ApiKeys,
 }
 
+fn default_echo_cancellation() -> EchoCancellation {
+    config::DEFAULT_ECHO_CANCELLATION
+}
+
+fn default_command_grammar() -> CommandGrammarMode {
+    config::DEFAULT_COMMAND_GRAMMAR_MODE
+}
+
+fn default_telemetry_hz() -> u32 {
+    config::DEFAULT_TELEMETRY_HZ
+}
+
 impl Default for Settings {
     fn default() -> Settings {
         Settings {
@@ -40,15 +110,30 @@ impl Default for Settings {
 
             wake_word_engine: config::DEFAULT_WAKE_WORD_ENGINE,
             intent_recognition_engine: config::DEFAULT_INTENT_RECOGNITION_ENGINE,
+            embedding: EmbeddingSettings::default(),
             slot_extraction_engine: SlotExtractionEngine::None,
             gliner_model: String::new(),
             speech_to_text_engine: config::DEFAULT_SPEECH_TO_TEXT_ENGINE,
             vosk_model: String::from(""), // auto detect first available
+            command_grammar: config::DEFAULT_COMMAND_GRAMMAR_MODE,
+            early_intent: config::DEFAULT_EARLY_INTENT,
 
             // audio processing defaults
             noise_suppression: config::DEFAULT_NOISE_SUPPRESSION,
             vad: config::DEFAULT_VAD,
             gain_normalizer: config::DEFAULT_GAIN_NORMALIZER,
+            agc: AgcSettings::default(),
+            agc_profiles: HashMap::new(),
+            vad_settings: VadSettings::default(),
+            echo_cancellation: config::DEFAULT_ECHO_CANCELLATION,
+            audio_pipeline: Vec::new(),
+            recognize_processed_audio: config::DEFAULT_RECOGNIZE_PROCESSED_AUDIO,
+
+            wake_diagnostics: config::DEFAULT_WAKE_DIAGNOSTICS,
+            telemetry_hz: config::DEFAULT_TELEMETRY_HZ,
+            ducking: DuckingSettings::default(),
+            models_mirror: String::new(),
+            disabled_commands: Vec::new(),
 
             language: String::from("ru"),
 
@@ -60,6 +145,125 @@ impl Default for Settings {
     }
 }
 
+impl Settings {
+    // AGC settings for the given microphone (profile if any, global otherwise)
+    pub fn agc_for(&self, device_name: &str) -> AgcSettings {
+        self.agc_profiles
+            .get(device_name)
+            .cloned()
+            .unwrap_or_else(|| self.agc.clone())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct AgcSettings {
+    pub target_rms: f32,
+    pub min_gain: f32,
+    pub max_gain: f32,
+    pub attack_ms: f32,
+    pub release_ms: f32,
+    pub gate_threshold: f32,
+    pub limiter_threshold: f32,
+}
+
+impl Default for AgcSettings {
+    fn default() -> AgcSettings {
+        AgcSettings {
+            target_rms: config::GAIN_TARGET_RMS,
+            min_gain: config::GAIN_MIN,
+            max_gain: config::GAIN_MAX,
+            attack_ms: config::AGC_ATTACK_MS,
+            release_ms: config::AGC_RELEASE_MS,
+            gate_threshold: config::AGC_GATE_THRESHOLD,
+            limiter_threshold: config::AGC_LIMITER_THRESHOLD,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct VadSettings {
+    // energy VAD
+    pub energy_min_threshold: f32,
+    pub energy_ratio: f32,
+    pub noise_floor_adapt: f32,
+    pub attack_frames: u32,
+    pub hangover_frames: u32,
+
+    // app timeouts (ms)
+    pub wake_silence_ms: u64,
+    pub command_silence_ms: u64,
+    pub command_timeout_ms: u64,
+}
+
+impl Default for VadSettings {
+    fn default() -> VadSettings {
+        VadSettings {
+            energy_min_threshold: config::VAD_ENERGY_THRESHOLD,
+            energy_ratio: config::VAD_ENERGY_RATIO,
+            noise_floor_adapt: config::VAD_NOISE_FLOOR_ADAPT,
+            attack_frames: config::VAD_ATTACK_FRAMES,
+            hangover_frames: config::VAD_SILENCE_FRAMES,
+
+            wake_silence_ms: config::VAD_WAKE_SILENCE_MS,
+            command_silence_ms: config::VAD_COMMAND_SILENCE_MS,
+            command_timeout_ms: config::CMS_WAIT_DELAY.as_millis() as u64,
+        }
+    }
+}
+
+impl VadSettings {
+    // convert a timeout to a number of recorder frames
+    pub fn ms_to_frames(ms: u64, frame_length: usize, sample_rate: usize) -> u32 {
+        ((ms as f32 / 1000.0 * sample_rate as f32) / frame_length as f32) as u32
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct EmbeddingSettings {
+    // registry model id or model folder (relative to the app dir), empty = pick by language
+    pub model: String,
+    pub pooling: EmbeddingPooling,
+    pub quantization: EmbeddingQuantization,
+    // onnx output to pool over
+    pub output_key: String,
+    // prepended to user queries / command phrases, e.g. "query: " for E5 models
+    pub query_prefix: String,
+    pub passage_prefix: String,
+}
+
+impl Default for EmbeddingSettings {
+    fn default() -> EmbeddingSettings {
+        EmbeddingSettings {
+            model: String::new(),
+            pooling: config::DEFAULT_EMBEDDING_POOLING,
+            quantization: config::DEFAULT_EMBEDDING_QUANTIZATION,
+            output_key: config::DEFAULT_EMBEDDING_OUTPUT_KEY.to_string(),
+            query_prefix: String::new(),
+            passage_prefix: String::new(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct DuckingSettings {
+    pub enabled: bool,
+    // fraction of the original volume other apps are lowered to (0.0 - 1.0)
+    pub level: f32,
+}
+
+impl Default for DuckingSettings {
+    fn default() -> DuckingSettings {
+        DuckingSettings {
+            enabled: config::DEFAULT_DUCKING_ENABLED,
+            level: config::DUCKING_LEVEL,
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct ApiKeys {
     pub picovoice: String,
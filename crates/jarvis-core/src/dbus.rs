@@ -0,0 +1,148 @@
+// D-Bus access through zbus (Linux only): method calls, property reads, MPRIS players
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests;
+
+pub mod mpris;
+#[cfg(target_os = "linux")]
+pub mod value;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[cfg(target_os = "linux")]
+use once_cell::sync::OnceCell;
+#[cfg(target_os = "linux")]
+use zbus::blocking::{connection, Connection};
+
+#[cfg(target_os = "linux")]
+use crate::config;
+
+#[cfg(target_os = "linux")]
+static SESSION: OnceCell<Connection> = OnceCell::new();
+#[cfg(target_os = "linux")]
+static SYSTEM: OnceCell<Connection> = OnceCell::new();
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Bus {
+    Session,
+    System,
+    Address(String), // "unix:path=..."
+}
+
+impl Bus {
+    // "session" / "system" / bus address, empty = session
+    pub fn parse(s: &str) -> Bus {
+        match s.trim() {
+            "" | "session" => Bus::Session,
+            "system" => Bus::System,
+            address => Bus::Address(address.to_string()),
+        }
+    }
+
+    // session and system connections are shared, addresses get a new one each time
+    #[cfg(target_os = "linux")]
+    fn connect(&self) -> Result<Connection, String> {
+        let open = |builder: zbus::Result<connection::Builder>| {
+            builder
+                .map(|b| b.method_timeout(std::time::Duration::from_secs(config::DBUS_TIMEOUT_SECS)))
+                .and_then(|b| b.build())
+                .map_err(|e| format!("Failed to connect to D-Bus ({:?}): {}", self, e))
+        };
+
+        match self {
+            Bus::Session => SESSION.get_or_try_init(|| open(connection::Builder::session())).cloned(),
+            Bus::System => SYSTEM.get_or_try_init(|| open(connection::Builder::system())).cloned(),
+            Bus::Address(address) => open(connection::Builder::address(address.as_str())),
+        }
+    }
+}
+
+// [commands.dbus] of a "dbus" command
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct DbusCall {
+    #[serde(default)]
+    pub bus: String, // "session" (default), "system" or an address
+    pub dest: String,
+    pub path: String,
+    // "org.example.Interface.Method"
+    #[serde(default)]
+    pub method: String,
+    // "org.example.Interface.Property", read instead of calling a method
+    #[serde(default)]
+    pub property: String,
+    // D-Bus signature of args, e.g. "su", empty = guessed from the values
+    #[serde(default)]
+    pub signature: String,
+    // strings may contain {placeholders}, "5" fits a "u" signature too
+    #[serde(default)]
+    pub args: Vec<Value>,
+}
+
+// Call "interface.Method", returns the reply values as a json array
+#[cfg(target_os = "linux")]
+pub fn call(bus: &Bus, dest: &str, path: &str, method: &str, signature: &str, args: &[Value]) -> Result<Value, String> {
+    let (interface, member) = method.rsplit_once('.')
+        .ok_or_else(|| format!("Method '{}' must be 'interface.Name'", method))?;
+    let args = value::to_args(signature, args)?;
+    let connection = bus.connect()?;
+
+    // an empty structure is not a valid body
+    let reply = if args.is_empty() {
+        connection.call_method(Some(dest), path, Some(interface), member, &())
+    } else {
+        connection.call_method(Some(dest), path, Some(interface), member, &value::to_structure(args)?)
+    }
+    .map_err(|e| format!("D-Bus call {} failed: {}", method, e))?;
+
+    let body = reply.body();
+    if *body.signature() == zbus::zvariant::Signature::Unit {
+        return Ok(Value::Array(Vec::new()));
+    }
+
+    let values: zbus::zvariant::Structure = body.deserialize()
+        .map_err(|e| format!("Unexpected {} reply: {}", method, e))?;
+    Ok(value::to_json(&zbus::zvariant::Value::Structure(values)))
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn call(_bus: &Bus, _dest: &str, _path: &str, method: &str, _signature: &str, _args: &[Value]) -> Result<Value, String> {
+    Err(format!("D-Bus call {} failed: D-Bus is only available on Linux", method))
+}
+
+// Read "interface.Property" through org.freedesktop.DBus.Properties
+pub fn get_property(bus: &Bus, dest: &str, path: &str, property: &str) -> Result<Value, String> {
+    let (interface, name) = property.rsplit_once('.')
+        .ok_or_else(|| format!("Property '{}' must be 'interface.Name'", property))?;
+
+    let reply = call(bus, dest, path, "org.freedesktop.DBus.Properties.Get", "ss", &[interface.into(), name.into()])?;
+    Ok(first(reply))
+}
+
+// Names currently on the bus
+pub fn list_names(bus: &Bus) -> Result<Vec<String>, String> {
+    let reply = call(bus, "org.freedesktop.DBus", "/org/freedesktop/DBus", "org.freedesktop.DBus.ListNames", "", &[])?;
+
+    match first(reply) {
+        Value::Array(names) => Ok(names.into_iter().filter_map(|n| n.as_str().map(String::from)).collect()),
+        other => Err(format!("Unexpected ListNames reply: {}", other)),
+    }
+}
+
+// Single-value replies come as a 1-tuple
+pub fn first(reply: Value) -> Value {
+    match reply {
+        Value::Array(mut items) if items.len() == 1 => items.remove(0),
+        other => other,
+    }
+}
+
+// Reply as text for speaking / notifications
+pub fn to_text(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        Value::Array(items) if items.len() == 1 => to_text(&items[0]),
+        other => other.to_string(),
+    }
+}
@@ -0,0 +1,112 @@
+// MPRIS media players (org.mpris.MediaPlayer2.*)
+
+use serde_json::Value;
+
+use super::{call, get_property, list_names, Bus};
+
+const PREFIX: &str = "org.mpris.MediaPlayer2.";
+const PATH: &str = "/org/mpris/MediaPlayer2";
+const PLAYER: &str = "org.mpris.MediaPlayer2.Player";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Action {
+    PlayPause,
+    Play,
+    Pause,
+    Stop,
+    Next,
+    Previous,
+}
+
+impl Action {
+    pub fn parse(name: &str) -> Option<Action> {
+        match name.trim().to_lowercase().as_str() {
+            "play_pause" | "playpause" | "toggle" => Some(Action::PlayPause),
+            "play" => Some(Action::Play),
+            "pause" => Some(Action::Pause),
+            "stop" => Some(Action::Stop),
+            "next" => Some(Action::Next),
+            "previous" | "prev" => Some(Action::Previous),
+            _ => None,
+        }
+    }
+
+    fn method(&self) -> &'static str {
+        match self {
+            Action::PlayPause => "PlayPause",
+            Action::Play => "Play",
+            Action::Pause => "Pause",
+            Action::Stop => "Stop",
+            Action::Next => "Next",
+            Action::Previous => "Previous",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NowPlaying {
+    pub player: String, // bus name without the mpris prefix, e.g. "spotify"
+    pub title: String,
+    pub artist: String,
+}
+
+impl NowPlaying {
+    // "Artist - Title", or just the title
+    pub fn describe(&self) -> String {
+        if self.artist.is_empty() {
+            self.title.clone()
+        } else {
+            format!("{} - {}", self.artist, self.title)
+        }
+    }
+}
+
+// Bus names of running players
+pub fn players(bus: &Bus) -> Result<Vec<String>, String> {
+    let mut players: Vec<String> = list_names(bus)?
+        .into_iter()
+        .filter(|n| n.starts_with(PREFIX))
+        .collect();
+    players.sort();
+    Ok(players)
+}
+
+// The playing player, otherwise the first one
+pub fn active_player(bus: &Bus) -> Result<String, String> {
+    let players = players(bus)?;
+
+    let playing = players.iter().find(|p| {
+        get_property(bus, p, PATH, &format!("{}.PlaybackStatus", PLAYER))
+            .is_ok_and(|s| s == "Playing")
+    });
+
+    playing.or(players.first())
+        .cloned()
+        .ok_or_else(|| "No media player running".into())
+}
+
+pub fn control(bus: &Bus, action: Action) -> Result<(), String> {
+    let player = active_player(bus)?;
+    call(bus, &player, PATH, &format!("{}.{}", PLAYER, action.method()), "", &[]).map(|_| ())
+}
+
+pub fn now_playing(bus: &Bus) -> Result<NowPlaying, String> {
+    let player = active_player(bus)?;
+    let metadata = get_property(bus, &player, PATH, &format!("{}.Metadata", PLAYER))?;
+    Ok(from_metadata(&player, &metadata))
+}
+
+// xesam:artist is a list, players fill it differently
+pub(crate) fn from_metadata(player: &str, metadata: &Value) -> NowPlaying {
+    let artist = match metadata.get("xesam:artist") {
+        Some(Value::Array(names)) => names.iter().filter_map(|n| n.as_str()).collect::<Vec<_>>().join(", "),
+        Some(Value::String(name)) => name.clone(),
+        _ => String::new(),
+    };
+
+    NowPlaying {
+        player: player.trim_start_matches(PREFIX).to_string(),
+        title: metadata.get("xesam:title").and_then(|t| t.as_str()).unwrap_or_default().to_string(),
+        artist,
+    }
+}
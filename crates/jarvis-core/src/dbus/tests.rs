@@ -0,0 +1,94 @@
+use std::io::{BufRead, BufReader};
+use std::process::{Child, Command, Stdio};
+
+use serde_json::json;
+use zbus::zvariant::{self, Dict, Signature};
+
+use super::*;
+
+#[test]
+fn converts_replies() {
+    assert_eq!(value::to_json(&zvariant::Value::from(vec!["org.freedesktop.DBus", ":1.0"])), json!(["org.freedesktop.DBus", ":1.0"]));
+    assert_eq!(value::to_json(&zvariant::Value::Value(Box::new("Playing".into()))), json!("Playing"));
+    assert_eq!(value::to_json(&zvariant::Value::Structure(
+        value::to_structure(vec![1u32.into(), (-3i64).into(), 2.5.into()]).unwrap()
+    )), json!([1, -3, 2.5]));
+
+    let mut metadata = Dict::new(&Signature::Str, &Signature::Variant);
+    metadata.append("xesam:title".into(), zvariant::Value::Value(Box::new("Song 'A'".into()))).unwrap();
+    metadata.append("xesam:artist".into(), zvariant::Value::Value(Box::new(vec!["X", "Y"].into()))).unwrap();
+    metadata.append("mpris:length".into(), zvariant::Value::Value(Box::new(1000i64.into()))).unwrap();
+
+    let np = mpris::from_metadata("org.mpris.MediaPlayer2.spotify", &value::to_json(&zvariant::Value::Dict(metadata)));
+    assert_eq!(np.title, "Song 'A'");
+    assert_eq!(np.describe(), "X, Y - Song 'A'");
+    assert_eq!(np.player, "spotify");
+}
+
+#[test]
+fn converts_call_arguments() {
+    // typed by the signature, strings from placeholders fit numbers and booleans
+    let args = value::to_args("bus", &[json!("true"), json!("5"), json!("it's")]).unwrap();
+    assert_eq!(args, [zvariant::Value::Bool(true), zvariant::Value::U32(5), zvariant::Value::from("it's")]);
+
+    // guessed without one
+    let args = value::to_args("", &[json!(1), json!(["a"]), json!({"k": 2})]).unwrap();
+    assert_eq!(value::to_structure(args).unwrap().signature().to_string(), "(iasa{sv})");
+
+    assert_eq!(value::split("sa{sv}(ii)av").unwrap(), ["s", "a{sv}", "(ii)", "av"]);
+    assert!(value::split("a{sv").is_err());
+
+    assert!(value::to_args("u", &[json!(-1)]).is_err());
+    assert!(value::to_args("s", &[json!("a"), json!("b")]).is_err());
+    assert!(value::to_args("", &[json!(null)]).is_err());
+
+    assert_eq!(Bus::parse(""), Bus::Session);
+    assert_eq!(Bus::parse("unix:path=/tmp/x"), Bus::Address("unix:path=/tmp/x".into()));
+}
+
+// private bus, killed on drop
+struct Daemon(Child, String);
+
+impl Drop for Daemon {
+    fn drop(&mut self) {
+        let _ = self.0.kill();
+        let _ = self.0.wait();
+    }
+}
+
+fn private_bus() -> Daemon {
+    let mut child = Command::new("dbus-daemon")
+        .args(["--session", "--nofork", "--print-address=1"])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .expect("dbus-daemon is needed for this test");
+
+    let mut address = String::new();
+    BufReader::new(child.stdout.take().unwrap()).read_line(&mut address).unwrap();
+    let address = address.trim().to_string();
+    Daemon(child, address)
+}
+
+#[test]
+#[ignore = "needs dbus-daemon, run with --ignored"]
+fn talks_to_a_private_bus() {
+    let daemon = private_bus();
+    let bus = Bus::parse(&daemon.1);
+
+    assert!(list_names(&bus).unwrap().iter().any(|n| n == "org.freedesktop.DBus"));
+
+    let id = call(&bus, "org.freedesktop.DBus", "/org/freedesktop/DBus", "org.freedesktop.DBus.GetId", "", &[]).unwrap();
+    assert!(matches!(first(id), Value::String(s) if !s.is_empty()));
+
+    let has_owner = call(&bus, "org.freedesktop.DBus", "/org/freedesktop/DBus", "org.freedesktop.DBus.NameHasOwner", "", &[json!("org.freedesktop.DBus")]).unwrap();
+    assert_eq!(first(has_owner), json!(true));
+
+    let features = get_property(&bus, "org.freedesktop.DBus", "/org/freedesktop/DBus", "org.freedesktop.DBus.Features").unwrap();
+    assert!(features.is_array());
+
+    assert!(call(&bus, "org.freedesktop.DBus", "/org/freedesktop/DBus", "org.freedesktop.DBus.NoSuchMethod", "", &[]).is_err());
+
+    assert!(mpris::players(&bus).unwrap().is_empty());
+    assert!(mpris::active_player(&bus).is_err());
+}
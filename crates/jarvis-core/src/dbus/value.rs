@@ -0,0 +1,195 @@
+// json <-> D-Bus values, call arguments are typed by a signature or guessed from the json
+
+use serde_json::{Map, Number, Value as Json};
+use zbus::zvariant::{Array, Dict, ObjectPath, Signature, Structure, StructureBuilder, Value};
+
+// Arguments of a method call, typed by the signature or guessed when it's empty
+pub fn to_args(signature: &str, args: &[Json]) -> Result<Vec<Value<'static>>, String> {
+    let types = if signature.is_empty() {
+        args.iter().map(guess).collect::<Result<Vec<_>, _>>()?
+    } else {
+        split(signature)?.into_iter().map(String::from).collect()
+    };
+
+    if types.len() != args.len() {
+        return Err(format!("Signature '{}' has {} types for {} arguments", signature, types.len(), args.len()));
+    }
+
+    types.iter().zip(args).map(|(ty, arg)| to_value(arg, ty)).collect()
+}
+
+// zbus sends the fields of a structure body as separate arguments
+pub fn to_structure(fields: Vec<Value<'static>>) -> Result<Structure<'static>, String> {
+    fields.into_iter()
+        .fold(StructureBuilder::new(), |builder, field| builder.append_field(field))
+        .build()
+        .map_err(|e| e.to_string())
+}
+
+// D-Bus value of the single complete type `ty`
+pub fn to_value(json: &Json, ty: &str) -> Result<Value<'static>, String> {
+    let mismatch = || format!("Argument {} does not fit type '{}'", json, ty);
+
+    let value = match ty {
+        "y" => Value::U8(int(json).ok_or_else(mismatch)?),
+        "n" => Value::I16(int(json).ok_or_else(mismatch)?),
+        "q" => Value::U16(int(json).ok_or_else(mismatch)?),
+        "i" => Value::I32(int(json).ok_or_else(mismatch)?),
+        "u" => Value::U32(int(json).ok_or_else(mismatch)?),
+        "x" => Value::I64(int(json).ok_or_else(mismatch)?),
+        "t" => Value::U64(int(json).ok_or_else(mismatch)?),
+        "d" => Value::F64(match json {
+            Json::Number(n) => n.as_f64(),
+            Json::String(s) => s.trim().parse().ok(),
+            _ => None,
+        }.ok_or_else(mismatch)?),
+        "b" => Value::Bool(match json {
+            Json::Bool(b) => Some(*b),
+            Json::String(s) => s.trim().parse().ok(),
+            _ => None,
+        }.ok_or_else(mismatch)?),
+        "s" => Value::from(text(json).ok_or_else(mismatch)?),
+        "o" => Value::ObjectPath(ObjectPath::try_from(text(json).ok_or_else(mismatch)?).map_err(|e| e.to_string())?),
+        "g" => Value::Signature(Signature::try_from(text(json).ok_or_else(mismatch)?.as_str()).map_err(|e| e.to_string())?),
+        "v" => Value::Value(Box::new(to_value(json, &guess(json)?)?)),
+        _ => return to_container(json, ty).ok_or_else(mismatch)?,
+    };
+    Ok(value)
+}
+
+// arrays, dicts and structures
+fn to_container(json: &Json, ty: &str) -> Option<Result<Value<'static>, String>> {
+    let signature = |s: &str| Signature::try_from(s).map_err(|e| e.to_string());
+
+    if let Some(entry) = ty.strip_prefix("a{").and_then(|t| t.strip_suffix('}')) {
+        let Json::Object(map) = json else { return None };
+        let (key, value) = match split(entry) {
+            Ok(types) if types.len() == 2 => (types[0], types[1]),
+            _ => return Some(Err(format!("Bad dict type '{}'", ty))),
+        };
+
+        return Some((|| {
+            let mut dict = Dict::new(&signature(key)?, &signature(value)?);
+            for (k, v) in map {
+                dict.append(to_value(&Json::String(k.clone()), key)?, to_value(v, value)?)
+                    .map_err(|e| e.to_string())?;
+            }
+            Ok(Value::Dict(dict))
+        })());
+    }
+
+    if let Some(element) = ty.strip_prefix('a') {
+        let Json::Array(items) = json else { return None };
+
+        return Some((|| {
+            let mut array = Array::new(&signature(element)?);
+            for item in items {
+                array.append(to_value(item, element)?).map_err(|e| e.to_string())?;
+            }
+            Ok(Value::Array(array))
+        })());
+    }
+
+    if let Some(fields) = ty.strip_prefix('(').and_then(|t| t.strip_suffix(')')) {
+        let Json::Array(items) = json else { return None };
+        return Some(to_args(fields, items).and_then(to_structure).map(Value::Structure));
+    }
+
+    None
+}
+
+// Type for an argument given without a signature
+fn guess(json: &Json) -> Result<String, String> {
+    let ty = match json {
+        Json::Bool(_) => "b",
+        Json::Number(n) if n.as_i64().is_some_and(|n| i32::try_from(n).is_ok()) => "i",
+        Json::Number(n) if n.is_i64() => "x",
+        Json::Number(n) if n.is_u64() => "t",
+        Json::Number(_) => "d",
+        Json::String(_) => "s",
+        Json::Array(items) if !items.is_empty() && items.iter().all(Json::is_string) => "as",
+        Json::Array(_) => "av",
+        Json::Object(_) => "a{sv}",
+        Json::Null => return Err("null can't be sent over D-Bus".into()),
+    };
+    Ok(ty.to_string())
+}
+
+// "sa{sv}(ii)" -> ["s", "a{sv}", "(ii)"]
+pub fn split(signature: &str) -> Result<Vec<&str>, String> {
+    let mut types = Vec::new();
+    let mut rest = signature;
+
+    while !rest.is_empty() {
+        let len = complete_type_len(rest.as_bytes())
+            .ok_or_else(|| format!("Bad D-Bus signature '{}'", signature))?;
+        types.push(&rest[..len]);
+        rest = &rest[len..];
+    }
+    Ok(types)
+}
+
+fn complete_type_len(sig: &[u8]) -> Option<usize> {
+    match sig.first()? {
+        b'y' | b'b' | b'n' | b'q' | b'i' | b'u' | b'x' | b't' | b'd' | b's' | b'o' | b'g' | b'v' | b'h' => Some(1),
+        b'a' => complete_type_len(&sig[1..]).map(|len| len + 1),
+        open @ (b'(' | b'{') => {
+            let close = if *open == b'(' { b')' } else { b'}' };
+            let mut pos = 1;
+            while *sig.get(pos)? != close {
+                pos += complete_type_len(&sig[pos..])?;
+            }
+            Some(pos + 1)
+        }
+        _ => None,
+    }
+}
+
+fn int<T: TryFrom<i128>>(json: &Json) -> Option<T> {
+    let n: i128 = match json {
+        Json::Number(n) => n.as_i64().map(i128::from).or(n.as_u64().map(i128::from))?,
+        Json::String(s) => s.trim().parse().ok()?,
+        _ => return None,
+    };
+    T::try_from(n).ok()
+}
+
+// strings as is, other scalars as their json text (e.g. a number slot into an "s" argument)
+fn text(json: &Json) -> Option<String> {
+    match json {
+        Json::String(s) => Some(s.clone()),
+        Json::Number(_) | Json::Bool(_) => Some(json.to_string()),
+        _ => None,
+    }
+}
+
+// Reply values for the lua api and to_text, variants are unwrapped
+pub fn to_json(value: &Value) -> Json {
+    match value {
+        Value::U8(n) => Json::from(*n),
+        Value::Bool(b) => Json::Bool(*b),
+        Value::I16(n) => Json::from(*n),
+        Value::U16(n) => Json::from(*n),
+        Value::I32(n) => Json::from(*n),
+        Value::U32(n) => Json::from(*n),
+        Value::I64(n) => Json::from(*n),
+        Value::U64(n) => Json::from(*n),
+        Value::F64(n) => Number::from_f64(*n).map(Json::Number).unwrap_or(Json::Null),
+        Value::Str(s) => Json::String(s.to_string()),
+        Value::Signature(s) => Json::String(s.to_string()),
+        Value::ObjectPath(p) => Json::String(p.to_string()),
+        Value::Value(inner) => to_json(inner),
+        Value::Array(array) => Json::Array(array.inner().iter().map(to_json).collect()),
+        Value::Structure(fields) => Json::Array(fields.fields().iter().map(to_json).collect()),
+        Value::Dict(dict) => Json::Object(dict.iter()
+            .map(|(k, v)| {
+                let key = match to_json(k) {
+                    Json::String(s) => s,
+                    other => other.to_string(),
+                };
+                (key, to_json(v))
+            })
+            .collect::<Map<_, _>>()),
+        _ => Json::Null, // file descriptors
+    }
+}
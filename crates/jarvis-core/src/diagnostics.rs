@@ -0,0 +1,224 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Instant;
+
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+
+use crate::audio_buffer::AudioRingBuffer;
+use crate::{config, APP_CONFIG_DIR, DB};
+
+#[cfg(test)]
+mod tests;
+
+mod structs;
+pub use structs::*;
+
+const SAMPLE_RATE: u32 = 16000;
+const FRAME_LENGTH: usize = 512;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+// pre-roll kept only for diagnostics, independent from the VAD buffers in the app loop
+static BUFFER: Lazy<Mutex<AudioRingBuffer>> = Lazy::new(|| {
+    Mutex::new(AudioRingBuffer::new(config::DIAGNOSTICS_PREROLL_SECONDS, FRAME_LENGTH, SAMPLE_RATE as usize))
+});
+
+// last capture time per kind (detections repeat for several frames)
+static LAST_CAPTURE: Lazy<Mutex<Vec<(SampleKind, Instant)>>> = Lazy::new(|| Mutex::new(Vec::new()));
+
+pub fn init() {
+    let enabled = DB.get()
+        .map(|db| db.read().wake_diagnostics)
+        .unwrap_or(config::DEFAULT_WAKE_DIAGNOSTICS);
+
+    ENABLED.store(enabled, Ordering::SeqCst);
+
+    if enabled {
+        info!("Wake-word diagnostics enabled, samples dir: {}", samples_dir().display());
+    }
+}
+
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::SeqCst);
+    if !enabled {
+        BUFFER.lock().clear();
+    }
+}
+
+pub fn samples_dir() -> PathBuf {
+    APP_CONFIG_DIR.get()
+        .cloned()
+        .unwrap_or_else(|| std::env::current_dir().unwrap())
+        .join(config::DIAGNOSTICS_PATH)
+}
+
+// Feed every raw microphone frame here
+pub fn push_frame(frame: &[i16]) {
+    if !is_enabled() {
+        return;
+    }
+
+    BUFFER.lock().push(frame);
+}
+
+// Save the buffered audio + JSON sidecar (non-blocking)
+pub fn capture(kind: SampleKind, engine: &str, score: f64, threshold: f64, near_miss_bound: f64, text: Option<String>) {
+    if !is_enabled() {
+        return;
+    }
+
+    // debounce
+    {
+        let mut last = LAST_CAPTURE.lock();
+        let now = Instant::now();
+        let min_interval = std::time::Duration::from_millis(config::DIAGNOSTICS_MIN_INTERVAL_MS);
+
+        if let Some(entry) = last.iter_mut().find(|(k, _)| *k == kind) {
+            if now.duration_since(entry.1) < min_interval {
+                return;
+            }
+            entry.1 = now;
+        } else {
+            last.push((kind, now));
+        }
+    }
+
+    let samples = BUFFER.lock().to_vec();
+    if samples.is_empty() {
+        return;
+    }
+
+    let now = chrono::Local::now();
+    let id = format!("{}-{}", now.format("%Y%m%d-%H%M%S-%3f"), kind.to_string().to_lowercase());
+
+    let meta = SampleMeta {
+        id,
+        kind,
+        timestamp: now.timestamp(),
+        engine: engine.to_string(),
+        score,
+        threshold,
+        near_miss_bound,
+        text,
+        duration_ms: (samples.len() as u64 * 1000) / SAMPLE_RATE as u64,
+        settings: settings_snapshot(),
+        label: None,
+    };
+
+    std::thread::spawn(move || {
+        if let Err(e) = write_sample(&samples_dir(), &meta, &samples) {
+            warn!("Failed to save diagnostic sample {}: {}", meta.id, e);
+        } else {
+            debug!("Diagnostic sample saved: {} ({}, score {:.3})", meta.id, meta.kind, meta.score);
+        }
+    });
+}
+
+fn write_sample(dir: &Path, meta: &SampleMeta, samples: &[i16]) -> Result<(), String> {
+    fs::create_dir_all(dir)
+        .map_err(|e| format!("Cannot create {}: {}", dir.display(), e))?;
+
+    let spec = hound::WavSpec {
+        channels: 1,
+        sample_rate: SAMPLE_RATE,
+        bits_per_sample: 16,
+        sample_format: hound::SampleFormat::Int,
+    };
+
+    let mut writer = hound::WavWriter::create(dir.join(format!("{}.wav", meta.id)), spec)
+        .map_err(|e| e.to_string())?;
+
+    for &s in samples {
+        writer.write_sample(s).map_err(|e| e.to_string())?;
+    }
+    writer.finalize().map_err(|e| e.to_string())?;
+
+    write_meta(dir, meta)
+}
+
+fn write_meta(dir: &Path, meta: &SampleMeta) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(meta).map_err(|e| e.to_string())?;
+    fs::write(dir.join(format!("{}.json", meta.id)), json).map_err(|e| e.to_string())
+}
+
+fn settings_snapshot() -> SampleSettings {
+    match DB.get() {
+        Some(db) => {
+            let s = db.read();
+            SampleSettings {
+                wake_word_engine: s.wake_word_engine.to_string(),
+                vosk_model: s.vosk_model.clone(),
+                language: s.language.clone(),
+                noise_suppression: s.noise_suppression.to_string(),
+                vad: s.vad.to_string(),
+                gain_normalizer: s.gain_normalizer,
+                microphone: s.microphone,
+            }
+        }
+        None => SampleSettings::default(),
+    }
+}
+
+// List captured samples, newest first
+pub fn list_samples() -> Vec<SampleMeta> {
+    let mut samples: Vec<SampleMeta> = match fs::read_dir(samples_dir()) {
+        Ok(entries) => entries.flatten()
+            .map(|e| e.path())
+            .filter(|p| p.extension().map(|e| e == "json").unwrap_or(false))
+            .filter_map(|p| fs::read_to_string(&p).ok())
+            .filter_map(|json| serde_json::from_str(&json).ok())
+            .collect(),
+        Err(_) => Vec::new(),
+    };
+
+    samples.sort_by(|a, b| b.id.cmp(&a.id));
+    samples
+}
+
+pub fn label_sample(id: &str, label: SampleLabel) -> Result<SampleMeta, String> {
+    check_sample_id(id)?;
+    let dir = samples_dir();
+    let meta_path = dir.join(format!("{}.json", id));
+
+    let json = fs::read_to_string(&meta_path)
+        .map_err(|_| format!("Sample not found: {}", id))?;
+    let mut meta: SampleMeta = serde_json::from_str(&json)
+        .map_err(|e| format!("Failed to parse {}: {}", meta_path.display(), e))?;
+
+    meta.label = Some(label);
+    write_meta(&dir, &meta)?;
+
+    info!("Diagnostic sample {} labeled as {}", id, label);
+    Ok(meta)
+}
+
+pub fn delete_sample(id: &str) -> Result<(), String> {
+    check_sample_id(id)?;
+    let dir = samples_dir();
+    let _ = fs::remove_file(dir.join(format!("{}.wav", id)));
+    fs::remove_file(dir.join(format!("{}.json", id)))
+        .map_err(|_| format!("Sample not found: {}", id))
+}
+
+// Ids come from the GUI / CLI and end up in file paths, only accept what capture() generates:
+// "20240131-235959-123-nearmiss"
+fn check_sample_id(id: &str) -> Result<(), String> {
+    let parts: Vec<&str> = id.splitn(4, '-').collect();
+    let digits = |part: &str, len: usize| part.len() == len && part.bytes().all(|b| b.is_ascii_digit());
+
+    let valid = matches!(parts.as_slice(), [date, time, millis, kind]
+        if digits(date, 8) && digits(time, 6) && digits(millis, 3)
+            && !kind.is_empty() && kind.bytes().all(|b| b.is_ascii_lowercase() || b == b'_'));
+
+    if valid {
+        Ok(())
+    } else {
+        Err(format!("Invalid sample id: {}", id))
+    }
+}
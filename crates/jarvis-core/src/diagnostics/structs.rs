@@ -0,0 +1,89 @@
+use std::fmt;
+use std::str::FromStr;
+use serde::{Deserialize, Serialize};
+
+// What caused the sample to be captured
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SampleKind {
+    // wake word fired (score above the threshold)
+    Detection,
+
+    // score landed between the near-miss bound and the threshold
+    NearMiss,
+
+    // speech recognized after activation
+    Command,
+}
+
+// User verdict, used for threshold tuning
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SampleLabel {
+    Correct,
+    FalseAccept,
+    Miss,
+}
+
+impl FromStr for SampleLabel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().replace('-', "_").as_str() {
+            "correct" | "ok" => Ok(SampleLabel::Correct),
+            "false_accept" | "fa" => Ok(SampleLabel::FalseAccept),
+            "miss" | "false_reject" | "fr" => Ok(SampleLabel::Miss),
+            _ => Err(format!("Unknown label: {}", s)),
+        }
+    }
+}
+
+impl fmt::Display for SampleKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl fmt::Display for SampleLabel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+// JSON sidecar written next to every captured WAV file
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SampleMeta {
+    pub id: String,
+    pub kind: SampleKind,
+    pub timestamp: i64,
+
+    // "Rustpotter", "Vosk", or the STT engine for command samples
+    pub engine: String,
+
+    // raw score (rustpotter score, vosk similarity ratio, ...)
+    pub score: f64,
+    pub threshold: f64,
+    pub near_miss_bound: f64,
+
+    // recognized text, if any
+    #[serde(default)]
+    pub text: Option<String>,
+
+    pub duration_ms: u64,
+    pub settings: SampleSettings,
+
+    #[serde(default)]
+    pub label: Option<SampleLabel>,
+}
+
+// Snapshot of the settings that affect detection
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct SampleSettings {
+    pub wake_word_engine: String,
+    pub vosk_model: String,
+    pub language: String,
+    pub noise_suppression: String,
+    pub vad: String,
+    pub gain_normalizer: bool,
+    pub microphone: i32,
+}
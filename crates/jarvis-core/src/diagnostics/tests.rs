@@ -0,0 +1,23 @@
+use crate::diagnostics::{check_sample_id, SampleLabel};
+
+#[test]
+fn accepts_generated_ids_only() {
+    assert!(check_sample_id("20240131-235959-123-nearmiss").is_ok());
+    assert!(check_sample_id("20240131-235959-123-detection").is_ok());
+
+    assert!(check_sample_id("../settings").is_err());
+    assert!(check_sample_id("20240131-235959-123-../../x").is_err());
+    assert!(check_sample_id("20240131-235959-123-a/b").is_err());
+    assert!(check_sample_id("20240131-235959-123-a\\b").is_err());
+    assert!(check_sample_id("20240131-235959-123-").is_err());
+    assert!(check_sample_id("2024013-235959-123-detection").is_err());
+    assert!(check_sample_id("").is_err());
+}
+
+#[test]
+fn parses_labels() {
+    assert_eq!("correct".parse(), Ok(SampleLabel::Correct));
+    assert_eq!("False-Accept".parse(), Ok(SampleLabel::FalseAccept));
+    assert_eq!("fr".parse(), Ok(SampleLabel::Miss));
+    assert!("maybe".parse::<SampleLabel>().is_err());
+}
@@ -0,0 +1,92 @@
+mod noop;
+
+#[cfg(target_os = "linux")]
+mod pulse;
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests;
+
+use once_cell::sync::OnceCell;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+use crate::DB;
+
+// Lowers other applications' volume while the assistant listens
+pub trait Ducker: Send {
+    fn name(&self) -> &'static str;
+
+    // level is a fraction of the current volume (0.0 - 1.0)
+    fn duck(&mut self, level: f32) -> Result<(), String>;
+
+    fn restore(&mut self) -> Result<(), String>;
+}
+
+static DUCKER: OnceCell<Mutex<Box<dyn Ducker>>> = OnceCell::new();
+static LEVEL: OnceCell<f32> = OnceCell::new();
+static DUCKED: AtomicBool = AtomicBool::new(false);
+
+pub fn init() {
+    if DUCKER.get().is_some() {
+        return;
+    }
+
+    let settings = match DB.get() {
+        Some(db) => db.read().ducking.clone(),
+        None => return,
+    };
+
+    if !settings.enabled {
+        info!("Ducking: disabled");
+        return;
+    }
+
+    let ducker = backend();
+    info!("Ducking: {} (level {:.2})", ducker.name(), settings.level);
+
+    LEVEL.set(settings.level.clamp(0.0, 1.0)).ok();
+    DUCKER.set(Mutex::new(ducker)).ok();
+}
+
+fn backend() -> Box<dyn Ducker> {
+    #[cfg(target_os = "linux")]
+    {
+        if pulse::available() {
+            return Box::new(pulse::PulseDucker::new(pulse::Pactl));
+        }
+        warn!("Ducking: pactl not available, ducking will do nothing.");
+    }
+
+    Box::new(noop::NoopDucker)
+}
+
+pub fn duck() {
+    let ducker = match DUCKER.get() {
+        Some(d) => d,
+        None => return,
+    };
+
+    if DUCKED.swap(true, Ordering::SeqCst) {
+        return; // already ducked
+    }
+
+    let level = *LEVEL.get().unwrap_or(&crate::config::DUCKING_LEVEL);
+    if let Err(e) = ducker.lock().unwrap().duck(level) {
+        warn!("Ducking failed: {}", e);
+    }
+}
+
+pub fn restore() {
+    let ducker = match DUCKER.get() {
+        Some(d) => d,
+        None => return,
+    };
+
+    if !DUCKED.swap(false, Ordering::SeqCst) {
+        return;
+    }
+
+    if let Err(e) = ducker.lock().unwrap().restore() {
+        warn!("Ducking restore failed: {}", e);
+    }
+}
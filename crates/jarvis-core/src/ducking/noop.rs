@@ -0,0 +1,18 @@
+use super::Ducker;
+
+// platforms without a ducking implementation yet
+pub struct NoopDucker;
+
+impl Ducker for NoopDucker {
+    fn name(&self) -> &'static str {
+        "none"
+    }
+
+    fn duck(&mut self, _level: f32) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn restore(&mut self) -> Result<(), String> {
+        Ok(())
+    }
+}
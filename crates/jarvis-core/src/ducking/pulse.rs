@@ -0,0 +1,165 @@
+// PulseAudio / PipeWire (pipewire-pulse) ducking via pactl
+
+use std::process::Command;
+
+use super::Ducker;
+
+pub trait CommandRunner: Send {
+    fn run(&mut self, args: &[&str]) -> Result<String, String>;
+}
+
+pub struct Pactl;
+
+impl CommandRunner for Pactl {
+    fn run(&mut self, args: &[&str]) -> Result<String, String> {
+        let output = Command::new("pactl")
+            .args(args)
+            .env("LC_ALL", "C") // output is parsed
+            .output()
+            .map_err(|e| format!("Cannot run pactl: {}", e))?;
+
+        if !output.status.success() {
+            return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    }
+}
+
+pub fn available() -> bool {
+    Command::new("pactl")
+        .arg("info")
+        .output()
+        .map(|o| o.status.success())
+        .unwrap_or(false)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SinkInput {
+    pub index: u32,
+    pub volumes: Vec<u32>,
+    pub pid: Option<u32>,
+    pub corked: bool,
+}
+
+pub struct PulseDucker<R: CommandRunner> {
+    runner: R,
+    own_pid: u32,
+    // original volumes of the streams we lowered
+    saved: Vec<(u32, Vec<u32>)>,
+}
+
+impl<R: CommandRunner> PulseDucker<R> {
+    pub fn new(runner: R) -> Self {
+        Self::with_pid(runner, std::process::id())
+    }
+
+    pub fn with_pid(runner: R, own_pid: u32) -> Self {
+        Self {
+            runner,
+            own_pid,
+            saved: Vec::new(),
+        }
+    }
+
+    fn set_volume(&mut self, index: u32, volumes: &[u32]) -> Result<(), String> {
+        let index = index.to_string();
+        let volumes: Vec<String> = volumes.iter().map(|v| v.to_string()).collect();
+
+        let mut args = vec!["set-sink-input-volume", index.as_str()];
+        args.extend(volumes.iter().map(|v| v.as_str()));
+
+        self.runner.run(&args).map(|_| ())
+    }
+}
+
+impl<R: CommandRunner> Ducker for PulseDucker<R> {
+    fn name(&self) -> &'static str {
+        "pulseaudio"
+    }
+
+    fn duck(&mut self, level: f32) -> Result<(), String> {
+        if !self.saved.is_empty() {
+            return Ok(()); // already ducked
+        }
+
+        let list = self.runner.run(&["list", "sink-inputs"])?;
+
+        for input in parse_sink_inputs(&list) {
+            // leave our own sounds and paused streams alone
+            if input.corked || input.pid == Some(self.own_pid) || input.volumes.is_empty() {
+                continue;
+            }
+
+            let lowered: Vec<u32> = input.volumes.iter()
+                .map(|v| (*v as f32 * level) as u32)
+                .collect();
+
+            match self.set_volume(input.index, &lowered) {
+                Ok(_) => self.saved.push((input.index, input.volumes)),
+                Err(e) => warn!("Cannot duck sink input #{}: {}", input.index, e),
+            }
+        }
+
+        debug!("Ducked {} stream(s).", self.saved.len());
+        Ok(())
+    }
+
+    fn restore(&mut self) -> Result<(), String> {
+        for (index, volumes) in std::mem::take(&mut self.saved) {
+            // stream may have ended meanwhile
+            if let Err(e) = self.set_volume(index, &volumes) {
+                debug!("Cannot restore sink input #{}: {}", index, e);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// parse `pactl list sink-inputs` (C locale)
+pub fn parse_sink_inputs(output: &str) -> Vec<SinkInput> {
+    let mut inputs = Vec::new();
+    let mut current: Option<SinkInput> = None;
+
+    for line in output.lines() {
+        let trimmed = line.trim();
+
+        if let Some(index) = trimmed.strip_prefix("Sink Input #") {
+            if let Some(input) = current.take() {
+                inputs.push(input);
+            }
+            current = index.trim().parse().ok().map(|index| SinkInput {
+                index,
+                volumes: Vec::new(),
+                pid: None,
+                corked: false,
+            });
+            continue;
+        }
+
+        let input = match current.as_mut() {
+            Some(i) => i,
+            None => continue,
+        };
+
+        if let Some(corked) = trimmed.strip_prefix("Corked:") {
+            input.corked = corked.trim() == "yes";
+        } else if let Some(volume) = trimmed.strip_prefix("Volume:") {
+            // front-left: 65536 / 100% / 0.00 dB,   front-right: 65536 / 100% / 0.00 dB
+            input.volumes = volume.split(',')
+                .filter_map(|channel| channel.split(':').nth(1))
+                .filter_map(|value| value.split_whitespace().next())
+                .filter_map(|raw| raw.parse().ok())
+                .collect();
+        } else if let Some(pid) = trimmed.strip_prefix("application.process.id = ") {
+            input.pid = pid.trim_matches('"').parse().ok();
+        }
+    }
+
+    if let Some(input) = current {
+        inputs.push(input);
+    }
+
+    inputs
+}
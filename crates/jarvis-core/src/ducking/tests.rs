@@ -0,0 +1,90 @@
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use crate::ducking::pulse::{parse_sink_inputs, CommandRunner, PulseDucker};
+    use crate::ducking::Ducker;
+
+    const SINK_INPUTS: &str = "Sink Input #42
+	Driver: protocol-native.c
+	Owner Module: 9
+	Client: 51
+	Sink: 0
+	Corked: no
+	Mute: no
+	Volume: front-left: 65536 / 100% / 0.00 dB,   front-right: 32768 /  50% / -18.06 dB
+	        balance -0.50
+	Properties:
+		application.name = \"Firefox\"
+		application.process.id = \"1234\"
+
+Sink Input #43
+	Corked: yes
+	Volume: mono: 65536 / 100% / 0.00 dB
+	Properties:
+		application.process.id = \"2222\"
+
+Sink Input #44
+	Corked: no
+	Volume: front-left: 40000 /  61% / -12.89 dB,   front-right: 40000 /  61% / -12.89 dB
+	Properties:
+		application.process.id = \"999\"
+";
+
+    #[derive(Clone, Default)]
+    struct MockRunner {
+        calls: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl CommandRunner for MockRunner {
+        fn run(&mut self, args: &[&str]) -> Result<String, String> {
+            self.calls.lock().unwrap().push(args.join(" "));
+
+            if args == ["list", "sink-inputs"] {
+                return Ok(SINK_INPUTS.to_string());
+            }
+            Ok(String::new())
+        }
+    }
+
+    #[test]
+    fn test_parse_sink_inputs() {
+        let inputs = parse_sink_inputs(SINK_INPUTS);
+
+        assert_eq!(inputs.len(), 3);
+        assert_eq!(inputs[0].index, 42);
+        assert_eq!(inputs[0].volumes, vec![65536, 32768]);
+        assert_eq!(inputs[0].pid, Some(1234));
+        assert!(!inputs[0].corked);
+        assert!(inputs[1].corked);
+        assert_eq!(inputs[1].volumes, vec![65536]);
+    }
+
+    #[test]
+    fn test_duck_and_restore() {
+        let runner = MockRunner::default();
+        let calls = runner.calls.clone();
+
+        // pretend we are pid 999, so #44 is our own playback
+        let mut ducker = PulseDucker::with_pid(runner, 999);
+
+        ducker.duck(0.5).unwrap();
+        assert_eq!(*calls.lock().unwrap(), vec![
+            "list sink-inputs",
+            "set-sink-input-volume 42 32768 16384",
+        ]);
+
+        // second duck is a no-op
+        ducker.duck(0.5).unwrap();
+        assert_eq!(calls.lock().unwrap().len(), 2);
+
+        calls.lock().unwrap().clear();
+        ducker.restore().unwrap();
+        assert_eq!(*calls.lock().unwrap(), vec!["set-sink-input-volume 42 65536 32768"]);
+
+        // nothing left to restore
+        calls.lock().unwrap().clear();
+        ducker.restore().unwrap();
+        assert!(calls.lock().unwrap().is_empty());
+    }
+}
@@ -1,8 +1,6 @@
 use std::collections::HashMap;
 use std::fs;
-use crate::APP_DIR;
-
-const GLINER_DIRS: &[&str] = &["gliner_small-v2.1", "gliner_multi-v2.1"];
+use crate::models::{self, ModelKind};
 
 #[derive(Debug, Clone)]
 pub struct GlinerModelVariant {
@@ -12,15 +10,13 @@ pub struct GlinerModelVariant {
     pub display_name: String,
 }
 
-// scan both model dirs and return a deduplicated list of model types
+// scan registered model dirs and return a deduplicated list of model types
 pub fn scan_gliner_variants() -> Vec<GlinerModelVariant> {
-    let base = APP_DIR.join("resources").join("models");
-
-    // collect: type -> { dir_name -> size_mb }
+    // collect: type -> { model_id -> size_mb }
     let mut types: HashMap<String, HashMap<String, u64>> = HashMap::new();
 
-    for dir_name in GLINER_DIRS {
-        let onnx_dir = base.join(dir_name).join("onnx");
+    for entry in models::list(ModelKind::Gliner) {
+        let onnx_dir = entry.dir().join("onnx");
         if !onnx_dir.exists() { continue; }
 
         let entries = match fs::read_dir(&onnx_dir) {
@@ -40,7 +36,7 @@ pub fn scan_gliner_variants() -> Vec<GlinerModelVariant> {
 
             types.entry(variant_type)
                 .or_default()
-                .insert(dir_name.to_string(), size_mb);
+                .insert(entry.id.clone(), size_mb);
         }
     }
 
@@ -84,9 +80,9 @@ fn build_size_string(sizes: &HashMap<String, u64>) -> String {
     }
 
     let mut parts: Vec<String> = Vec::new();
-    for dir_name in GLINER_DIRS {
-        if let Some(mb) = sizes.get(*dir_name) {
-            parts.push(format!("{}: {}MB", short_dir_name(dir_name), mb));
+    for entry in models::list(ModelKind::Gliner) {
+        if let Some(mb) = sizes.get(&entry.id) {
+            parts.push(format!("{}: {}MB", short_dir_name(&entry.id), mb));
         }
     }
     parts.join(" / ")
@@ -101,23 +97,14 @@ fn short_dir_name(dir: &str) -> &str {
 // resolve variant type + language into actual file path
 // returns (model_dir_path, onnx_file_name) or None
 pub fn resolve_model(variant: &str, language: &str) -> Option<(std::path::PathBuf, String)> {
-    let base = APP_DIR.join("resources").join("models");
     let file_name = type_to_file_name(variant);
 
-    // pick dir based on language
-    let preferred: &[&str] = match language {
-        "en" => &["gliner_small-v2.1", "gliner_multi-v2.1"],
-        _ => &["gliner_multi-v2.1", "gliner_small-v2.1"],
-    };
-
-    for dir_name in preferred {
-        let path = base.join(dir_name).join("onnx").join(&file_name);
-        if path.exists() {
-            return Some((base.join(dir_name), file_name));
-        }
-    }
-
-    None
+    // registry order: language-specific first, then multilingual
+    models::candidates(ModelKind::Gliner, language)
+        .into_iter()
+        .map(|entry| entry.dir())
+        .find(|dir| dir.join("onnx").join(&file_name).exists())
+        .map(|dir| (dir, file_name))
 }
 
 // "full" -> "model.onnx", "int8" -> "model_int8.onnx"
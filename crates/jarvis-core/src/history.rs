@@ -0,0 +1,79 @@
+// Command history: what was said, what ran and how confirmations went.
+// Recent entries stay in memory, everything is appended to history.jsonl
+
+use std::collections::VecDeque;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+
+use crate::{config, APP_CONFIG_DIR};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum HistoryKind {
+    Executed,
+    Failed,
+    ConfirmationRequested,
+    Confirmed,
+    Cancelled,
+    TimedOut,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct HistoryEntry {
+    pub time: String, // rfc3339, local time
+    pub command: String,
+    pub kind: HistoryKind,
+    // the phrase, or the reply for confirmations
+    #[serde(default)]
+    pub text: Option<String>,
+}
+
+static RECENT: Lazy<Mutex<VecDeque<HistoryEntry>>> = Lazy::new(|| Mutex::new(VecDeque::new()));
+
+pub fn record(command: &str, kind: HistoryKind, text: Option<&str>) {
+    let entry = HistoryEntry {
+        time: chrono::Local::now().to_rfc3339(),
+        command: command.to_string(),
+        kind,
+        text: text.map(String::from),
+    };
+    debug!("History: {:?}", entry);
+
+    if let Err(e) = append(&entry) {
+        warn!("Failed to write history: {}", e);
+    }
+
+    let mut recent = RECENT.lock();
+    if recent.len() >= config::HISTORY_MAX_ENTRIES {
+        recent.pop_front();
+    }
+    recent.push_back(entry);
+}
+
+// Latest entries, oldest first
+pub fn recent(limit: usize) -> Vec<HistoryEntry> {
+    let recent = RECENT.lock();
+    recent.iter().skip(recent.len().saturating_sub(limit)).cloned().collect()
+}
+
+pub fn path() -> Option<PathBuf> {
+    APP_CONFIG_DIR.get().map(|dir| dir.join(config::HISTORY_PATH))
+}
+
+fn append(entry: &HistoryEntry) -> Result<(), String> {
+    // no config dir (tests, cli tools), memory only
+    let Some(path) = path() else { return Ok(()) };
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+
+    let line = serde_json::to_string(entry).map_err(|e| e.to_string())?;
+    let mut file = OpenOptions::new().create(true).append(true).open(&path).map_err(|e| e.to_string())?;
+    writeln!(file, "{}", line).map_err(|e| e.to_string())
+}
@@ -0,0 +1,19 @@
+// Shared blocking HTTP client, used by the Lua http API and "http" commands
+
+use std::time::Duration;
+
+use once_cell::sync::OnceCell;
+
+use crate::config;
+
+static CLIENT: OnceCell<reqwest::blocking::Client> = OnceCell::new();
+
+pub fn shared() -> Result<&'static reqwest::blocking::Client, String> {
+    CLIENT.get_or_try_init(|| {
+        reqwest::blocking::Client::builder()
+            .timeout(Duration::from_secs(config::HTTP_TIMEOUT_SECS))
+            .user_agent(config::HTTP_USER_AGENT)
+            .build()
+            .map_err(|e| format!("Failed to create HTTP client: {}", e))
+    })
+}
@@ -66,8 +66,24 @@ settings-noise-suppression = Noise suppression
 settings-noise-suppression-desc = Reduces background noise. May negatively affect recognition.
 settings-vad = Voice detection (VAD)
 settings-vad-desc = Skips silence, saves CPU resources.
+settings-vad-silero-missing = Silero model is not installed, Energy detection is used instead. Import the silero_vad archive.
+settings-vad-silero-install = Import Silero model
 settings-gain-normalizer = Gain normalizer
 settings-gain-normalizer-desc = Automatically adjusts volume level.
+settings-processed-audio = Recognize processed audio
+settings-processed-audio-desc = Wake word and speech recognition hear the audio after noise suppression and gain instead of the raw microphone.
+settings-echo-cancellation = Self-listening suppression
+settings-echo-cancellation-desc = Keeps the assistant from reacting to its own voice.
+settings-echo-gate = Ignore wake-word during playback
+settings-echo-nlms = Echo cancellation (NLMS)
+settings-ducking = Lower other apps' volume
+settings-ducking-desc = Quiets music and videos while the assistant listens for a command.
+settings-early-intent = Early command execution
+settings-early-intent-desc = Runs confident commands as soon as the recognized text stops changing, without waiting for silence.
+settings-command-grammar = Command vocabulary recognition
+settings-command-grammar-desc = Biases speech recognition towards the phrases of installed commands. Helps small models with known commands.
+settings-command-grammar-alongside = Alongside free speech
+settings-command-grammar-only = Commands only
 settings-api-keys = API Keys
 settings-save = Save
 settings-cancel = Cancel
@@ -81,6 +97,7 @@ settings-beta-desc = Some features may not work correctly.
 settings-beta-feedback = Report all bugs to
 settings-beta-bot = our Telegram bot
 settings-open-logs = Open logs folder
+settings-open-samples = Wake-word samples
 
 # settings - picovoice
 settings-attention = Attention!
@@ -96,7 +113,10 @@ settings-vosk-model-desc =
     Select Vosk model for speech recognition.
     You can download models here: https://alphacephei.com/vosk/models
 settings-models-not-found = Models not found
-settings-models-hint = Place Vosk models in resources/vosk folder
+settings-models-hint = Place Vosk models in resources/vosk folder or import a downloaded .zip archive
+settings-vosk-import = Import model from archive
+settings-vosk-import-failed = Model import failed
+settings-vosk-model-broken = broken
 
 # settings - openai
 settings-openai-key = OpenAI Key
@@ -110,6 +130,25 @@ commands-wip-title = [404] This section is under development!
 commands-wip-desc = Here will be a list of commands + full-featured command editor.
 commands-wip-follow = Follow updates in
 commands-wip-channel = our Telegram channel
+commands-packs-title = Command packs
+commands-pack-commands = commands
+commands-pack-capabilities = Capabilities
+commands-pack-none = none
+commands-pack-min-version = requires app
+commands-pack-no-manifest = no [pack] manifest
+commands-toggle-offline = Start the assistant to switch commands on or off.
+
+# ### DIAGNOSTICS PAGE
+diagnostics-title = Wake-word samples
+diagnostics-desc = Recordings saved by wake-word diagnostics. Mark them to tune the activation threshold.
+diagnostics-empty = No samples yet. Turn on wake-word diagnostics and talk to the assistant.
+diagnostics-open-folder = Open samples folder
+diagnostics-label-correct = Correct
+diagnostics-label-false-accept = False accept
+diagnostics-label-miss = Miss
+diagnostics-delete = Delete
+diagnostics-unlabeled = not marked
+diagnostics-score = score
 
 # ### ERRORS
 error-generic = An error occurred
@@ -121,6 +160,10 @@ notification-saved = Settings saved!
 notification-error = Error
 notification-assistant-started = Assistant started
 notification-assistant-stopped = Assistant stopped
+confirm-prompt = Run «{ $command }»? Say "yes" or "no".
+confirm-phrase-prompt = Run «{ $command }»? Say "{ $phrase }" to confirm.
+confirm-cancelled = Cancelled
+vad-silero-missing = Silero voice detection model is not installed, using Energy detection. Import it in the settings.
 
 # SLOTS EXTRACTION
 settings-slot-engine = Slot extraction
@@ -66,8 +66,24 @@ settings-noise-suppression = Шумоподавление
 settings-noise-suppression-desc = Уменьшает фоновый шум. Может негативно влиять на распознавание.
 settings-vad = Определение голоса (VAD)
 settings-vad-desc = Пропускает тишину, экономит ресурсы CPU.
+settings-vad-silero-missing = Модель Silero не установлена, вместо неё используется Energy. Импортируйте архив silero_vad.
+settings-vad-silero-install = Импортировать модель Silero
 settings-gain-normalizer = Нормализация громкости
 settings-gain-normalizer-desc = Автоматически регулирует уровень громкости.
+settings-processed-audio = Распознавать обработанный звук
+settings-processed-audio-desc = Распознавание активации и речи получает звук после шумоподавления и нормализации, а не сырой сигнал микрофона.
+settings-echo-cancellation = Подавление самопрослушивания
+settings-echo-cancellation-desc = Не даёт ассистенту реагировать на собственный голос.
+settings-echo-gate = Игнорировать активацию во время воспроизведения
+settings-echo-nlms = Эхоподавление (NLMS)
+settings-ducking = Приглушать другие приложения
+settings-ducking-desc = Уменьшает громкость музыки и видео, пока ассистент слушает команду.
+settings-early-intent = Раннее выполнение команд
+settings-early-intent-desc = Выполняет уверенно распознанные команды, как только текст перестаёт меняться, не дожидаясь тишины.
+settings-command-grammar = Распознавание по словарю команд
+settings-command-grammar-desc = Подстраивает распознавание речи под фразы установленных команд. Помогает небольшим моделям точнее понимать известные команды.
+settings-command-grammar-alongside = Вместе со свободной речью
+settings-command-grammar-only = Только команды
 settings-api-keys = API Ключи
 settings-save = Сохранить
 settings-cancel = Отмена
@@ -81,6 +97,7 @@ settings-beta-desc = Часть функций может работать не
 settings-beta-feedback = Сообщайте обо всех найденных багах в
 settings-beta-bot = наш телеграм бот
 settings-open-logs = Открыть папку с логами
+settings-open-samples = Записи активации
 
 # settings - picovoice
 settings-attention = Внимание!
@@ -96,7 +113,10 @@ settings-vosk-model-desc =
     Выберите модель Vosk для распознавания речи.
     Вы можете скачать модели здесь: https://alphacephei.com/vosk/models
 settings-models-not-found = Модели не найдены
-settings-models-hint = Поместите модели Vosk в папку resources/vosk
+settings-models-hint = Поместите модели Vosk в папку resources/vosk или импортируйте скачанный .zip архив
+settings-vosk-import = Импортировать модель из архива
+settings-vosk-import-failed = Не удалось импортировать модель
+settings-vosk-model-broken = повреждена
 
 # settings - openai
 settings-openai-key = Ключ OpenAI
@@ -110,6 +130,25 @@ commands-wip-title = [404] Этот раздел еще находится в р
 commands-wip-desc = Тут будет список команд + полноценный редактор команд.
 commands-wip-follow = Следите за обновлениями в
 commands-wip-channel = нашем телеграм канале
+commands-packs-title = Пакеты команд
+commands-pack-commands = команд
+commands-pack-capabilities = Разрешения
+commands-pack-none = нет
+commands-pack-min-version = требует версию
+commands-pack-no-manifest = без манифеста [pack]
+commands-toggle-offline = Запустите ассистента, чтобы включать и выключать команды.
+
+# ### DIAGNOSTICS PAGE
+diagnostics-title = Записи активации
+diagnostics-desc = Записи, сохранённые диагностикой слова активации. Отмечайте их, чтобы подобрать порог срабатывания.
+diagnostics-empty = Записей пока нет. Включите диагностику слова активации и поговорите с ассистентом.
+diagnostics-open-folder = Открыть папку с записями
+diagnostics-label-correct = Верно
+diagnostics-label-false-accept = Ложное срабатывание
+diagnostics-label-miss = Пропуск
+diagnostics-delete = Удалить
+diagnostics-unlabeled = не отмечено
+diagnostics-score = оценка
 
 # ### ERRORS
 error-generic = Произошла ошибка
@@ -121,6 +160,10 @@ notification-saved = Настройки сохранены!
 notification-error = Ошибка
 notification-assistant-started = Ассистент запущен
 notification-assistant-stopped = Ассистент остановлен
+confirm-prompt = Выполнить «{ $command }»? Скажите «да» или «нет».
+confirm-phrase-prompt = Выполнить «{ $command }»? Скажите «{ $phrase }» для подтверждения.
+confirm-cancelled = Отменено
+vad-silero-missing = Модель определения голоса Silero не установлена, используется Energy. Импортируйте её в настройках.
 
 # SLOTS EXTRACTION
 settings-slot-engine = Извлечение параметров
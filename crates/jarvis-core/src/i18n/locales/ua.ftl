@@ -66,8 +66,24 @@ settings-noise-suppression = Шумозаглушення
 settings-noise-suppression-desc = Зменшує фоновий шум. Може негативно впливати на розпізнавання.
 settings-vad = Визначення голосу (VAD)
 settings-vad-desc = Пропускає тишу, економить ресурси CPU.
+settings-vad-silero-missing = Модель Silero не встановлена, замість неї використовується Energy. Імпортуйте архів silero_vad.
+settings-vad-silero-install = Імпортувати модель Silero
 settings-gain-normalizer = Нормалізація гучності
 settings-gain-normalizer-desc = Автоматично регулює рівень гучності.
+settings-processed-audio = Розпізнавати оброблений звук
+settings-processed-audio-desc = Розпізнавання активації та мовлення отримує звук після шумозаглушення й нормалізації, а не сирий сигнал мікрофона.
+settings-echo-cancellation = Придушення самопрослуховування
+settings-echo-cancellation-desc = Не дає асистенту реагувати на власний голос.
+settings-echo-gate = Ігнорувати активацію під час відтворення
+settings-echo-nlms = Ехоподавлення (NLMS)
+settings-ducking = Приглушувати інші застосунки
+settings-ducking-desc = Зменшує гучність музики та відео, поки асистент слухає команду.
+settings-early-intent = Раннє виконання команд
+settings-early-intent-desc = Виконує впевнено розпізнані команди, щойно текст перестає змінюватися, не чекаючи тиші.
+settings-command-grammar = Розпізнавання за словником команд
+settings-command-grammar-desc = Підлаштовує розпізнавання мовлення під фрази встановлених команд. Допомагає невеликим моделям точніше розуміти відомі команди.
+settings-command-grammar-alongside = Разом із вільним мовленням
+settings-command-grammar-only = Лише команди
 settings-api-keys = API Ключі
 settings-save = Зберегти
 settings-cancel = Скасувати
@@ -81,6 +97,7 @@ settings-beta-desc = Частина функцій може працювати 
 settings-beta-feedback = Повідомляйте про всі знайдені баги в
 settings-beta-bot = наш телеграм бот
 settings-open-logs = Відкрити папку з логами
+settings-open-samples = Записи активації
 
 # settings - picovoice
 settings-attention = Увага!
@@ -96,7 +113,10 @@ settings-vosk-model-desc =
     Виберіть модель Vosk для розпізнавання мовлення.
     Ви можете завантажити моделі тут: https://alphacephei.com/vosk/models
 settings-models-not-found = Моделі не знайдено
-settings-models-hint = Помістіть моделі Vosk в папку resources/vosk
+settings-models-hint = Помістіть моделі Vosk у теку resources/vosk або імпортуйте завантажений .zip архів
+settings-vosk-import = Імпортувати модель з архіву
+settings-vosk-import-failed = Не вдалося імпортувати модель
+settings-vosk-model-broken = пошкоджена
 
 # settings - openai
 settings-openai-key = Ключ OpenAI
@@ -110,6 +130,25 @@ commands-wip-title = [404] Цей розділ ще в розробці!
 commands-wip-desc = Тут буде список команд + повноцінний редактор команд.
 commands-wip-follow = Слідкуйте за оновленнями в
 commands-wip-channel = нашому телеграм каналі
+commands-packs-title = Пакети команд
+commands-pack-commands = команд
+commands-pack-capabilities = Дозволи
+commands-pack-none = немає
+commands-pack-min-version = потребує версію
+commands-pack-no-manifest = без маніфесту [pack]
+commands-toggle-offline = Запустіть асистента, щоб вмикати та вимикати команди.
+
+# ### DIAGNOSTICS PAGE
+diagnostics-title = Записи активації
+diagnostics-desc = Записи, збережені діагностикою слова активації. Позначайте їх, щоб підібрати поріг спрацювання.
+diagnostics-empty = Записів поки немає. Увімкніть діагностику слова активації та поговоріть з асистентом.
+diagnostics-open-folder = Відкрити папку із записами
+diagnostics-label-correct = Вірно
+diagnostics-label-false-accept = Хибне спрацювання
+diagnostics-label-miss = Пропуск
+diagnostics-delete = Видалити
+diagnostics-unlabeled = не позначено
+diagnostics-score = оцінка
 
 # ### ERRORS
 error-generic = Сталася помилка
@@ -121,6 +160,10 @@ notification-saved = Налаштування збережено!
 notification-error = Помилка
 notification-assistant-started = Асистент запущено
 notification-assistant-stopped = Асистент зупинено
+confirm-prompt = Виконати «{ $command }»? Скажіть «так» або «ні».
+confirm-phrase-prompt = Виконати «{ $command }»? Скажіть «{ $phrase }» для підтвердження.
+confirm-cancelled = Скасовано
+vad-silero-missing = Модель визначення голосу Silero не встановлена, використовується Energy. Імпортуйте її в налаштуваннях.
 
 # SLOTS EXTRACTION
 settings-slot-engine = Витяг параметрів
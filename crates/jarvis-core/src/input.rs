@@ -0,0 +1,269 @@
+// Keyboard / media key emulation: "keys" commands and jarvis.input in Lua
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(target_os = "linux")]
+mod linux;
+#[cfg(target_os = "windows")]
+mod windows;
+#[cfg(target_os = "macos")]
+mod macos;
+
+use std::time::Duration;
+
+use once_cell::sync::OnceCell;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+
+static BACKEND: OnceCell<Box<dyn InputBackend>> = OnceCell::new();
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Key {
+    // modifiers
+    Ctrl,
+    Shift,
+    Alt,
+    Super, // win / cmd
+
+    Char(char), // a-z, 0-9
+    F(u8),      // F1 - F24
+
+    Enter,
+    Escape,
+    Tab,
+    Space,
+    Backspace,
+    Delete,
+    Insert,
+    Home,
+    End,
+    PageUp,
+    PageDown,
+    Up,
+    Down,
+    Left,
+    Right,
+    PrintScreen,
+
+    // media
+    PlayPause,
+    NextTrack,
+    PrevTrack,
+    Stop,
+    VolumeUp,
+    VolumeDown,
+    Mute,
+}
+
+impl Key {
+    pub fn parse(name: &str) -> Option<Key> {
+        let name = name.trim().to_lowercase();
+
+        let key = match name.as_str() {
+            "ctrl" | "control" => Key::Ctrl,
+            "shift" => Key::Shift,
+            "alt" | "option" => Key::Alt,
+            "super" | "win" | "meta" | "cmd" | "command" => Key::Super,
+            "enter" | "return" => Key::Enter,
+            "esc" | "escape" => Key::Escape,
+            "tab" => Key::Tab,
+            "space" => Key::Space,
+            "backspace" => Key::Backspace,
+            "delete" | "del" => Key::Delete,
+            "insert" | "ins" => Key::Insert,
+            "home" => Key::Home,
+            "end" => Key::End,
+            "pageup" | "pgup" => Key::PageUp,
+            "pagedown" | "pgdn" => Key::PageDown,
+            "up" => Key::Up,
+            "down" => Key::Down,
+            "left" => Key::Left,
+            "right" => Key::Right,
+            "printscreen" | "print" => Key::PrintScreen,
+            "play_pause" | "playpause" | "media_play_pause" => Key::PlayPause,
+            "next" | "next_track" | "media_next" => Key::NextTrack,
+            "prev" | "previous" | "prev_track" | "media_prev" => Key::PrevTrack,
+            "stop" | "media_stop" => Key::Stop,
+            "volume_up" | "volumeup" => Key::VolumeUp,
+            "volume_down" | "volumedown" => Key::VolumeDown,
+            "mute" | "volume_mute" => Key::Mute,
+            other => {
+                let mut chars = other.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) if c.is_ascii_alphanumeric() => Key::Char(c),
+                    (Some('f'), Some(_)) => match other[1..].parse::<u8>() {
+                        Ok(n) if (1..=24).contains(&n) => Key::F(n),
+                        _ => return None,
+                    },
+                    _ => return None,
+                }
+            }
+        };
+
+        Some(key)
+    }
+
+    pub fn is_modifier(&self) -> bool {
+        matches!(self, Key::Ctrl | Key::Shift | Key::Alt | Key::Super)
+    }
+}
+
+// "ctrl+shift+t" -> [Ctrl, Shift, Char('t')], modifiers first
+pub fn parse_chord(chord: &str) -> Result<Vec<Key>, String> {
+    let keys = chord.split('+')
+        .map(|k| Key::parse(k).ok_or_else(|| format!("Unknown key '{}' in '{}'", k.trim(), chord)))
+        .collect::<Result<Vec<Key>, String>>()?;
+
+    if keys.iter().filter(|k| !k.is_modifier()).count() > 1 {
+        return Err(format!("'{}' has more than one non-modifier key", chord));
+    }
+    Ok(keys)
+}
+
+// One entry of a "keys" command / jarvis.input.send
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(untagged)]
+pub enum InputStep {
+    Chord(String),         // "ctrl+c", "volume_up"
+    Text { text: String }, // { text = "hello" }
+    Delay { delay: u64 },  // { delay = 200 } ms
+}
+
+// What a backend was asked to do
+#[derive(Debug, Clone, PartialEq)]
+pub enum InputEvent {
+    Chord(Vec<Key>),
+    Text(String),
+}
+
+pub trait InputBackend: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    // press all keys in order, release in reverse
+    fn chord(&self, keys: &[Key]) -> Result<(), String>;
+
+    fn type_text(&self, text: &str) -> Result<(), String>;
+
+    fn send(&self, event: &InputEvent) -> Result<(), String> {
+        match event {
+            InputEvent::Chord(keys) => self.chord(keys),
+            InputEvent::Text(text) => self.type_text(text),
+        }
+    }
+}
+
+// Records events instead of sending them, for tests and dry runs
+#[derive(Default)]
+pub struct Recorder {
+    events: Mutex<Vec<InputEvent>>,
+}
+
+impl Recorder {
+    pub fn events(&self) -> Vec<InputEvent> {
+        self.events.lock().clone()
+    }
+}
+
+impl InputBackend for Recorder {
+    fn name(&self) -> &'static str {
+        "recorder"
+    }
+
+    fn chord(&self, keys: &[Key]) -> Result<(), String> {
+        self.events.lock().push(InputEvent::Chord(keys.to_vec()));
+        Ok(())
+    }
+
+    fn type_text(&self, text: &str) -> Result<(), String> {
+        self.events.lock().push(InputEvent::Text(text.to_string()));
+        Ok(())
+    }
+}
+
+// Backend for this platform, picked on first use
+pub fn backend() -> &'static dyn InputBackend {
+    BACKEND.get_or_init(|| {
+        let backend = platform_backend();
+        info!("Input backend: {}", backend.name());
+        backend
+    }).as_ref()
+}
+
+#[cfg(target_os = "linux")]
+fn platform_backend() -> Box<dyn InputBackend> {
+    linux::detect()
+}
+
+#[cfg(target_os = "windows")]
+fn platform_backend() -> Box<dyn InputBackend> {
+    Box::new(windows::PowerShell)
+}
+
+#[cfg(target_os = "macos")]
+fn platform_backend() -> Box<dyn InputBackend> {
+    Box::new(macos::AppleScript)
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "windows", target_os = "macos")))]
+fn platform_backend() -> Box<dyn InputBackend> {
+    Box::new(Unsupported)
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "windows", target_os = "macos")))]
+struct Unsupported;
+
+#[cfg(not(any(target_os = "linux", target_os = "windows", target_os = "macos")))]
+impl InputBackend for Unsupported {
+    fn name(&self) -> &'static str {
+        "unsupported"
+    }
+
+    fn chord(&self, _keys: &[Key]) -> Result<(), String> {
+        Err("Key emulation is not supported on this platform".into())
+    }
+
+    fn type_text(&self, _text: &str) -> Result<(), String> {
+        Err("Key emulation is not supported on this platform".into())
+    }
+}
+
+// Parse all steps first, so a typo doesn't leave half of the keys pressed
+pub fn run(steps: &[InputStep], backend: &dyn InputBackend) -> Result<(), String> {
+    enum Parsed {
+        Event(InputEvent),
+        Delay(u64),
+    }
+
+    let parsed = steps.iter()
+        .map(|step| match step {
+            InputStep::Chord(chord) => parse_chord(chord).map(|keys| Parsed::Event(InputEvent::Chord(keys))),
+            InputStep::Text { text } => Ok(Parsed::Event(InputEvent::Text(text.clone()))),
+            InputStep::Delay { delay } => Ok(Parsed::Delay(*delay)),
+        })
+        .collect::<Result<Vec<Parsed>, String>>()?;
+
+    for step in parsed {
+        match step {
+            Parsed::Event(event) => backend.send(&event)?,
+            Parsed::Delay(ms) => std::thread::sleep(Duration::from_millis(ms)),
+        }
+    }
+
+    Ok(())
+}
+
+// Run a helper tool, turning a missing binary or a failure into an error
+#[cfg(any(target_os = "linux", target_os = "windows", target_os = "macos"))]
+fn run_tool(program: &str, args: &[String]) -> Result<(), String> {
+    let output = std::process::Command::new(program)
+        .args(args)
+        .output()
+        .map_err(|e| format!("Failed to run {}: {}", program, e))?;
+
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(format!("{} failed: {}", program, String::from_utf8_lossy(&output.stderr).trim()))
+    }
+}
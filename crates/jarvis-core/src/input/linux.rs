@@ -0,0 +1,147 @@
+// Linux backends: xdotool (X11) and ydotool (uinput, works on Wayland too)
+
+use super::{run_tool, InputBackend, Key};
+
+pub fn detect() -> Box<dyn InputBackend> {
+    let wayland = std::env::var_os("WAYLAND_DISPLAY").is_some();
+
+    // xdotool can't reach native wayland windows
+    if wayland && has_tool("ydotool") {
+        Box::new(Ydotool)
+    } else if has_tool("xdotool") {
+        Box::new(Xdotool)
+    } else if has_tool("ydotool") {
+        Box::new(Ydotool)
+    } else {
+        warn!("Neither xdotool nor ydotool found, key emulation will fail");
+        Box::new(Xdotool)
+    }
+}
+
+fn has_tool(name: &str) -> bool {
+    std::env::var_os("PATH")
+        .is_some_and(|paths| std::env::split_paths(&paths).any(|p| p.join(name).is_file()))
+}
+
+pub struct Xdotool;
+
+impl InputBackend for Xdotool {
+    fn name(&self) -> &'static str {
+        "xdotool"
+    }
+
+    fn chord(&self, keys: &[Key]) -> Result<(), String> {
+        let chord = keys.iter().map(|k| keysym(*k)).collect::<Vec<_>>().join("+");
+        run_tool("xdotool", &["key".into(), "--clearmodifiers".into(), chord])
+    }
+
+    fn type_text(&self, text: &str) -> Result<(), String> {
+        run_tool("xdotool", &["type".into(), "--clearmodifiers".into(), "--".into(), text.into()])
+    }
+}
+
+pub struct Ydotool;
+
+impl InputBackend for Ydotool {
+    fn name(&self) -> &'static str {
+        "ydotool"
+    }
+
+    // "29:1 46:1 46:0 29:0" = ctrl down, c down, c up, ctrl up
+    fn chord(&self, keys: &[Key]) -> Result<(), String> {
+        let codes = keys.iter()
+            .map(|k| keycode(*k).ok_or_else(|| format!("ydotool can't press {:?}", k)))
+            .collect::<Result<Vec<u16>, String>>()?;
+
+        let mut args = vec!["key".to_string()];
+        args.extend(codes.iter().map(|c| format!("{}:1", c)));
+        args.extend(codes.iter().rev().map(|c| format!("{}:0", c)));
+        run_tool("ydotool", &args)
+    }
+
+    fn type_text(&self, text: &str) -> Result<(), String> {
+        run_tool("ydotool", &["type".into(), "--".into(), text.into()])
+    }
+}
+
+fn keysym(key: Key) -> String {
+    let name = match key {
+        Key::Ctrl => "ctrl",
+        Key::Shift => "shift",
+        Key::Alt => "alt",
+        Key::Super => "super",
+        Key::Char(c) => return c.to_string(),
+        Key::F(n) => return format!("F{}", n),
+        Key::Enter => "Return",
+        Key::Escape => "Escape",
+        Key::Tab => "Tab",
+        Key::Space => "space",
+        Key::Backspace => "BackSpace",
+        Key::Delete => "Delete",
+        Key::Insert => "Insert",
+        Key::Home => "Home",
+        Key::End => "End",
+        Key::PageUp => "Prior",
+        Key::PageDown => "Next",
+        Key::Up => "Up",
+        Key::Down => "Down",
+        Key::Left => "Left",
+        Key::Right => "Right",
+        Key::PrintScreen => "Print",
+        Key::PlayPause => "XF86AudioPlay",
+        Key::NextTrack => "XF86AudioNext",
+        Key::PrevTrack => "XF86AudioPrev",
+        Key::Stop => "XF86AudioStop",
+        Key::VolumeUp => "XF86AudioRaiseVolume",
+        Key::VolumeDown => "XF86AudioLowerVolume",
+        Key::Mute => "XF86AudioMute",
+    };
+    name.to_string()
+}
+
+// linux/input-event-codes.h
+pub(super) fn keycode(key: Key) -> Option<u16> {
+    let code = match key {
+        Key::Ctrl => 29,
+        Key::Shift => 42,
+        Key::Alt => 56,
+        Key::Super => 125,
+        Key::Char(c) => return char_keycode(c),
+        Key::F(n @ 1..=10) => 58 + n as u16,
+        Key::F(11) => 87,
+        Key::F(12) => 88,
+        Key::F(n @ 13..=24) => 183 + (n as u16 - 13),
+        Key::F(_) => return None,
+        Key::Enter => 28,
+        Key::Escape => 1,
+        Key::Tab => 15,
+        Key::Space => 57,
+        Key::Backspace => 14,
+        Key::Delete => 111,
+        Key::Insert => 110,
+        Key::Home => 102,
+        Key::End => 107,
+        Key::PageUp => 104,
+        Key::PageDown => 109,
+        Key::Up => 103,
+        Key::Down => 108,
+        Key::Left => 105,
+        Key::Right => 106,
+        Key::PrintScreen => 99,
+        Key::PlayPause => 164,
+        Key::NextTrack => 163,
+        Key::PrevTrack => 165,
+        Key::Stop => 166,
+        Key::VolumeUp => 115,
+        Key::VolumeDown => 114,
+        Key::Mute => 113,
+    };
+    Some(code)
+}
+
+pub(super) fn char_keycode(c: char) -> Option<u16> {
+    const ROWS: [(&str, u16); 4] = [("1234567890", 2), ("qwertyuiop", 16), ("asdfghjkl", 30), ("zxcvbnm", 44)];
+
+    let c = c.to_ascii_lowercase();
+    ROWS.iter().find_map(|(row, start)| row.find(c).map(|i| start + i as u16))
+}
@@ -0,0 +1,79 @@
+// macOS backend: System Events through osascript
+
+use super::{run_tool, InputBackend, Key};
+
+pub struct AppleScript;
+
+impl InputBackend for AppleScript {
+    fn name(&self) -> &'static str {
+        "applescript"
+    }
+
+    fn chord(&self, keys: &[Key]) -> Result<(), String> {
+        let (modifiers, rest): (Vec<Key>, Vec<Key>) = keys.iter().partition(|k| k.is_modifier());
+
+        let using = if modifiers.is_empty() {
+            String::new()
+        } else {
+            let names: Vec<&str> = modifiers.iter()
+                .map(|k| match k {
+                    Key::Ctrl => "control down",
+                    Key::Shift => "shift down",
+                    Key::Alt => "option down",
+                    _ => "command down",
+                })
+                .collect();
+            format!(" using {{{}}}", names.join(", "))
+        };
+
+        let script = match rest.first() {
+            Some(Key::Char(c)) => format!("tell application \"System Events\" to keystroke \"{}\"{}", c, using),
+            Some(key) => match (key_code(*key), volume_script(*key)) {
+                (Some(code), _) => format!("tell application \"System Events\" to key code {}{}", code, using),
+                (None, Some(script)) => script.to_string(),
+                (None, None) => return Err(format!("Can't press {:?} on macOS", key)),
+            },
+            None => return Err("Modifier-only chords are not supported on macOS".into()),
+        };
+
+        run_tool("osascript", &["-e".into(), script])
+    }
+
+    fn type_text(&self, text: &str) -> Result<(), String> {
+        let escaped = text.replace('\\', "\\\\").replace('"', "\\\"");
+        run_tool("osascript", &["-e".into(), format!("tell application \"System Events\" to keystroke \"{}\"", escaped)])
+    }
+}
+
+// no media keys in System Events, volume goes through the sound settings
+fn volume_script(key: Key) -> Option<&'static str> {
+    match key {
+        Key::VolumeUp => Some("set volume output volume ((output volume of (get volume settings)) + 6)"),
+        Key::VolumeDown => Some("set volume output volume ((output volume of (get volume settings)) - 6)"),
+        Key::Mute => Some("set volume output muted (not (output muted of (get volume settings)))"),
+        _ => None,
+    }
+}
+
+// Carbon virtual key codes (HIToolbox/Events.h)
+fn key_code(key: Key) -> Option<u16> {
+    let code = match key {
+        Key::F(n) => return [122, 120, 99, 118, 96, 97, 98, 100, 101, 109, 103, 111].get(n as usize - 1).copied(),
+        Key::Enter => 36,
+        Key::Escape => 53,
+        Key::Tab => 48,
+        Key::Space => 49,
+        Key::Backspace => 51,
+        Key::Delete => 117,
+        Key::Home => 115,
+        Key::End => 119,
+        Key::PageUp => 116,
+        Key::PageDown => 121,
+        Key::Up => 126,
+        Key::Down => 125,
+        Key::Left => 123,
+        Key::Right => 124,
+        _ => return None,
+    };
+    Some(code)
+}
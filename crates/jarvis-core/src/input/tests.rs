@@ -0,0 +1,50 @@
+use crate::input::{parse_chord, run, InputEvent, InputStep, Key, Recorder};
+
+#[test]
+fn parses_chords() {
+    assert_eq!(parse_chord("ctrl+shift+t").unwrap(), [Key::Ctrl, Key::Shift, Key::Char('t')]);
+    assert_eq!(parse_chord("Alt + F4").unwrap(), [Key::Alt, Key::F(4)]);
+    assert_eq!(parse_chord("media_play_pause").unwrap(), [Key::PlayPause]);
+    assert_eq!(parse_chord("super").unwrap(), [Key::Super]);
+
+    assert!(parse_chord("ctrl+").is_err());
+    assert!(parse_chord("ctrl+hyper").unwrap_err().contains("hyper"));
+    assert!(parse_chord("a+b").is_err());
+    assert!(parse_chord("f25").is_err());
+}
+
+#[test]
+fn runs_steps_in_order() {
+    let steps: Vec<InputStep> = serde_json::from_str(r#"["ctrl+l", {"text": "weather"}, {"delay": 1}, "enter"]"#).unwrap();
+    let recorder = Recorder::default();
+
+    run(&steps, &recorder).unwrap();
+
+    assert_eq!(recorder.events(), [
+        InputEvent::Chord(vec![Key::Ctrl, Key::Char('l')]),
+        InputEvent::Text("weather".into()),
+        InputEvent::Chord(vec![Key::Enter]),
+    ]);
+}
+
+#[test]
+fn bad_step_sends_nothing() {
+    let steps = vec![InputStep::Chord("volume_up".into()), InputStep::Chord("ctrl+nope".into())];
+    let recorder = Recorder::default();
+
+    assert!(run(&steps, &recorder).is_err());
+    assert!(recorder.events().is_empty());
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn maps_linux_keycodes() {
+    use crate::input::linux::{char_keycode, keycode};
+
+    assert_eq!(char_keycode('q'), Some(16));
+    assert_eq!(char_keycode('M'), Some(50));
+    assert_eq!(char_keycode('0'), Some(11));
+    assert_eq!(keycode(Key::F(1)), Some(59));
+    assert_eq!(keycode(Key::F(12)), Some(88));
+    assert_eq!(keycode(Key::VolumeUp), Some(115));
+}
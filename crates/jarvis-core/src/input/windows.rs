@@ -0,0 +1,85 @@
+// Windows backend: keybd_event / SendKeys through PowerShell
+
+use super::{run_tool, InputBackend, Key};
+
+const KEYBD_EVENT: &str = "$k = Add-Type -MemberDefinition '[DllImport(\"user32.dll\")] public static extern void keybd_event(byte vk, byte scan, uint flags, UIntPtr extra);' -Name Keys -Namespace Jarvis -PassThru;";
+const KEYEVENTF_KEYUP: u32 = 2;
+
+pub struct PowerShell;
+
+impl InputBackend for PowerShell {
+    fn name(&self) -> &'static str {
+        "powershell"
+    }
+
+    fn chord(&self, keys: &[Key]) -> Result<(), String> {
+        let codes = keys.iter()
+            .map(|k| virtual_key(*k).ok_or_else(|| format!("Can't press {:?} on Windows", k)))
+            .collect::<Result<Vec<u8>, String>>()?;
+
+        let mut script = KEYBD_EVENT.to_string();
+        for code in &codes {
+            script.push_str(&format!(" $k::keybd_event({}, 0, 0, [UIntPtr]::Zero);", code));
+        }
+        for code in codes.iter().rev() {
+            script.push_str(&format!(" $k::keybd_event({}, 0, {}, [UIntPtr]::Zero);", code, KEYEVENTF_KEYUP));
+        }
+
+        run_tool("powershell", &["-NoProfile".into(), "-Command".into(), script])
+    }
+
+    fn type_text(&self, text: &str) -> Result<(), String> {
+        // SendKeys treats +^%~(){}[] as syntax, braces make them literal
+        let escaped: String = text.chars()
+            .map(|c| match c {
+                '+' | '^' | '%' | '~' | '(' | ')' | '{' | '}' | '[' | ']' => format!("{{{}}}", c),
+                '\'' => "''".to_string(),
+                c => c.to_string(),
+            })
+            .collect();
+
+        let script = format!(
+            "Add-Type -AssemblyName System.Windows.Forms; [System.Windows.Forms.SendKeys]::SendWait('{}')",
+            escaped
+        );
+        run_tool("powershell", &["-NoProfile".into(), "-Command".into(), script])
+    }
+}
+
+// https://learn.microsoft.com/windows/win32/inputdev/virtual-key-codes
+fn virtual_key(key: Key) -> Option<u8> {
+    let code = match key {
+        Key::Ctrl => 0x11,
+        Key::Shift => 0x10,
+        Key::Alt => 0x12,
+        Key::Super => 0x5B,
+        Key::Char(c) if c.is_ascii_alphanumeric() => c.to_ascii_uppercase() as u8,
+        Key::Char(_) => return None,
+        Key::F(n @ 1..=24) => 0x70 + n - 1,
+        Key::F(_) => return None,
+        Key::Enter => 0x0D,
+        Key::Escape => 0x1B,
+        Key::Tab => 0x09,
+        Key::Space => 0x20,
+        Key::Backspace => 0x08,
+        Key::Delete => 0x2E,
+        Key::Insert => 0x2D,
+        Key::Home => 0x24,
+        Key::End => 0x23,
+        Key::PageUp => 0x21,
+        Key::PageDown => 0x22,
+        Key::Up => 0x26,
+        Key::Down => 0x28,
+        Key::Left => 0x25,
+        Key::Right => 0x27,
+        Key::PrintScreen => 0x2C,
+        Key::PlayPause => 0xB3,
+        Key::NextTrack => 0xB0,
+        Key::PrevTrack => 0xB1,
+        Key::Stop => 0xB2,
+        Key::VolumeUp => 0xAF,
+        Key::VolumeDown => 0xAE,
+        Key::Mute => 0xAD,
+    };
+    Some(code)
+}
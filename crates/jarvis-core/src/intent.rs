@@ -78,12 +78,15 @@ pub async fn classify(text: &str) -> Option<(String, f64)> {
 }
 
 pub fn get_command_by_intent(commands: &'static Vec<JCommandsList>, intent_id: &str) -> Option<(&'static PathBuf, &'static JCommand)> {
-    match IRE_TYPE.get()? {
+    let found = match IRE_TYPE.get()? {
         IntentRecognitionEngine::IntentClassifier => {
             intentclassifier::get_command(commands, intent_id)
         }
         IntentRecognitionEngine::EmbeddingClassifier => {
             embeddingclassifier::get_command(commands, intent_id)
         }
-    }
+    };
+
+    // disabled after the classifier was trained
+    found.filter(|(_, cmd)| crate::commands::is_enabled(&cmd.id))
 }
\ No newline at end of file
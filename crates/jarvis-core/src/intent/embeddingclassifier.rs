@@ -1,5 +1,5 @@
 use parking_lot::Mutex;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::fs;
 
 // use fastembed::{TextEmbedding, InitOptions, EmbeddingModel};
@@ -8,6 +8,9 @@ use once_cell::sync::OnceCell;
 
 use crate::commands::JCommandsList;
 use crate::i18n::get_language;
+use crate::config::structs::{EmbeddingPooling, EmbeddingQuantization};
+use crate::db::structs::EmbeddingSettings;
+use crate::models::{self, ModelKind};
 use crate::{APP_CONFIG_DIR, APP_DIR, i18n};
 
 static CLASSIFIER: OnceCell<Mutex<EmbeddingClassifier>> = OnceCell::new();
@@ -20,6 +23,7 @@ struct IntentVector {
 struct EmbeddingClassifier {
     model: TextEmbedding,
     intents: Vec<IntentVector>,
+    query_prefix: String,
 }
 
 const CACHE_FILE: &str = "embedding_intents.json";
@@ -36,21 +40,12 @@ pub fn init(commands: &[JCommandsList]) -> Result<(), String> {
     //     InitOptions::new(EmbeddingModel::AllMiniLML6V2).with_show_download_progress(true),
     // ).map_err(|e| format!("Failed to load embedding model: {}", e))?;
 
-    let model_dir;
-    match i18n::get_language().as_str() {
-        "en" => {
-            // smaller model for English
-            info!("Loading all-MiniLM-L6-v2 ...");
-            model_dir = APP_DIR.join("resources").join("models").join("all-MiniLM-L6-v2");
-        },
-        _ => {
-            // bigger model for any other languages (multilingual)
-            info!("Loading paraphrase-multilingual-MiniLM-L12-v2-onnx-Q ...");
-            model_dir = APP_DIR.join("resources").join("models").join("paraphrase-multilingual-MiniLM-L12-v2-onnx-Q");
-        }
-    }
+    let settings = crate::DB.get()
+        .map(|db| db.read().embedding.clone())
+        .unwrap_or_default();
 
-    // info!("{}", model_dir.display());
+    let model_dir = resolve_model_dir(&settings)?;
+    info!("Loading embedding model from {} ...", model_dir.display());
 
     let user_model = UserDefinedEmbeddingModel {
         onnx_file: std::fs::read(model_dir.join("model.onnx"))
@@ -65,9 +60,17 @@ pub fn init(commands: &[JCommandsList]) -> Result<(), String> {
             tokenizer_config_file: std::fs::read(model_dir.join("tokenizer_config.json"))
                 .map_err(|e| format!("Failed to read tokenizer_config.json: {}", e))?,
         },
-        pooling: Some(Pooling::Mean),
-        quantization: QuantizationMode::None,
-        output_key: Some(OutputKey::ByName("last_hidden_state")),
+        pooling: Some(match settings.pooling {
+            EmbeddingPooling::Mean => Pooling::Mean,
+            EmbeddingPooling::Cls => Pooling::Cls,
+        }),
+        quantization: match settings.quantization {
+            EmbeddingQuantization::None => QuantizationMode::None,
+            EmbeddingQuantization::Static => QuantizationMode::Static,
+            EmbeddingQuantization::Dynamic => QuantizationMode::Dynamic,
+        },
+        // fastembed wants a static name, init runs once
+        output_key: Some(OutputKey::ByName(Box::leak(settings.output_key.clone().into_boxed_str()))),
     };
 
     let mut model = TextEmbedding::try_new_from_user_defined(user_model, Default::default())
@@ -75,7 +78,12 @@ pub fn init(commands: &[JCommandsList]) -> Result<(), String> {
 
     info!("Embedding model loaded");
 
-    let current_hash = crate::commands::commands_hash(commands);
+    // vectors from another model (or other prefixes) are meaningless, key the cache by both
+    let current_hash = format!(
+        "{}:{}",
+        crate::commands::commands_hash(commands),
+        model_fingerprint(&model_dir, &settings)
+    );
     let config_dir = APP_CONFIG_DIR.get().ok_or("Config dir not set")?;
     let hash_path = config_dir.join(HASH_FILE);
     let cache_path = config_dir.join(CACHE_FILE);
@@ -90,7 +98,7 @@ pub fn init(commands: &[JCommandsList]) -> Result<(), String> {
 
     let intents = if should_retrain {
         info!("Building intent vectors from commands...");
-        let intents = build_intent_vectors(&mut model, commands)?;
+        let intents = build_intent_vectors(&mut model, commands, &settings.passage_prefix)?;
         
         // cache to disk
         if let Ok(json) = serde_json::to_string(&intents_to_cache(&intents)) {
@@ -107,27 +115,73 @@ pub fn init(commands: &[JCommandsList]) -> Result<(), String> {
 
     info!("Embedding classifier ready with {} intents", intents.len());
 
-    CLASSIFIER.set(Mutex::new(EmbeddingClassifier { model, intents }))
+    CLASSIFIER.set(Mutex::new(EmbeddingClassifier { model, intents, query_prefix: settings.query_prefix }))
         .map_err(|_| "Classifier already set")?;
 
     Ok(())
 }
 
+// Configured model (registry id or folder), or the registry pick for the current language
+fn resolve_model_dir(settings: &EmbeddingSettings) -> Result<PathBuf, String> {
+    if settings.model.is_empty() {
+        let language = i18n::get_language();
+        let entry = models::resolve(ModelKind::Embedding, &language)
+            .ok_or_else(|| format!("No embedding model installed for language '{}'", language))?;
+        models::verify(entry)?;
+        return Ok(entry.dir());
+    }
+
+    if let Some(entry) = models::find(&settings.model) {
+        models::verify(entry)?;
+        return Ok(entry.dir());
+    }
+
+    let dir = APP_DIR.join(&settings.model); // absolute paths replace APP_DIR
+    if !dir.is_dir() {
+        return Err(format!("Embedding model '{}' not found", settings.model));
+    }
+    Ok(dir)
+}
+
+// Identity of the loaded model + everything that changes its vectors
+fn model_fingerprint(model_dir: &Path, settings: &EmbeddingSettings) -> String {
+    use sha2::{Sha256, Digest};
+
+    let onnx = fs::metadata(model_dir.join("model.onnx")).ok();
+    let modified = onnx.as_ref()
+        .and_then(|m| m.modified().ok())
+        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+
+    let mut hasher = Sha256::new();
+    hasher.update(model_dir.to_string_lossy().as_bytes());
+    hasher.update(onnx.map(|m| m.len()).unwrap_or_default().to_le_bytes());
+    hasher.update(modified.to_le_bytes());
+    hasher.update(format!(
+        "|{:?}|{:?}|{}|{}|{}",
+        settings.pooling, settings.quantization, settings.output_key, settings.query_prefix, settings.passage_prefix
+    ).as_bytes());
+
+    format!("{:x}", hasher.finalize())
+}
+
 fn build_intent_vectors(
     model: &mut TextEmbedding,
     commands: &[JCommandsList],
+    prefix: &str,
 ) -> Result<Vec<IntentVector>, String> {
     let lang = i18n::get_language();
     let mut intents = Vec::new();
 
     for cmd_list in commands {
-        for cmd in &cmd_list.commands {
+        for cmd in cmd_list.commands.iter().filter(|c| crate::commands::is_enabled(&c.id)) {
             let phrases = cmd.get_phrases(&lang);
             if phrases.is_empty() {
                 continue;
             }
 
-            let texts: Vec<&str> = phrases.iter().map(|s| s.as_str()).collect();
+            let texts: Vec<String> = phrases.iter().map(|s| format!("{}{}", prefix, s)).collect();
             
             let embeddings = model.embed(texts, None)
                 .map_err(|e| format!("Embedding failed for '{}': {}", cmd.id, e))?;
@@ -168,7 +222,8 @@ fn build_intent_vectors(
 pub fn classify(text: &str) -> Result<(String, f64), String> {
     let mut classifier = CLASSIFIER.get().ok_or("Classifier not initialized")?.lock();
     
-    let embeddings = classifier.model.embed(vec![text], None)
+    let query = format!("{}{}", classifier.query_prefix, text);
+    let embeddings = classifier.model.embed(vec![query], None)
         .map_err(|e| format!("Failed to embed query: {}", e))?;
     
     let mut query_vec = embeddings.into_iter().next()
@@ -93,7 +93,7 @@ async fn train_classifier(
     let mut total_examples = 0;
 
     for assistant_cmd in commands {
-        for cmd in &assistant_cmd.commands {
+        for cmd in assistant_cmd.commands.iter().filter(|c| commands::is_enabled(&c.id)) {
             // use language-specific phrases
             let phrases = cmd.get_phrases(&lang);
             
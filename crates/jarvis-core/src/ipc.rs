@@ -1,5 +1,6 @@
 mod events;
 mod server;
+pub mod telemetry;
 
 pub use events::{IpcAction, IpcEvent};
 pub use server::{init, send, set_action_handler, start_server, has_clients, IPC_ADDR, IPC_PORT};
\ No newline at end of file
@@ -12,10 +12,40 @@ pub enum IpcEvent {
     
     // Speech recognized
     SpeechRecognized { text: String },
+
+    // Partial hypothesis while the user is still talking
+    PartialTranscript { text: String },
+    
+    // Command was executed, exit_code / output only for waited cli/ahk processes
+    CommandExecuted {
+        id: String,
+        success: bool,
+        exit_code: Option<i32>,
+        output: Option<String>,
+    },
     
-    // Command was executed
-    CommandExecuted { id: String, success: bool },
+    // Command with confirm = true is waiting for "yes" / "no"
+    ConfirmationRequested { id: String, prompt: String },
+
+    // Confirmation answered, reply is None on timeout
+    ConfirmationResolved {
+        id: String,
+        confirmed: bool,
+        reply: Option<String>,
+    },
+
+    // One step of a "sequence" command finished (or was skipped by its condition)
+    SequenceStep {
+        id: String,
+        index: usize,
+        command: String,
+        success: bool,
+        skipped: bool,
+    },
     
+    // Command switched on / off
+    CommandEnabledChanged { id: String, enabled: bool },
+
     // Returned to idle state
     Idle,
     
@@ -33,6 +63,16 @@ pub enum IpcEvent {
 
     // request GUI to reveal/focus window
     RevealWindow,
+
+    // live mic level / VAD telemetry (only sent to clients that opted in)
+    AudioLevel {
+        rms: f32,
+        peak: f32,
+        is_voice: bool,
+        vad_confidence: f32,
+        vad_state: String,
+        wake_score: Option<f32>,
+    },
 }
 
 // Actions sent from GUI to jarvis-app
@@ -53,4 +93,10 @@ pub enum IpcAction {
 
     // Execute text command
     TextCommand { text: String },
+
+    // Opt this client in/out of audio level telemetry
+    SetTelemetry { enabled: bool },
+
+    // Switch a command on / off (persisted in settings)
+    SetCommandEnabled { id: String, enabled: bool },
 }
\ No newline at end of file
@@ -9,9 +9,9 @@ use tokio::sync::broadcast;
 use tokio_tungstenite::{accept_async, tungstenite::Message};
 
 use super::events::{IpcAction, IpcEvent};
+use super::telemetry;
 
-pub const IPC_PORT: u16 = 9712;
-pub const IPC_ADDR: &str = "127.0.0.1";
+pub use crate::config::{IPC_ADDR, IPC_PORT};
 
 static BROADCAST_TX: OnceCell<broadcast::Sender<IpcEvent>> = OnceCell::new();
 static ACTION_HANDLER: OnceCell<Arc<RwLock<Option<Box<dyn Fn(IpcAction) + Send + Sync>>>>> = OnceCell::new();
@@ -33,9 +33,12 @@ pub fn init() -> broadcast::Sender<IpcEvent> {
 // Send event to all connected clients
 pub fn send(event: IpcEvent) {
     if let Some(tx) = BROADCAST_TX.get() {
+        // telemetry is too chatty to log
+        let quiet = matches!(event, IpcEvent::AudioLevel { .. });
+
         match tx.send(event.clone()) {
             Ok(n) => {
-                if n > 0 {
+                if n > 0 && !quiet {
                     debug!("IPC: Sent {:?} to {} client(s)", event, n);
                 }
             }
@@ -123,12 +126,19 @@ async fn handle_client(
 
     let (mut ws_tx, mut ws_rx) = ws_stream.split();
 
+    // per-client opt-in
+    let mut telemetry_enabled = false;
+
     loop {
         tokio::select! {
             // forward events to client
             event_result = event_rx.recv() => {
                 match event_result {
                     Ok(event) => {
+                        if !telemetry_enabled && matches!(event, IpcEvent::AudioLevel { .. }) {
+                            continue;
+                        }
+
                         let json = match serde_json::to_string(&event) {
                             Ok(j) => j,
                             Err(e) => {
@@ -157,6 +167,13 @@ async fn handle_client(
                 match msg_result {
                     Some(Ok(Message::Text(text))) => {
                         match serde_json::from_str::<IpcAction>(&text) {
+                            Ok(IpcAction::SetTelemetry { enabled }) => {
+                                if enabled != telemetry_enabled {
+                                    telemetry_enabled = enabled;
+                                    if enabled { telemetry::subscribe() } else { telemetry::unsubscribe() }
+                                    info!("IPC: Client {} telemetry {}", peer_addr, if enabled { "on" } else { "off" });
+                                }
+                            }
                             Ok(action) => handle_action(action),
                             Err(e) => {
                                 warn!("IPC: Invalid action from {}: {} ({})", peer_addr, text, e);
@@ -186,6 +203,10 @@ async fn handle_client(
         }
     }
 
+    if telemetry_enabled {
+        telemetry::unsubscribe();
+    }
+
     info!("IPC: Client disconnected: {}", peer_addr);
 }
 
@@ -0,0 +1,103 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+use once_cell::sync::OnceCell;
+use parking_lot::Mutex;
+
+use super::events::IpcEvent;
+use super::server::send;
+
+// clients that opted in to telemetry (nothing is computed without them)
+static SUBSCRIBERS: AtomicUsize = AtomicUsize::new(0);
+static METER: OnceCell<Mutex<Meter>> = OnceCell::new();
+
+// accumulates frames between two telemetry events
+struct Meter {
+    interval: Duration,
+    last_sent: Instant,
+
+    sum_squares: f64,
+    samples: usize,
+    peak: i32,
+    voice: bool,
+    vad_confidence: f32,
+}
+
+pub fn init(rate_hz: u32) {
+    if METER.get().is_some() {
+        return;
+    }
+
+    if rate_hz == 0 {
+        info!("IPC: audio telemetry disabled");
+        return;
+    }
+
+    METER.set(Mutex::new(Meter {
+        interval: Duration::from_secs_f32(1.0 / rate_hz as f32),
+        last_sent: Instant::now(),
+        sum_squares: 0.0,
+        samples: 0,
+        peak: 0,
+        voice: false,
+        vad_confidence: 0.0,
+    })).ok();
+
+    info!("IPC: audio telemetry at {} Hz (for subscribed clients)", rate_hz);
+}
+
+pub(super) fn subscribe() {
+    SUBSCRIBERS.fetch_add(1, Ordering::SeqCst);
+}
+
+pub(super) fn unsubscribe() {
+    let _ = SUBSCRIBERS.fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1));
+}
+
+pub fn is_active() -> bool {
+    METER.get().is_some() && SUBSCRIBERS.load(Ordering::SeqCst) > 0
+}
+
+// Feed one raw mic frame plus the VAD decision for it.
+// `vad_state` is the app's listening state (e.g. "waiting_for_voice"),
+// `wake_score` the best wake-word score since the last call, if any.
+pub fn push(samples: &[i16], is_voice: bool, vad_confidence: f32, vad_state: &str, wake_score: Option<f32>) {
+    if !is_active() {
+        return;
+    }
+
+    let mut meter = METER.get().unwrap().lock();
+
+    for &s in samples {
+        meter.sum_squares += (s as f64).powi(2);
+        meter.peak = meter.peak.max((s as i32).abs());
+    }
+    meter.samples += samples.len();
+    meter.voice |= is_voice;
+    meter.vad_confidence = meter.vad_confidence.max(vad_confidence);
+
+    if meter.last_sent.elapsed() < meter.interval || meter.samples == 0 {
+        return;
+    }
+
+    // levels normalized to 0.0 - 1.0 of full scale
+    let full = i16::MAX as f64;
+    let rms = ((meter.sum_squares / meter.samples as f64).sqrt() / full) as f32;
+    let peak = (meter.peak as f64 / full).min(1.0) as f32;
+
+    send(IpcEvent::AudioLevel {
+        rms,
+        peak,
+        is_voice: meter.voice,
+        vad_confidence: meter.vad_confidence,
+        vad_state: vad_state.to_string(),
+        wake_score,
+    });
+
+    meter.last_sent = Instant::now();
+    meter.sum_squares = 0.0;
+    meter.samples = 0;
+    meter.peak = 0;
+    meter.voice = false;
+    meter.vad_confidence = 0.0;
+}
@@ -29,6 +29,7 @@ pub mod intent;
 #[cfg(feature = "jarvis_app")]
 pub mod slots;
 
+pub mod models;
 pub mod vosk_models;
 pub mod gliner_models;
 
@@ -38,10 +39,26 @@ pub mod audio_processing;
 #[cfg(feature = "jarvis_app")]
 pub mod ipc;
 
+#[cfg(feature = "jarvis_app")]
+pub mod ducking;
+
 pub mod voices;
 
 pub mod audio_buffer;
 
+pub mod diagnostics;
+
+pub mod notify;
+
+pub mod history;
+
+pub mod input;
+
+pub mod dbus;
+
+#[cfg(feature = "http")]
+pub mod http_client;
+
 #[cfg(feature = "lua")]
 pub mod lua;
 
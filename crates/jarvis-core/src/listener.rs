@@ -5,10 +5,10 @@ mod rustpotter;
 mod vosk;
 
 use once_cell::sync::OnceCell;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
 
 use crate::config::structs::WakeWordEngine;
-use crate::{config, stt};
+use crate::{audio_processing, config, ipc, stt};
 
 use crate::DB;
 
@@ -18,6 +18,10 @@ static WAKE_WORD_ENGINE: OnceCell<WakeWordEngine> = OnceCell::new();
 // track listening state
 static LISTENING: AtomicBool = AtomicBool::new(false);
 
+// best wake-word score (0.0 - 1.0) since last taken, f32 bits, for telemetry
+const NO_SCORE: u32 = u32::MAX;
+static WAKE_SCORE: AtomicU32 = AtomicU32::new(NO_SCORE);
+
 pub fn init() -> Result<(), ()> {
     if WAKE_WORD_ENGINE.get().is_some() {
         return Ok(());
@@ -54,12 +58,40 @@ pub fn init() -> Result<(), ()> {
 }
 
 pub fn data_callback(frame_buffer: &[i16]) -> Option<i32> {
-    match WAKE_WORD_ENGINE.get().unwrap() {
+    let result = match WAKE_WORD_ENGINE.get().unwrap() {
         WakeWordEngine::Porcupine => {
             // porcupine::data_callback(frame_buffer)
             unimplemented!("f*ck picovoice");
         },
         WakeWordEngine::Rustpotter => rustpotter::data_callback(frame_buffer),
         WakeWordEngine::Vosk => vosk::data_callback(frame_buffer),
+    };
+
+    // engines are still fed to keep their state continuous,
+    // but detections caused by our own voice are dropped
+    if result.is_some() && audio_processing::echo_cancellation::wake_gated() {
+        info!("Wake-word ignored: assistant audio is playing.");
+        return None;
+    }
+
+    result
+}
+
+// engines report every scored candidate here (normalized to 0.0 - 1.0)
+pub(crate) fn report_score(score: f32) {
+    if !ipc::telemetry::is_active() {
+        return;
+    }
+
+    let current = WAKE_SCORE.load(Ordering::SeqCst);
+    if current == NO_SCORE || score > f32::from_bits(current) {
+        WAKE_SCORE.store(score.to_bits(), Ordering::SeqCst);
+    }
+}
+
+pub fn take_wake_score() -> Option<f32> {
+    match WAKE_SCORE.swap(NO_SCORE, Ordering::SeqCst) {
+        NO_SCORE => None,
+        bits => Some(f32::from_bits(bits)),
     }
 }
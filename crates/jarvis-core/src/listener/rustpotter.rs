@@ -8,6 +8,7 @@ use rustpotter::{
 };
 
 use crate::config;
+use crate::diagnostics::{self, SampleKind};
 use crate::DB;
 
 // store rustpotter instance
@@ -61,14 +62,32 @@ pub fn data_callback(frame_buffer: &[i16]) -> Option<i32> {
     // info!("Ruspotter data callback");
 
     if let Some(detection) = detection {
+        super::report_score(detection.score);
+
         if detection.score > config::RUSPOTTER_MIN_SCORE {
             info!("Rustpotter detection info:\n{:?}", detection);
+            capture_diagnostics(SampleKind::Detection, detection.score);
 
             return Some(0);
         } else {
-            info!("Rustpotter detection info:\n{:?}", detection)
+            info!("Rustpotter detection info:\n{:?}", detection);
+
+            if detection.score >= config::RUSPOTTER_NEAR_MISS_SCORE {
+                capture_diagnostics(SampleKind::NearMiss, detection.score);
+            }
         }
     }
 
     None
 }
+
+fn capture_diagnostics(kind: SampleKind, score: f32) {
+    diagnostics::capture(
+        kind,
+        "Rustpotter",
+        score as f64,
+        config::RUSPOTTER_MIN_SCORE as f64,
+        config::RUSPOTTER_NEAR_MISS_SCORE as f64,
+        None,
+    );
+}
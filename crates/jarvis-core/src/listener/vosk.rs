@@ -1,4 +1,5 @@
 use crate::{config, stt, i18n};
+use crate::diagnostics::{self, SampleKind};
 
 pub fn init() -> Result<(), ()> {
     Ok(()) // nothing to init for Vosk
@@ -20,6 +21,8 @@ pub fn data_callback(frame_buffer: &[i16]) -> Option<i32> {
         let wake_phrases = config::get_wake_phrases(&lang);
 
         // verify with seqdiff ratio
+        let mut best_similarity: f64 = 0.0;
+
         for word in recognized.split_whitespace() {
             if word == "[unk]" {
                 continue;
@@ -32,11 +35,21 @@ pub fn data_callback(frame_buffer: &[i16]) -> Option<i32> {
                 let similarity = seqdiff::ratio(&wake_chars, &word_chars);
                 
                 if similarity >= config::VOSK_MIN_RATIO {
+                    super::report_score((similarity / 100.0) as f32);
                     info!("Wake word match: '{}' ~ '{}' ({:.1}%)", word, wake_phrase, similarity);
+                    capture_diagnostics(SampleKind::Detection, similarity, &recognized);
                     return Some(0);
                 }
+
+                best_similarity = best_similarity.max(similarity);
             }
         }
+
+        super::report_score((best_similarity / 100.0) as f32);
+
+        if best_similarity >= config::VOSK_NEAR_MISS_RATIO {
+            capture_diagnostics(SampleKind::NearMiss, best_similarity, &recognized);
+        }
         
         // info!("Similarity: {:.1}% ('{}' vs '{}')", similarity, recognized, config::VOSK_FETCH_PHRASE);
     }
@@ -44,6 +57,17 @@ pub fn data_callback(frame_buffer: &[i16]) -> Option<i32> {
     None
 }
 
+fn capture_diagnostics(kind: SampleKind, similarity: f64, recognized: &str) {
+    diagnostics::capture(
+        kind,
+        "Vosk",
+        similarity,
+        config::VOSK_MIN_RATIO,
+        config::VOSK_NEAR_MISS_RATIO,
+        Some(recognized.to_string()),
+    );
+}
+
 // @TODO. Make it better somehow (more accurate or with higher sensitivity).
 // pub fn data_callback(frame_buffer: &[i16]) -> Option<i32> {
 //     // recognize & convert to sequence
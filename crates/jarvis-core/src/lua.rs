@@ -7,7 +7,7 @@ mod structs;
 pub use structs::*;
 
 pub use engine::LuaEngine;
-pub use sandbox::SandboxLevel;
+pub use sandbox::{Capabilities, SandboxLevel};
 pub use error::LuaError;
 
 use std::path::PathBuf;
@@ -20,9 +20,9 @@ mod tests;
 pub fn execute(
     script_path: &PathBuf,
     context: CommandContext,
-    sandbox: SandboxLevel,
+    sandbox: impl Into<Capabilities>,
     timeout: Duration,
 ) -> Result<CommandResult, LuaError> {
-    let engine = LuaEngine::new(sandbox)?;
+    let engine = LuaEngine::new(sandbox.into())?;
     engine.execute(script_path, context, timeout)
 }
\ No newline at end of file
@@ -4,4 +4,6 @@ pub mod context;
 pub mod http;
 pub mod fs;
 pub mod state;
-pub mod system;
\ No newline at end of file
+pub mod system;
+pub mod input;
+pub mod dbus;
\ No newline at end of file
@@ -0,0 +1,91 @@
+// D-Bus Lua API: property reads, method calls, media players
+
+use mlua::{Lua, LuaSerdeExt, Table, Value};
+
+use crate::dbus::{self, mpris, Bus};
+use crate::lua::sandbox::Capabilities;
+
+pub fn register(lua: &Lua, jarvis: &Table, sandbox: Capabilities) -> mlua::Result<()> {
+    let dbus_table = lua.create_table()?;
+
+    // jarvis.dbus.get(dest, path, "interface.Property", bus?) -> value or nil
+    let get_fn = lua.create_function(|lua, (dest, path, property, bus): (String, String, String, Option<String>)| {
+        let bus = Bus::parse(&bus.unwrap_or_default());
+        match dbus::get_property(&bus, &dest, &path, &property) {
+            Ok(value) => lua.to_value(&value),
+            Err(e) => {
+                log::warn!("[Lua] D-Bus get failed: {}", e);
+                Ok(Value::Nil)
+            }
+        }
+    })?;
+    dbus_table.set("get", get_fn)?;
+
+    // jarvis.dbus.call(dest, path, "interface.Method", {args}?, bus?, signature?) -> reply values or nil
+    // without a signature the types are guessed: integers "i", strings "s", tables "as" / "av" / "a{sv}"
+    // only in full sandbox, a method call can do anything the user can
+    if sandbox.allows_exec() {
+        let call_fn = lua.create_function(|lua, (dest, path, method, args, bus, signature): (String, String, String, Option<Value>, Option<String>, Option<String>)| {
+            let args: Vec<serde_json::Value> = match args {
+                Some(args) => lua.from_value(args)?,
+                None => Vec::new(),
+            };
+
+            let bus = Bus::parse(&bus.unwrap_or_default());
+            match dbus::call(&bus, &dest, &path, &method, &signature.unwrap_or_default(), &args) {
+                Ok(value) => lua.to_value(&value),
+                Err(e) => {
+                    log::warn!("[Lua] D-Bus call failed: {}", e);
+                    Ok(Value::Nil)
+                }
+            }
+        })?;
+        dbus_table.set("call", call_fn)?;
+    }
+
+    // jarvis.dbus.mpris.next() etc.
+    let mpris_table = lua.create_table()?;
+
+    for (name, action) in [
+        ("play_pause", mpris::Action::PlayPause),
+        ("play", mpris::Action::Play),
+        ("pause", mpris::Action::Pause),
+        ("stop", mpris::Action::Stop),
+        ("next", mpris::Action::Next),
+        ("previous", mpris::Action::Previous),
+    ] {
+        let action_fn = lua.create_function(move |_, ()| {
+            match mpris::control(&Bus::Session, action) {
+                Ok(()) => Ok(true),
+                Err(e) => {
+                    log::warn!("[Lua] Media player {} failed: {}", name, e);
+                    Ok(false)
+                }
+            }
+        })?;
+        mpris_table.set(name, action_fn)?;
+    }
+
+    // jarvis.dbus.mpris.now_playing() -> {title, artist, player} or nil
+    let now_playing_fn = lua.create_function(|lua, ()| {
+        match mpris::now_playing(&Bus::Session) {
+            Ok(np) => {
+                let table = lua.create_table()?;
+                table.set("title", np.title)?;
+                table.set("artist", np.artist)?;
+                table.set("player", np.player)?;
+                Ok(Value::Table(table))
+            }
+            Err(e) => {
+                log::debug!("[Lua] Nothing playing: {}", e);
+                Ok(Value::Nil)
+            }
+        }
+    })?;
+    mpris_table.set("now_playing", now_playing_fn)?;
+
+    dbus_table.set("mpris", mpris_table)?;
+    jarvis.set("dbus", dbus_table)?;
+
+    Ok(())
+}
@@ -4,13 +4,13 @@ use mlua::{Lua, Table};
 use std::path::{Path, PathBuf};
 use std::fs;
 
-use crate::lua::sandbox::SandboxLevel;
+use crate::lua::sandbox::Capabilities;
 
 pub fn register(
     lua: &Lua,
     jarvis: &Table,
     command_path: &PathBuf,
-    sandbox: SandboxLevel,
+    sandbox: Capabilities,
 ) -> mlua::Result<()> {
     let fs_table = lua.create_table()?;
     
@@ -183,7 +183,7 @@ pub fn register(
 }
 
 // Resolve path relative to command folder, with sandbox checks
-fn resolve_path(command_path: &PathBuf, path: &str, sandbox: SandboxLevel) -> mlua::Result<PathBuf> {
+fn resolve_path(command_path: &PathBuf, path: &str, sandbox: Capabilities) -> mlua::Result<PathBuf> {
     let path = Path::new(path);
     
     // if absolute path, check sandbox allows it
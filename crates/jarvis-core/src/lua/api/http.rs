@@ -2,7 +2,6 @@
 
 use mlua::{Lua, Table, Value};
 use std::collections::HashMap;
-use std::time::Duration;
 
 pub fn register(lua: &Lua, jarvis: &Table) -> mlua::Result<()> {
     let http = lua.create_table()?;
@@ -44,10 +43,7 @@ pub fn register(lua: &Lua, jarvis: &Table) -> mlua::Result<()> {
     
     // jarvis.http.json(url) - GET + parse JSON
     let json_fn = lua.create_function(|lua, url: String| {
-        let client = reqwest::blocking::Client::builder()
-            .timeout(Duration::from_secs(30))
-            .build()
-            .map_err(|e| mlua::Error::runtime(e.to_string()))?;
+        let client = crate::http_client::shared().map_err(mlua::Error::runtime)?;
         
         let response = client.get(&url)
             .send()
@@ -93,10 +89,7 @@ fn http_request_with_headers(
     body: Option<String>,
     headers: HashMap<String, String>,
 ) -> mlua::Result<Table> {
-    let client = reqwest::blocking::Client::builder()
-        .timeout(Duration::from_secs(30))
-        .build()
-        .map_err(|e| mlua::Error::runtime(e.to_string()))?;
+    let client = crate::http_client::shared().map_err(mlua::Error::runtime)?;
     
     let mut request = match method {
         "POST" => client.post(url),
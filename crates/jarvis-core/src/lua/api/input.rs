@@ -0,0 +1,42 @@
+// Input Lua API: key chords, media keys, typing
+
+use mlua::{Lua, LuaSerdeExt, Table, Value};
+
+use crate::input::{self, InputStep};
+
+pub fn register(lua: &Lua, jarvis: &Table) -> mlua::Result<()> {
+    let input_table = lua.create_table()?;
+
+    // jarvis.input.press("ctrl+shift+t") / jarvis.input.press("volume_up")
+    let press_fn = lua.create_function(|_, chord: String| {
+        Ok(send(&[InputStep::Chord(chord)]))
+    })?;
+    input_table.set("press", press_fn)?;
+
+    // jarvis.input.type("hello")
+    let type_fn = lua.create_function(|_, text: String| {
+        Ok(send(&[InputStep::Text { text }]))
+    })?;
+    input_table.set("type", type_fn)?;
+
+    // jarvis.input.send({"ctrl+l", {text = "weather"}, {delay = 100}, "enter"})
+    let send_fn = lua.create_function(|lua, steps: Value| {
+        let steps: Vec<InputStep> = lua.from_value(steps)?;
+        Ok(send(&steps))
+    })?;
+    input_table.set("send", send_fn)?;
+
+    jarvis.set("input", input_table)?;
+
+    Ok(())
+}
+
+fn send(steps: &[InputStep]) -> bool {
+    match input::run(steps, input::backend()) {
+        Ok(()) => true,
+        Err(e) => {
+            log::warn!("[Lua] Input failed: {}", e);
+            false
+        }
+    }
+}
@@ -3,9 +3,9 @@
 use mlua::{Lua, Table};
 use std::process::Command;
 
-use crate::lua::sandbox::SandboxLevel;
+use crate::lua::sandbox::Capabilities;
 
-pub fn register(lua: &Lua, jarvis: &Table, sandbox: SandboxLevel) -> mlua::Result<()> {
+pub fn register(lua: &Lua, jarvis: &Table, sandbox: Capabilities) -> mlua::Result<()> {
     let system = lua.create_table()?;
     
     // jarvis.system.open(url_or_path) - always available
@@ -73,45 +73,7 @@ pub fn register(lua: &Lua, jarvis: &Table, sandbox: SandboxLevel) -> mlua::Resul
     // jarvis.system.notify(title, message) - always available
     let notify_fn = lua.create_function(|_, (title, message): (String, String)| {
         log::info!("[Lua] NOTIFY: {} - {}", title, message);
-        
-        // platform-specific notification
-        #[cfg(target_os = "windows")]
-        {
-            use winrt_notification::{Toast, Duration as ToastDuration};
-            
-            if let Err(e) = Toast::new(Toast::POWERSHELL_APP_ID)
-                .title(&title)
-                .text1(&message)
-                .duration(ToastDuration::Short)
-                .show()
-            {
-                log::warn!("[Lua] Failed to show toast notification: {}", e);
-                // fallback to msg.exe
-                let _ = Command::new("msg")
-                    .args(["*", "/time:10", &format!("{}: {}", title, message)])
-                    .spawn();
-            }
-        }
-        
-        #[cfg(target_os = "linux")]
-        {
-            let _ = Command::new("notify-send")
-                .args([&title, &message])
-                .spawn();
-        }
-        
-        #[cfg(target_os = "macos")]
-        {
-            let script = format!(
-                r#"display notification "{}" with title "{}""#,
-                message.replace("\"", "\\\""),
-                title.replace("\"", "\\\"")
-            );
-            let _ = Command::new("osascript")
-                .args(["-e", &script])
-                .spawn();
-        }
-        
+        crate::notify::show(&title, &message);
         Ok(true)
     })?;
     system.set("notify", notify_fn)?;
@@ -5,32 +5,28 @@ use std::fs;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, mpsc};
 
-use super::sandbox::SandboxLevel;
+use super::sandbox::Capabilities;
 use super::error::LuaError;
 use super::{CommandContext, CommandResult};
 use super::api;
 
 pub struct LuaEngine {
     lua: Lua,
-    sandbox: SandboxLevel,
+    sandbox: Capabilities,
 }
 
 
 
 impl LuaEngine {
-    pub fn new(sandbox: SandboxLevel) -> Result<Self, LuaError> {
-        // select which standard libraries to load based on sandbox access level
-        let std_libs = match sandbox {
-            SandboxLevel::Minimal => {
-                StdLib::TABLE | StdLib::STRING | StdLib::MATH
-            }
-            SandboxLevel::Standard => {
-                StdLib::TABLE | StdLib::STRING | StdLib::MATH | StdLib::UTF8
-            }
-            SandboxLevel::Full => {
-                StdLib::TABLE | StdLib::STRING | StdLib::MATH | StdLib::UTF8 | StdLib::OS
-            }
-        };
+    pub fn new(sandbox: Capabilities) -> Result<Self, LuaError> {
+        // select which standard libraries to load based on granted capabilities
+        let mut std_libs = StdLib::TABLE | StdLib::STRING | StdLib::MATH;
+        if sandbox.basic {
+            std_libs |= StdLib::UTF8;
+        }
+        if sandbox.exec {
+            std_libs |= StdLib::OS;
+        }
         
         let lua = Lua::new_with(std_libs, mlua::LuaOptions::default())
             .map_err(|e| LuaError::InitError(e.to_string()))?;
@@ -45,14 +41,14 @@ impl LuaEngine {
             let _ = globals.set("load", Value::Nil);
             let _ = globals.set("loadstring", Value::Nil);
             
-            // remove io unless full sandbox
-            if !matches!(sandbox, SandboxLevel::Full) {
+            // remove io unless exec is granted
+            if !sandbox.exec {
                 let _ = globals.set("io", Value::Nil);
             }
             
-            // remove os.execute, os.exit, os.setlocale even in full mode
+            // remove os.execute, os.exit, os.setlocale even with exec
             // for SECURITY REASONS!!!
-            if matches!(sandbox, SandboxLevel::Full) {
+            if sandbox.exec {
                 if let Ok(os) = globals.get::<mlua::Table>("os") {
                     let _ = os.set("execute", Value::Nil);
                     let _ = os.set("exit", Value::Nil);
@@ -92,6 +88,14 @@ impl LuaEngine {
             api::fs::register(&self.lua, &jarvis, &context.command_path, self.sandbox)?;
         }
         
+        if self.sandbox.allows_input() {
+            api::input::register(&self.lua, &jarvis)?;
+        }
+        
+        if self.sandbox.allows_dbus() {
+            api::dbus::register(&self.lua, &jarvis, self.sandbox)?;
+        }
+        
         api::system::register(&self.lua, &jarvis, self.sandbox)?;
         
         globals.set("jarvis", jarvis)
@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
 
-// Sandbox level controlling what APIs are available
+use crate::commands::Capability;
+
+// Legacy per-command sandbox level, packs with a [pack] manifest declare capabilities instead
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum SandboxLevel {
     // Minimal: only core APIs (log, speak, audio, context)
@@ -9,7 +11,7 @@ pub enum SandboxLevel {
     // Standard: + http, state, fs (command folder only)
     Standard,
 
-    // Full: + system.exec, expanded fs access
+    // Full: + system.exec, input, expanded fs access
     Full,
 }
 
@@ -21,45 +23,89 @@ impl SandboxLevel {
             _ => SandboxLevel::Standard,
         }
     }
-    
+}
+
+impl Default for SandboxLevel {
+    fn default() -> Self {
+        SandboxLevel::Standard
+    }
+}
+
+// What a script may do. Basic APIs come with any pack, the rest only when declared.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Capabilities {
+    pub basic: bool,     // state, fs reads in the command folder, D-Bus reads, utf8
+    pub network: bool,   // http
+    pub fs_write: bool,  // fs.write / fs.append
+    pub exec: bool,      // system.exec, dbus.call, input, os / io libs, paths outside the command folder
+    pub clipboard: bool, // clipboard.set
+}
+
+impl Capabilities {
+    // exactly what a pack declared
+    pub fn declared(capabilities: &[Capability]) -> Self {
+        Self {
+            basic: true,
+            network: capabilities.contains(&Capability::Network),
+            fs_write: capabilities.contains(&Capability::FsWrite),
+            exec: capabilities.contains(&Capability::Exec),
+            clipboard: capabilities.contains(&Capability::Clipboard),
+        }
+    }
+
     // Can use HTTP API
     pub fn allows_http(&self) -> bool {
-        matches!(self, SandboxLevel::Standard | SandboxLevel::Full)
+        self.network
     }
     
     // Can use persistent state API
     pub fn allows_state(&self) -> bool {
-        matches!(self, SandboxLevel::Standard | SandboxLevel::Full)
+        self.basic
     }
     
     // Can use file system API
     pub fn allows_fs(&self) -> bool {
-        matches!(self, SandboxLevel::Standard | SandboxLevel::Full)
+        self.basic
     }
     
     // Can write files
     pub fn allows_fs_write(&self) -> bool {
-        matches!(self, SandboxLevel::Standard | SandboxLevel::Full)
+        self.fs_write
+    }
+    
+    // Can press keys / type text, keys can type into a terminal just like exec
+    pub fn allows_input(&self) -> bool {
+        self.exec
+    }
+    
+    // Can read D-Bus properties and control media players (method calls need allows_exec)
+    pub fn allows_dbus(&self) -> bool {
+        self.basic
     }
     
     // Can execute system commands
     pub fn allows_exec(&self) -> bool {
-        matches!(self, SandboxLevel::Full)
+        self.exec
     }
     
     // Can access clipboard write
     pub fn allows_clipboard_write(&self) -> bool {
-        matches!(self, SandboxLevel::Full)
+        self.clipboard
     }
     
     // Can access paths outside command folder
     pub fn allows_expanded_paths(&self) -> bool {
-        matches!(self, SandboxLevel::Full)
+        self.exec
     }
 }
 
-impl Default for SandboxLevel {
-    fn default() -> Self {
-        SandboxLevel::Standard
+// the old levels map onto capability sets
+impl From<SandboxLevel> for Capabilities {
+    fn from(level: SandboxLevel) -> Self {
+        match level {
+            SandboxLevel::Minimal => Capabilities::default(),
+            SandboxLevel::Standard => Capabilities { basic: true, network: true, fs_write: true, ..Default::default() },
+            SandboxLevel::Full => Capabilities { basic: true, network: true, fs_write: true, exec: true, clipboard: true },
+        }
     }
-}
\ No newline at end of file
+}
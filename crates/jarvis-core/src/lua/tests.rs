@@ -1,6 +1,7 @@
 #[cfg(test)]
 mod tests {
-    use crate::lua::{CommandContext, LuaError, SandboxLevel, execute};
+    use crate::lua::{Capabilities, CommandContext, LuaError, SandboxLevel, execute};
+    use crate::commands::Capability;
 
     use std::path::PathBuf;
     use std::time::Duration;
@@ -109,4 +110,35 @@ mod tests {
         
         assert!(result.is_ok());
     }
+    
+    #[test]
+    fn test_declared_capabilities() {
+        let dir = tempdir().unwrap();
+        let script_path = dir.path().join("test.lua");
+        
+        fs::write(&script_path, r#"
+            if jarvis.http ~= nil then
+                error("http was not declared")
+            end
+            if jarvis.system.exec ~= nil then
+                error("exec was not declared")
+            end
+            if jarvis.input ~= nil then
+                error("input comes with exec only")
+            end
+            local ok = pcall(function()
+                jarvis.fs.write("out.txt", "data")
+            end)
+            if not ok then
+                error("fs-write was declared")
+            end
+            return true
+        "#).unwrap();
+        
+        let context = create_test_context(dir.path().to_path_buf());
+        let capabilities = Capabilities::declared(&[Capability::FsWrite]);
+        let result = execute(&script_path, context, capabilities, Duration::from_secs(5));
+        
+        assert!(result.is_ok());
+    }
 }
\ No newline at end of file
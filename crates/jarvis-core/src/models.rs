@@ -0,0 +1,305 @@
+#[cfg(test)]
+mod tests;
+
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use once_cell::sync::OnceCell;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::{APP_DIR, config};
+
+// built-in copy of resources/models/models.toml, used when the manifest on disk is missing or broken
+const BUILTIN_MANIFEST: &str = include_str!("models/models.toml");
+
+static REGISTRY: OnceCell<ModelManifest> = OnceCell::new();
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ModelKind {
+    Embedding,
+    Gliner,
+    Vosk,
+    Vad,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelFile {
+    pub path: String, // relative to the model folder
+    #[serde(default)]
+    pub sha256: String,
+    #[serde(default)]
+    pub size: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelEntry {
+    pub id: String,
+    pub kind: ModelKind,
+    #[serde(default)]
+    pub languages: Vec<String>,
+    pub path: String, // relative to the app dir
+    #[serde(default)]
+    pub priority: i32,
+    #[serde(default)]
+    pub archive: String, // file name on the mirror, default "<id>.zip"
+    #[serde(default)]
+    pub files: Vec<ModelFile>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ModelManifest {
+    #[serde(default)]
+    pub mirror: String,
+    #[serde(default)]
+    pub models: Vec<ModelEntry>,
+}
+
+impl ModelEntry {
+    pub fn dir(&self) -> PathBuf {
+        APP_DIR.join(&self.path)
+    }
+
+    pub fn archive_name(&self) -> String {
+        if self.archive.is_empty() {
+            format!("{}.zip", self.id)
+        } else {
+            self.archive.clone()
+        }
+    }
+
+    // 2 = language listed explicitly, 1 = any language, 0 = not for this language
+    fn language_score(&self, language: &str) -> u8 {
+        if self.languages.iter().any(|l| l == language) {
+            2
+        } else if self.languages.is_empty() || self.languages.iter().any(|l| l == "*") {
+            1
+        } else {
+            0
+        }
+    }
+
+    fn is_installed_in(&self, dir: &Path) -> bool {
+        dir.is_dir() && self.files.iter().all(|f| dir.join(&f.path).is_file())
+    }
+
+    pub fn is_installed(&self) -> bool {
+        self.is_installed_in(&self.dir())
+    }
+}
+
+// Registry loaded from MODELS_MANIFEST, falls back to the built-in copy
+pub fn registry() -> &'static ModelManifest {
+    REGISTRY.get_or_init(|| {
+        let path = APP_DIR.join(config::MODELS_PATH).join(config::MODELS_MANIFEST);
+        let manifest = fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|s| parse_manifest(&s));
+
+        match manifest {
+            Ok(m) => {
+                info!("Model registry loaded from {} ({} models)", path.display(), m.models.len());
+                m
+            }
+            Err(e) => {
+                warn!("Cannot load model registry {}: {}, using built-in", path.display(), e);
+                parse_manifest(BUILTIN_MANIFEST).unwrap_or_default()
+            }
+        }
+    })
+}
+
+pub fn parse_manifest(content: &str) -> Result<ModelManifest, String> {
+    toml::from_str(content).map_err(|e| format!("Invalid model manifest: {}", e))
+}
+
+// All registered models of a kind
+pub fn list(kind: ModelKind) -> Vec<&'static ModelEntry> {
+    registry().models.iter().filter(|m| m.kind == kind).collect()
+}
+
+pub fn find(id: &str) -> Option<&'static ModelEntry> {
+    registry().models.iter().find(|m| m.id == id)
+}
+
+// Registered model living at the given folder, if any
+pub fn find_by_dir(dir: &Path) -> Option<&'static ModelEntry> {
+    registry().models.iter().find(|m| m.dir() == dir)
+}
+
+// Installed models usable for a task and language, best first
+pub fn candidates(kind: ModelKind, language: &str) -> Vec<&'static ModelEntry> {
+    rank(&registry().models, kind, language, |m| m.is_installed())
+}
+
+// Best installed model for a task and language
+pub fn resolve(kind: ModelKind, language: &str) -> Option<&'static ModelEntry> {
+    candidates(kind, language).into_iter().next()
+}
+
+fn rank<'a>(
+    models: &'a [ModelEntry],
+    kind: ModelKind,
+    language: &str,
+    installed: impl Fn(&ModelEntry) -> bool,
+) -> Vec<&'a ModelEntry> {
+    let mut found: Vec<&ModelEntry> = models.iter()
+        .filter(|m| m.kind == kind && m.language_score(language) > 0 && installed(m))
+        .collect();
+
+    found.sort_by(|a, b| {
+        b.language_score(language).cmp(&a.language_score(language))
+            .then(b.priority.cmp(&a.priority))
+    });
+    found
+}
+
+// Check that all files of a model exist and match their size / sha256
+pub fn verify(entry: &ModelEntry) -> Result<(), String> {
+    verify_in(entry, &entry.dir())
+}
+
+fn verify_in(entry: &ModelEntry, dir: &Path) -> Result<(), String> {
+    for file in &entry.files {
+        let path = dir.join(&file.path);
+
+        let meta = fs::metadata(&path)
+            .map_err(|_| format!("Model '{}' is missing {}", entry.id, file.path))?;
+
+        if file.size > 0 && meta.len() != file.size {
+            return Err(format!(
+                "Model '{}': {} has size {}, expected {}",
+                entry.id, file.path, meta.len(), file.size
+            ));
+        }
+
+        if !file.sha256.is_empty() {
+            let actual = file_sha256(&path)?;
+            if !actual.eq_ignore_ascii_case(&file.sha256) {
+                return Err(format!("Model '{}': checksum mismatch for {}", entry.id, file.path));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+// Install a model from a local archive into its registry path
+pub fn install_from_archive(entry: &ModelEntry, archive: &Path) -> Result<PathBuf, String> {
+    let target = entry.dir();
+    if target.exists() {
+        return Err(format!("Model '{}' is already installed at {}", entry.id, target.display()));
+    }
+
+    let parent = target.parent().ok_or("Invalid model path")?;
+    fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+
+    // unpack next to the target, so the final move is a rename
+    let staging = tempfile::tempdir_in(parent).map_err(|e| e.to_string())?;
+    extract_archive(archive, staging.path())?;
+
+    let root = find_root(entry, staging.path())
+        .ok_or_else(|| format!("'{}' does not contain model '{}'", archive.display(), entry.id))?;
+    verify_in(entry, &root)?;
+
+    fs::rename(&root, &target).map_err(|e| format!("Failed to move model into place: {}", e))?;
+    info!("Installed model '{}' into {}", entry.id, target.display());
+
+    Ok(target)
+}
+
+// Download "<mirror>/<archive>" and install it
+#[cfg(feature = "model_download")]
+pub fn install_from_mirror(entry: &ModelEntry) -> Result<PathBuf, String> {
+    let mirror = crate::DB.get()
+        .map(|db| db.read().models_mirror.clone())
+        .filter(|m| !m.is_empty())
+        .unwrap_or_else(|| registry().mirror.clone());
+    if mirror.is_empty() {
+        return Err("No model mirror configured".into());
+    }
+
+    let url = format!("{}/{}", mirror.trim_end_matches('/'), entry.archive_name());
+    info!("Downloading model '{}' from {}", entry.id, url);
+
+    let download_dir = tempfile::tempdir().map_err(|e| e.to_string())?;
+    let archive = download_dir.path().join(entry.archive_name());
+
+    // no total timeout, reading a few hundred MB may take minutes
+    let client = reqwest::blocking::Client::builder()
+        .connect_timeout(std::time::Duration::from_secs(config::MODEL_DOWNLOAD_CONNECT_TIMEOUT_SECS))
+        .timeout(None)
+        .user_agent(config::HTTP_USER_AGENT)
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+
+    let mut response = client.get(&url)
+        .send()
+        .and_then(|r| r.error_for_status())
+        .map_err(|e| format!("Failed to download {}: {}", url, e))?;
+    let mut file = fs::File::create(&archive).map_err(|e| e.to_string())?;
+    std::io::copy(&mut response, &mut file).map_err(|e| format!("Failed to download {}: {}", url, e))?;
+    drop(file);
+
+    install_from_archive(entry, &archive)
+}
+
+// model files either at the archive root or inside a single top-level folder
+fn find_root(entry: &ModelEntry, dir: &Path) -> Option<PathBuf> {
+    if entry.is_installed_in(dir) {
+        return Some(dir.to_path_buf());
+    }
+
+    let subdirs: Vec<PathBuf> = fs::read_dir(dir).ok()?
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.is_dir())
+        .collect();
+
+    match subdirs.as_slice() {
+        [single] if entry.is_installed_in(single) => Some(single.clone()),
+        _ => None,
+    }
+}
+
+// .zip / .tar.gz / .tgz / .tar
+pub fn extract_archive(archive: &Path, dest: &Path) -> Result<(), String> {
+    let file = fs::File::open(archive)
+        .map_err(|e| format!("Failed to open {}: {}", archive.display(), e))?;
+    let file_name = archive.file_name().and_then(|n| n.to_str()).unwrap_or_default().to_lowercase();
+
+    if file_name.ends_with(".zip") {
+        zip::ZipArchive::new(file)
+            .and_then(|mut z| z.extract(dest))
+            .map_err(|e| format!("Failed to extract {}: {}", archive.display(), e))
+    } else if file_name.ends_with(".tar.gz") || file_name.ends_with(".tgz") {
+        tar::Archive::new(flate2::read::GzDecoder::new(file))
+            .unpack(dest)
+            .map_err(|e| format!("Failed to extract {}: {}", archive.display(), e))
+    } else if file_name.ends_with(".tar") {
+        tar::Archive::new(file)
+            .unpack(dest)
+            .map_err(|e| format!("Failed to extract {}: {}", archive.display(), e))
+    } else {
+        Err(format!("Unsupported archive format: {}", archive.display()))
+    }
+}
+
+pub fn file_sha256(path: &Path) -> Result<String, String> {
+    let mut file = fs::File::open(path)
+        .map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+    let mut hasher = Sha256::new();
+    let mut buf = [0u8; 64 * 1024];
+
+    loop {
+        let n = file.read(&mut buf).map_err(|e| e.to_string())?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buf[..n]);
+    }
+
+    Ok(format!("{:x}", hasher.finalize()))
+}
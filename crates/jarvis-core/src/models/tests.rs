@@ -0,0 +1,92 @@
+use std::fs;
+
+use crate::models::{parse_manifest, rank, verify_in, ModelKind, BUILTIN_MANIFEST};
+
+const MANIFEST: &str = r#"
+mirror = "https://example.com/models"
+
+[[models]]
+id = "small-en"
+kind = "embedding"
+languages = ["en"]
+path = "models/small-en"
+files = [{ path = "model.onnx", size = 4 }]
+
+[[models]]
+id = "multi"
+kind = "embedding"
+languages = ["*"]
+path = "models/multi"
+files = [{ path = "model.onnx" }]
+
+[[models]]
+id = "multi-better"
+kind = "embedding"
+languages = ["*"]
+path = "models/multi-better"
+priority = 5
+
+[[models]]
+id = "gliner"
+kind = "gliner"
+path = "models/gliner"
+"#;
+
+#[test]
+fn builtin_manifest_parses() {
+    let manifest = parse_manifest(BUILTIN_MANIFEST).unwrap();
+    assert!(manifest.models.iter().any(|m| m.kind == ModelKind::Embedding));
+    assert!(manifest.models.iter().any(|m| m.kind == ModelKind::Gliner));
+    assert!(manifest.models.iter().any(|m| m.kind == ModelKind::Vad));
+
+    // shipped embedding models are fully pinned
+    assert!(manifest.models.iter()
+        .filter(|m| m.kind == ModelKind::Embedding)
+        .flat_map(|m| &m.files)
+        .all(|f| f.sha256.len() == 64 && f.size > 0));
+}
+
+#[test]
+fn builtin_manifest_matches_resources() {
+    let shipped = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../../resources/models/models.toml");
+    assert_eq!(fs::read_to_string(shipped).unwrap(), BUILTIN_MANIFEST);
+}
+
+#[test]
+fn ranks_by_language_then_priority() {
+    let manifest = parse_manifest(MANIFEST).unwrap();
+    assert_eq!(manifest.models[0].archive_name(), "small-en.zip");
+
+    let ids = |lang: &str| -> Vec<String> {
+        rank(&manifest.models, ModelKind::Embedding, lang, |_| true)
+            .into_iter()
+            .map(|m| m.id.clone())
+            .collect()
+    };
+
+    assert_eq!(ids("en"), ["small-en", "multi-better", "multi"]);
+    assert_eq!(ids("ru"), ["multi-better", "multi"]);
+
+    // not installed models are skipped
+    let installed = rank(&manifest.models, ModelKind::Embedding, "en", |m| m.id == "multi");
+    assert_eq!(installed.len(), 1);
+    assert_eq!(installed[0].id, "multi");
+}
+
+#[test]
+fn verify_checks_files_size_and_hash() {
+    let manifest = parse_manifest(MANIFEST).unwrap();
+    let mut entry = manifest.models[0].clone();
+    let dir = tempfile::tempdir().unwrap();
+
+    assert!(verify_in(&entry, dir.path()).unwrap_err().contains("missing"));
+
+    fs::write(dir.path().join("model.onnx"), b"12345").unwrap();
+    assert!(verify_in(&entry, dir.path()).unwrap_err().contains("size"));
+
+    fs::write(dir.path().join("model.onnx"), b"1234").unwrap();
+    assert!(verify_in(&entry, dir.path()).is_ok());
+
+    entry.files[0].sha256 = "00".into();
+    assert!(verify_in(&entry, dir.path()).unwrap_err().contains("checksum"));
+}
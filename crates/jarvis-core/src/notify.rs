@@ -0,0 +1,50 @@
+// Desktop notifications, shared by command output and the Lua API
+
+use std::process::Command;
+
+pub fn show(title: &str, message: &str) {
+    debug!("Notification: {} - {}", title, message);
+
+    // platform-specific notification
+    #[cfg(target_os = "windows")]
+    {
+        #[cfg(feature = "winrt-notification")]
+        {
+            use winrt_notification::{Toast, Duration as ToastDuration};
+
+            match Toast::new(Toast::POWERSHELL_APP_ID)
+                .title(title)
+                .text1(message)
+                .duration(ToastDuration::Short)
+                .show()
+            {
+                Ok(_) => return,
+                Err(e) => warn!("Failed to show toast notification: {}", e),
+            }
+        }
+
+        // fallback to msg.exe
+        let _ = Command::new("msg")
+            .args(["*", "/time:10", &format!("{}: {}", title, message)])
+            .spawn();
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        let _ = Command::new("notify-send")
+            .args([title, message])
+            .spawn();
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        let script = format!(
+            r#"display notification "{}" with title "{}""#,
+            message.replace("\"", "\\\""),
+            title.replace("\"", "\\\"")
+        );
+        let _ = Command::new("osascript")
+            .args(["-e", &script])
+            .spawn();
+    }
+}
@@ -16,7 +16,8 @@ use structs::GlinerModelInfo;
 use std::fs;
 
 use crate::commands::{SlotDefinition, SlotValue};
-use crate::{APP_DIR, i18n};
+use crate::models::{self, ModelKind};
+use crate::i18n;
 
 // MODEL STATE
 
@@ -56,12 +57,18 @@ pub fn init() -> Result<(), String> {
     let language = i18n::get_language();
 
     let (model_dir, onnx_file) = if variant.is_empty() {
-        (select_model_dir(), "model.onnx".to_string())
+        (select_model_dir(&language)?, "model.onnx".to_string())
     } else {
-        crate::gliner_models::resolve_model(&variant, &language)
-            .unwrap_or_else(|| (select_model_dir(), "model.onnx".to_string()))
+        match crate::gliner_models::resolve_model(&variant, &language) {
+            Some(resolved) => resolved,
+            None => (select_model_dir(&language)?, "model.onnx".to_string()),
+        }
     };
 
+    if let Some(entry) = models::find_by_dir(&model_dir) {
+        models::verify(entry)?;
+    }
+
     let model_path = model_dir.join("onnx").join(&onnx_file);
     let tokenizer_path = model_dir.join("tokenizer.json");
 
@@ -85,23 +92,11 @@ pub fn init() -> Result<(), String> {
     Ok(())
 }
 
-fn select_model_dir() -> PathBuf {
-    let base = APP_DIR.join("resources").join("models");
-
-    match i18n::get_language().as_str() {
-        "en" => {
-            let path = base.join("gliner_small-v2.1");
-            if path.exists() { return path; }
-        }
-        _ => {}
-    }
-
-    // multilingual (covers RU, UA, EN)
-    let multi = base.join("gliner_multi-v2.1");
-    if multi.exists() { return multi; }
-
-    // fallback
-    base.join("gliner_small-v2.1")
+fn select_model_dir(language: &str) -> Result<PathBuf, String> {
+    // registry order: language-specific (small for EN) first, then multilingual
+    models::resolve(ModelKind::Gliner, language)
+        .map(|entry| entry.dir())
+        .ok_or_else(|| format!("No GLiNER model installed for language '{}'", language))
 }
 
 // WORD SPLITTING
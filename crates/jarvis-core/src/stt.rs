@@ -1,5 +1,6 @@
 #[cfg(feature = "vosk")]
 mod vosk;
+pub mod grammar;
 
 use crate::config;
 use once_cell::sync::OnceCell;
@@ -11,11 +12,29 @@ use crate::vosk_models;
 pub use self::vosk::init_vosk;
 pub use self::vosk::recognize_wake_word;
 pub use self::vosk::recognize_speech;
+pub use self::vosk::recognize_speech_alternatives;
+pub use self::vosk::take_speech_partial;
+pub use self::vosk::update_command_grammar;
+pub use self::vosk::set_reply_grammar;
+pub use self::vosk::recognize_reply;
 pub use self::vosk::reset_speech_recognizer;
 pub use self::vosk::reset_wake_recognizer;
 
 static STT_TYPE: OnceCell<SpeechToTextEngine> = OnceCell::new();
 
+// One n-best hypothesis, confidence is its share of the n-best list (0.0 - 1.0)
+#[derive(Debug, Clone)]
+pub struct SpeechAlternative {
+    pub text: String,
+    pub confidence: f32,
+}
+
+impl SpeechAlternative {
+    pub fn new(text: impl Into<String>, confidence: f32) -> Self {
+        Self { text: text.into(), confidence }
+    }
+}
+
 pub fn init() -> Result<(), ()> {
     if STT_TYPE.get().is_some() {
         return Ok(());
@@ -30,7 +49,10 @@ pub fn init() -> Result<(), ()> {
         SpeechToTextEngine::Vosk => {
             // Init Vosk
             info!("Initializing Vosk STT backend.");
-            vosk::init_vosk();
+            if let Err(e) = vosk::init_vosk() {
+                error!("Failed to initialize Vosk: {}", e);
+                return Err(());
+            }
             info!("STT backend initialized.");
         }
     }
@@ -46,6 +68,11 @@ pub fn recognize(data: &[i16], include_partial: bool) -> Option<String> {
     }
 }
 
+// All hypotheses of a finalized utterance, best first
+pub fn recognize_alternatives(data: &[i16]) -> Option<Vec<SpeechAlternative>> {
+    vosk::recognize_speech_alternatives(data)
+}
+
 // pub fn recognize(data: &[i16], partial: bool) -> Option<String> {
 //     match STT_TYPE.get().unwrap() {
 //         SpeechToTextEngine::Vosk => vosk::recognize(data, partial),
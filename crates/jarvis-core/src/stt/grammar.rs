@@ -0,0 +1,78 @@
+#[cfg(test)]
+mod tests;
+
+use std::collections::BTreeSet;
+
+use crate::commands::{self, JCommandsList};
+use crate::config;
+
+pub const UNKNOWN_TOKEN: &str = "[unk]";
+
+// Vocabulary for a grammar-restricted recognizer, built from all loaded command phrases.
+// Contains full phrases (slot placeholders become [unk]), every single word of them,
+// slot context words, wake phrases and [unk] itself, so partial or reordered speech still decodes.
+pub fn build_vocabulary(commands: &[JCommandsList], lang: &str) -> Vec<String> {
+    let mut entries: BTreeSet<String> = BTreeSet::new();
+
+    // disabled commands are left out, same as in commands_hash
+    for cmd in commands.iter().flat_map(|list| list.commands.iter()).filter(|c| commands::is_enabled(&c.id)) {
+        for phrase in cmd.get_phrases(lang).iter() {
+            let phrase = normalize_phrase(phrase);
+            if phrase.is_empty() {
+                continue;
+            }
+
+            for word in phrase.split_whitespace().filter(|w| *w != UNKNOWN_TOKEN) {
+                entries.insert(word.to_string());
+            }
+            entries.insert(phrase);
+        }
+
+        for slot in cmd.slots.values() {
+            for word in slot.context.iter().map(|w| normalize_phrase(w)).filter(|w| !w.is_empty()) {
+                entries.insert(word);
+            }
+        }
+    }
+
+    if entries.is_empty() {
+        return Vec::new();
+    }
+
+    for wake in config::get_wake_grammar(lang) {
+        entries.insert(wake.to_string());
+    }
+
+    // [unk] first, same as the wake grammar
+    entries.remove(UNKNOWN_TOKEN);
+    std::iter::once(UNKNOWN_TOKEN.to_string()).chain(entries).collect()
+}
+
+// Drop [unk] tokens from grammar recognizer output
+pub fn strip_unknown(text: &str) -> String {
+    text.split_whitespace()
+        .filter(|w| *w != UNKNOWN_TOKEN)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+// lowercase, {slot} -> [unk], no punctuation
+fn normalize_phrase(phrase: &str) -> String {
+    let mut normalized = String::with_capacity(phrase.len());
+    let mut in_slot = false;
+
+    for c in phrase.to_lowercase().chars() {
+        match c {
+            '{' => {
+                in_slot = true;
+                normalized.push_str(" [unk] ");
+            }
+            '}' => in_slot = false,
+            _ if in_slot => {}
+            c if c.is_alphanumeric() || c == '\'' || c == '-' => normalized.push(c),
+            _ => normalized.push(' '),
+        }
+    }
+
+    normalized.split_whitespace().collect::<Vec<_>>().join(" ")
+}
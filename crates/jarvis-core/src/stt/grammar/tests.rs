@@ -0,0 +1,51 @@
+use crate::commands::{JCommand, JCommandsList};
+use crate::stt::grammar::{build_vocabulary, strip_unknown, UNKNOWN_TOKEN};
+
+fn commands(json: serde_json::Value) -> Vec<JCommandsList> {
+    let commands: Vec<JCommand> = serde_json::from_value(json).unwrap();
+    vec![JCommandsList { path: Default::default(), pack: None, commands }]
+}
+
+#[test]
+fn vocabulary_contains_phrases_and_words() {
+    let list = commands(serde_json::json!([
+        { "id": "browser", "type": "cli", "phrases": { "en": ["Open the browser!"] } },
+    ]));
+
+    let vocabulary = build_vocabulary(&list, "en");
+
+    assert_eq!(vocabulary[0], UNKNOWN_TOKEN);
+    assert!(vocabulary.contains(&"open the browser".to_string()));
+    assert!(vocabulary.contains(&"browser".to_string()));
+    assert!(vocabulary.contains(&"jarvis".to_string()));
+    assert_eq!(vocabulary.iter().filter(|e| *e == UNKNOWN_TOKEN).count(), 1);
+}
+
+#[test]
+fn slot_placeholders_become_unknown() {
+    let list = commands(serde_json::json!([
+        {
+            "id": "weather",
+            "type": "lua",
+            "phrases": { "en": ["weather in {city}"] },
+            "slots": { "city": { "entity": "city name", "context": ["in", "For"] } }
+        },
+    ]));
+
+    let vocabulary = build_vocabulary(&list, "en");
+
+    assert!(vocabulary.contains(&"weather in [unk]".to_string()));
+    assert!(vocabulary.contains(&"for".to_string()));
+    assert!(!vocabulary.iter().any(|e| e.contains("city")));
+}
+
+#[test]
+fn empty_commands_give_empty_vocabulary() {
+    assert!(build_vocabulary(&[], "en").is_empty());
+}
+
+#[test]
+fn strips_unknown_tokens() {
+    assert_eq!(strip_unknown("[unk] open [unk] browser"), "open browser");
+    assert_eq!(strip_unknown("[unk]"), "");
+}
@@ -1,16 +1,34 @@
-use once_cell::sync::OnceCell;
-use vosk::{DecodingState, Model, Recognizer};
+use once_cell::sync::{Lazy, OnceCell};
+use vosk::{CompleteResult, DecodingState, Model, Recognizer};
 
 use std::sync::Mutex;
 
 // use crate::config::VOSK_MODEL_PATH;
-use crate::{stt::vosk_models, i18n, config};
+use crate::{stt::{grammar, vosk_models, SpeechAlternative}, i18n, config, commands, models};
+use crate::commands::JCommandsList;
+use crate::config::structs::CommandGrammarMode;
 use crate::DB;
 
 static MODEL: OnceCell<Model> = OnceCell::new();
 static WAKE_RECOGNIZER: OnceCell<Mutex<Recognizer>> = OnceCell::new();
 static SPEECH_RECOGNIZER: OnceCell<Mutex<Recognizer>> = OnceCell::new();
 
+// last partial hypothesis handed out, to only report changes
+static LAST_PARTIAL: Lazy<Mutex<String>> = Lazy::new(|| Mutex::new(String::new()));
+
+// recognizer restricted to the loaded command phrases, see update_command_grammar
+static COMMAND_GRAMMAR: Lazy<Mutex<Option<CommandGrammar>>> = Lazy::new(|| Mutex::new(None));
+
+// recognizer for short yes / no replies, see set_reply_grammar
+static REPLY_GRAMMAR: Lazy<Mutex<Option<Recognizer>>> = Lazy::new(|| Mutex::new(None));
+
+struct CommandGrammar {
+    hash: String,
+    mode: CommandGrammarMode,
+    recognizer: Recognizer,
+    pending: Vec<String>, // utterances finalized before the free-form recognizer did
+}
+
 pub fn init_vosk() -> Result<(), String> {
     if MODEL.get().is_some() {
         return Ok(());
@@ -77,23 +95,199 @@ pub fn recognize_wake_word(data: &[i16]) -> Option<(String, f32)> {
 
 
 pub fn recognize_speech(data: &[i16]) -> Option<String> {
+    recognize_speech_alternatives(data)
+        .and_then(|alternatives| alternatives.into_iter().next())
+        .map(|a| a.text)
+}
+
+
+pub fn recognize_speech_alternatives(data: &[i16]) -> Option<Vec<SpeechAlternative>> {
+    let mut command_grammar = COMMAND_GRAMMAR.lock().unwrap();
+
+    if let Some(cg) = command_grammar.as_mut().filter(|cg| cg.mode == CommandGrammarMode::Only) {
+        return match cg.recognizer.accept_waveform(data) {
+            Ok(DecodingState::Finalized) => {
+                LAST_PARTIAL.lock().unwrap().clear();
+                let text = grammar_text(cg.recognizer.result());
+                (!text.is_empty()).then(|| vec![SpeechAlternative::new(text, 1.0)])
+            }
+            _ => None,
+        };
+    }
+
+    // alongside: keep whatever the grammar recognizer finalizes on its own
+    if let Some(cg) = command_grammar.as_mut() {
+        if let Ok(DecodingState::Finalized) = cg.recognizer.accept_waveform(data) {
+            let text = grammar_text(cg.recognizer.result());
+            if !text.is_empty() {
+                cg.pending.push(text);
+            }
+        }
+    }
+
     let mut recognizer = SPEECH_RECOGNIZER.get()?.lock().unwrap();
     
     match recognizer.accept_waveform(data) {
         Ok(DecodingState::Finalized) => {
-            recognizer.result()
-                .multiple()
-                .and_then(|m| m.alternatives.first().map(|a| a.text.to_string()))
+            LAST_PARTIAL.lock().unwrap().clear();
+
+            let result = recognizer.result();
+            let mut alternatives: Vec<SpeechAlternative> = result.multiple()?
+                .alternatives
+                .iter()
+                .filter(|a| !a.text.trim().is_empty())
+                .map(|a| SpeechAlternative::new(a.text, a.confidence))
+                .collect();
+
+            normalize_confidences(&mut alternatives);
+
+            if let Some(cg) = command_grammar.as_mut() {
+                let mut texts = std::mem::take(&mut cg.pending);
+                texts.push(grammar_text(cg.recognizer.final_result()));
+                let text = grammar::strip_unknown(&texts.join(" "));
+
+                if !text.is_empty() && !alternatives.iter().any(|a| a.text == text) {
+                    // same confidence as the best free-form hypothesis, the command match decides
+                    let confidence = alternatives.first().map(|a| a.confidence).unwrap_or(1.0);
+                    alternatives.push(SpeechAlternative::new(text, confidence));
+                }
+            }
+
+            if alternatives.is_empty() {
+                return None;
+            }
+
+            Some(alternatives)
+        }
+        _ => None,
+    }
+}
+
+fn grammar_text(result: CompleteResult) -> String {
+    result.multiple()
+        .and_then(|m| m.alternatives.first().map(|a| grammar::strip_unknown(a.text)))
+        .unwrap_or_default()
+}
+
+// (Re)build the command grammar recognizer, only if the commands hash or mode changed
+pub fn update_command_grammar(commands: &[JCommandsList]) {
+    let mode = DB.get()
+        .map(|db| db.read().command_grammar)
+        .unwrap_or(config::DEFAULT_COMMAND_GRAMMAR_MODE);
+
+    let mut command_grammar = COMMAND_GRAMMAR.lock().unwrap();
+    if mode == CommandGrammarMode::Off {
+        *command_grammar = None;
+        return;
+    }
+
+    let Some(model) = MODEL.get() else {
+        return;
+    };
+
+    let hash = commands::commands_hash(commands);
+    if command_grammar.as_ref().is_some_and(|cg| cg.hash == hash && cg.mode == mode) {
+        return;
+    }
+
+    let vocabulary = grammar::build_vocabulary(commands, &i18n::get_language());
+    if vocabulary.is_empty() {
+        warn!("No command phrases, command grammar disabled");
+        *command_grammar = None;
+        return;
+    }
+
+    let entries: Vec<&str> = vocabulary.iter().map(String::as_str).collect();
+    match Recognizer::new_with_grammar(model, 16000.0, &entries) {
+        Some(mut recognizer) => {
+            recognizer.set_max_alternatives(1);
+            info!("Command grammar built ({:?}): {} entries", mode, entries.len());
+            *command_grammar = Some(CommandGrammar { hash, mode, recognizer, pending: Vec::new() });
+        }
+        None => {
+            error!("Failed to create command grammar recognizer");
+            *command_grammar = None;
+        }
+    }
+}
+
+// Restrict the next replies to the given phrases (confirmation yes / no), None goes back to normal speech
+pub fn set_reply_grammar(entries: Option<&[String]>) {
+    let mut reply_grammar = REPLY_GRAMMAR.lock().unwrap();
+    *reply_grammar = None;
+
+    let (Some(entries), Some(model)) = (entries, MODEL.get()) else {
+        return;
+    };
+
+    let entries: Vec<&str> = entries.iter().map(String::as_str).collect();
+    match Recognizer::new_with_grammar(model, 16000.0, &entries) {
+        Some(mut recognizer) => {
+            recognizer.set_max_alternatives(1);
+            *reply_grammar = Some(recognizer);
+        }
+        None => error!("Failed to create reply grammar recognizer"),
+    }
+}
+
+// Finalized reply text, the free-form recognizer is used when no reply grammar is set
+pub fn recognize_reply(data: &[i16]) -> Option<String> {
+    let mut reply_grammar = REPLY_GRAMMAR.lock().unwrap();
+    let Some(recognizer) = reply_grammar.as_mut() else {
+        drop(reply_grammar);
+        return recognize_speech(data);
+    };
+
+    match recognizer.accept_waveform(data) {
+        Ok(DecodingState::Finalized) => {
+            let text = grammar_text(recognizer.result());
+            (!text.is_empty()).then_some(text)
         }
         _ => None,
     }
 }
 
+// vosk n-best confidences are raw lattice scores, turn them into shares of the list
+fn normalize_confidences(alternatives: &mut [SpeechAlternative]) {
+    let best = alternatives.iter().map(|a| a.confidence).fold(f32::NEG_INFINITY, f32::max);
+    let weight = |score: f32| ((score - best) / config::VOSK_ALTERNATIVES_SCORE_SCALE).exp();
+    let total: f32 = alternatives.iter().map(|a| weight(a.confidence)).sum();
+
+    for a in alternatives.iter_mut() {
+        a.confidence = weight(a.confidence) / total;
+    }
+}
+
+
+// Current partial hypothesis of the speech recognizer, only if it changed since last call
+pub fn take_speech_partial() -> Option<String> {
+    let mut command_grammar = COMMAND_GRAMMAR.lock().unwrap();
+    let partial = match command_grammar.as_mut().filter(|cg| cg.mode == CommandGrammarMode::Only) {
+        Some(cg) => grammar::strip_unknown(cg.recognizer.partial_result().partial),
+        None => SPEECH_RECOGNIZER.get()?.lock().unwrap().partial_result().partial.trim().to_string(),
+    };
+
+    let mut last = LAST_PARTIAL.lock().unwrap();
+    if partial.is_empty() || *last == partial {
+        return None;
+    }
+
+    *last = partial.clone();
+    Some(partial)
+}
 
 pub fn reset_speech_recognizer() {
+    if let Some(recognizer) = REPLY_GRAMMAR.lock().unwrap().as_mut() {
+        recognizer.reset();
+    }
+    if let Some(cg) = COMMAND_GRAMMAR.lock().unwrap().as_mut() {
+        cg.recognizer.reset();
+        cg.pending.clear();
+    }
     if let Some(recognizer) = SPEECH_RECOGNIZER.get() {
         recognizer.lock().unwrap().reset();
     }
+    LAST_PARTIAL.lock().unwrap().clear();
 }
 
 pub fn reset_wake_recognizer() {
@@ -152,47 +346,23 @@ pub fn reset_wake_recognizer() {
 // }
 
 fn get_configured_model_path() -> Result<std::path::PathBuf, String> {
-    // try to get from settings
-    if let Some(db) = DB.get() {
-        let settings = db.read();
-        if !settings.vosk_model.is_empty() {
-            if let Some(path) = vosk_models::get_model_path(&settings.vosk_model) {
-                return Ok(path);
-            }
-            warn!("Configured Vosk model '{}' not found, falling back to auto-detect", settings.vosk_model);
-        }
-    }
-    
-    // auto-detect: prefer model matching current language
-    let available = vosk_models::scan_vosk_models();
-    let language = i18n::get_language();
-
-    // try language match first
-    let lang_code = match language.as_str() {
-        "ru" => "ru",
-        "en" => "us",  // vosk uses "us" not "en"
-        "ua" => "uk",  // vosk uses "uk" not "ua"
-        other => other,
-    };
+    let configured = DB.get()
+        .map(|db| db.read().vosk_model.clone())
+        .unwrap_or_default();
 
-    if let Some(matched) = available.iter().find(|m| m.language == lang_code) {
-        info!("Auto-detected Vosk model for '{}': {}", language, matched.name);
-        return Ok(matched.path.clone());
-    }
+    let model = vosk_models::select_model(&configured, &i18n::get_language())?;
+    vosk_models::verify_checksum(&model)?;
 
-    // fallback to first available
-    if let Some(first) = available.first() {
-        info!("Auto-detected Vosk model (no language match): {}", first.name);
-        return Ok(first.path.clone());
+    // registry models carry size / checksum info
+    if let Some(entry) = models::find_by_dir(&model.path) {
+        models::verify(entry)?;
     }
-    
-    // fallback to legacy path
-    let legacy_path = std::path::Path::new(config::VOSK_MODEL_PATH);
-    if legacy_path.exists() {
-        return Ok(legacy_path.to_path_buf());
+
+    if model.sample_rate != config::VOSK_DEFAULT_SAMPLE_RATE {
+        warn!("Vosk model '{}' expects {} Hz audio, microphone input is {} Hz", model.name, model.sample_rate, config::VOSK_DEFAULT_SAMPLE_RATE);
     }
-    
-    Err("No Vosk models found".into())
+
+    Ok(model.path)
 }
 
 // pub fn stereo_to_mono(input_data: &[i16]) -> Vec<i16> {
@@ -1,30 +1,71 @@
+#[cfg(test)]
+mod tests;
+
 use std::fs;
 use std::path::{Path, PathBuf};
 
+use serde::{Deserialize, Serialize};
+
+use crate::models::{extract_archive, file_sha256};
 use crate::{APP_DIR, config};
 
+// per-model metadata, stored as MANIFEST_FILE in the model folder
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct VoskModelManifest {
+    pub language: String,       // app language code: "ru", "en", "ua"
+    #[serde(default)]
+    pub size: String,           // "small", "big", "lgraph", ...
+    #[serde(default = "default_sample_rate")]
+    pub sample_rate: u32,
+    // sha256 of CHECKSUM_FILE, empty = not verified
+    #[serde(default)]
+    pub checksum: String,
+}
+
+fn default_sample_rate() -> u32 {
+    config::VOSK_DEFAULT_SAMPLE_RATE
+}
+
+const MANIFEST_FILE: &str = "jarvis-model.toml";
+const CHECKSUM_FILE: &str = "am/final.mdl";
+
 #[derive(Debug, Clone)]
 pub struct VoskModelInfo {
     pub name: String,       // folder name: "vosk-model-small-ru-0.22"
     pub path: PathBuf,      // full path
-    pub language: String,   // extracted from name: "ru"
+    pub language: String,   // app language code: "ru"
     pub size: String,       // "small", "large", etc.
+    pub sample_rate: u32,
+    pub has_manifest: bool,
+    pub problems: Vec<String>, // missing files etc, empty = usable
+}
+
+impl VoskModelInfo {
+    pub fn is_valid(&self) -> bool {
+        self.problems.is_empty()
+    }
+}
+
+pub fn models_dir() -> PathBuf {
+    APP_DIR.join(config::VOSK_MODELS_PATH)
 }
 
 // Scan for available Vosk models
 pub fn scan_vosk_models() -> Vec<VoskModelInfo> {
-    let models_dir = {
-        APP_DIR.join(config::VOSK_MODELS_PATH)
-    };
-    let mut models = Vec::new();
-    
+    let models_dir = models_dir();
     info!("VOSK MODELS DIR: {}", models_dir.display());
 
+    scan_dir(&models_dir)
+}
+
+fn scan_dir(models_dir: &Path) -> Vec<VoskModelInfo> {
+    let mut models = Vec::new();
+
     if !models_dir.exists() {
         warn!("Vosk models directory not found: {}", models_dir.display());
         return models;
     }
-    
+
     let entries = match fs::read_dir(models_dir) {
         Ok(e) => e,
         Err(e) => {
@@ -32,82 +73,300 @@ pub fn scan_vosk_models() -> Vec<VoskModelInfo> {
             return models;
         }
     };
-    
-    for entry in entries {
-        let entry = match entry {
-            Ok(e) => e,
-            Err(_) => continue,
-        };
-        
+
+    for entry in entries.flatten() {
         let path = entry.path();
-        
-        // must be a directory
-        if !path.is_dir() {
+
+        // must be a directory, hidden ones are import leftovers
+        if !path.is_dir() || entry.file_name().to_string_lossy().starts_with('.') {
             continue;
         }
-        
+
         // check if it looks like a vosk model (has am/conf/graph folders or similar)
         if !is_vosk_model(&path) {
             continue;
         }
-        
-        let name = path.file_name()
-            .and_then(|n| n.to_str())
-            .unwrap_or("")
-            .to_string();
-        
+
+        models.push(load_model_info(&path));
+    }
+
+    models.sort_by(|a, b| a.name.cmp(&b.name));
+    models
+}
+
+// Build model info from the manifest, or guess it from the folder name
+pub fn load_model_info(path: &Path) -> VoskModelInfo {
+    let name = path.file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("")
+        .to_string();
+
+    let mut problems = validate_model(path);
+
+    let manifest = match read_manifest(path) {
+        Ok(m) => m,
+        Err(e) => {
+            problems.push(e);
+            None
+        }
+    };
+    let has_manifest = manifest.is_some();
+
+    let manifest = manifest.unwrap_or_else(|| {
         let (language, size) = parse_model_name(&name);
-        
-        models.push(VoskModelInfo {
-            name,
-            path,
+        VoskModelManifest {
             language,
             size,
-        });
+            sample_rate: read_sample_rate(path).unwrap_or(config::VOSK_DEFAULT_SAMPLE_RATE),
+            checksum: String::new(),
+        }
+    });
+
+    VoskModelInfo {
+        name,
+        path: path.to_path_buf(),
+        language: manifest.language,
+        size: manifest.size,
+        sample_rate: manifest.sample_rate,
+        has_manifest,
+        problems,
     }
-    
-    models.sort_by(|a, b| a.name.cmp(&b.name));
-    models
 }
 
 // Check if directory looks like a Vosk model
 fn is_vosk_model(path: &Path) -> bool {
     // vosk models typically have these subdirectories
-    path.join("am").exists() || 
-    path.join("conf").exists() || 
+    path.join("am").exists() ||
+    path.join("conf").exists() ||
     path.join("graph").exists() ||
     path.join("ivector").exists()
 }
 
+// Missing required files, empty if the model can be loaded
+pub fn validate_model(path: &Path) -> Vec<String> {
+    let mut problems: Vec<String> = ["am/final.mdl", "conf/mfcc.conf", "conf/model.conf"]
+        .iter()
+        .filter(|f| !path.join(f).is_file())
+        .map(|f| format!("missing {}", f))
+        .collect();
+
+    // static graph or lookahead (lgraph) graph
+    let graph = path.join("graph");
+    let has_graph = graph.join("HCLG.fst").is_file()
+        || (graph.join("HCLr.fst").is_file() && graph.join("Gr.fst").is_file());
+    if !has_graph {
+        problems.push("missing graph/HCLG.fst (or graph/HCLr.fst + graph/Gr.fst)".into());
+    }
+
+    problems
+}
+
+// Compare CHECKSUM_FILE against the manifest checksum
+pub fn verify_checksum(model: &VoskModelInfo) -> Result<(), String> {
+    let expected = match read_manifest(&model.path)? {
+        Some(m) if !m.checksum.is_empty() => m.checksum,
+        _ => return Ok(()), // nothing to verify against
+    };
+
+    let actual = file_sha256(&model.path.join(CHECKSUM_FILE))?;
+    if actual.eq_ignore_ascii_case(&expected) {
+        Ok(())
+    } else {
+        Err(format!("Checksum mismatch for '{}': expected {}, got {}", model.name, expected, actual))
+    }
+}
+
+fn read_manifest(path: &Path) -> Result<Option<VoskModelManifest>, String> {
+    let manifest_path = path.join(MANIFEST_FILE);
+    if !manifest_path.exists() {
+        return Ok(None);
+    }
+
+    let content = fs::read_to_string(&manifest_path)
+        .map_err(|e| format!("Failed to read {}: {}", manifest_path.display(), e))?;
+    toml::from_str(&content)
+        .map(Some)
+        .map_err(|e| format!("Invalid {}: {}", manifest_path.display(), e))
+}
+
+fn write_manifest(path: &Path, manifest: &VoskModelManifest) -> Result<(), String> {
+    let content = toml::to_string(manifest).map_err(|e| e.to_string())?;
+    fs::write(path.join(MANIFEST_FILE), content)
+        .map_err(|e| format!("Failed to write model manifest: {}", e))
+}
+
+// "--sample-frequency=16000" from conf/mfcc.conf
+fn read_sample_rate(path: &Path) -> Option<u32> {
+    let conf = fs::read_to_string(path.join("conf").join("mfcc.conf")).ok()?;
+    conf.lines()
+        .find_map(|l| l.trim().strip_prefix("--sample-frequency="))
+        .and_then(|v| v.trim().parse::<f32>().ok())
+        .map(|v| v as u32)
+}
+
 // Extract language and size from model name
 // e.g., "vosk-model-small-ru-0.22" -> ("ru", "small")
 fn parse_model_name(name: &str) -> (String, String) {
     let parts: Vec<&str> = name.split('-').collect();
-    
+
     let mut language = String::from("unknown");
     let mut size = String::from("unknown");
-    
+
     // look for common size indicators
     for part in &parts {
         match *part {
             "small" | "big" | "large" | "lgraph" => size = part.to_string(),
-            // language codes are usually 2 letters
-            s if s.len() == 2 && s.chars().all(|c| c.is_alphabetic()) => {
-                language = s.to_string();
+            // language codes are usually 2 letters, first one wins ("en-us")
+            s if language == "unknown" && s.len() == 2 && s.chars().all(|c| c.is_alphabetic()) => {
+                language = to_app_language(s).to_string();
             }
             _ => {}
         }
     }
-    
+
     (language, size)
 }
 
+// vosk uses "us" / "uk", the app uses "en" / "ua"
+fn to_app_language(code: &str) -> &str {
+    match code {
+        "us" => "en",
+        "uk" => "ua",
+        other => other,
+    }
+}
+
 // Get model path by name
 pub fn get_model_path(model_name: &str) -> Option<PathBuf> {
-    let path = Path::new(config::VOSK_MODELS_PATH).join(model_name);
+    let path = models_dir().join(model_name);
     if path.exists() && path.is_dir() {
         Some(path)
     } else {
         None
     }
-}
\ No newline at end of file
+}
+
+// Pick the model to load: the configured one if usable, otherwise a valid model for the language
+pub fn select_model(configured: &str, language: &str) -> Result<VoskModelInfo, String> {
+    pick_model(&scan_vosk_models(), configured, language, &models_dir())
+}
+
+fn pick_model(models: &[VoskModelInfo], configured: &str, language: &str, models_dir: &Path) -> Result<VoskModelInfo, String> {
+    if !configured.is_empty() {
+        match models.iter().find(|m| m.name == configured) {
+            Some(m) if m.is_valid() => {
+                if m.language != language {
+                    warn!("Vosk model '{}' is for '{}', but the assistant language is '{}'", m.name, m.language, language);
+                }
+                return Ok(m.clone());
+            }
+            Some(m) => warn!("Configured Vosk model '{}' is broken ({}), falling back to auto-detect", m.name, m.problems.join(", ")),
+            None => warn!("Configured Vosk model '{}' not found, falling back to auto-detect", configured),
+        }
+    }
+
+    for m in models.iter().filter(|m| !m.is_valid()) {
+        warn!("Skipping broken Vosk model '{}': {}", m.name, m.problems.join(", "));
+    }
+
+    // prefer model matching current language
+    if let Some(matched) = models.iter().find(|m| m.is_valid() && m.language == language) {
+        info!("Auto-detected Vosk model for '{}': {}", language, matched.name);
+        return Ok(matched.clone());
+    }
+
+    // a model in another language would never recognize the speech, pick it explicitly if really wanted
+    let others: Vec<String> = models.iter()
+        .filter(|m| m.is_valid())
+        .map(|m| format!("{} ({})", m.name, m.language))
+        .collect();
+    if !others.is_empty() {
+        return Err(format!(
+            "No Vosk model for language '{}' in {}, only for other languages: {}. Import a '{}' model or select one in the settings.",
+            language, models_dir.display(), others.join(", "), language
+        ));
+    }
+
+    Err(format!(
+        "No usable Vosk models found in {} (language '{}'). Download one from https://alphacephei.com/vosk/models and import it.",
+        models_dir.display(), language
+    ))
+}
+
+// Import a model from a local .zip / .tar.gz / .tgz / .tar archive into the models dir
+pub fn import_model_archive(archive: &Path) -> Result<VoskModelInfo, String> {
+    let models_dir = models_dir();
+    fs::create_dir_all(&models_dir).map_err(|e| format!("Failed to create {}: {}", models_dir.display(), e))?;
+
+    // unpack next to the models, so the final move is a rename
+    let staging = tempfile::tempdir_in(&models_dir).map_err(|e| e.to_string())?;
+    extract_archive(archive, staging.path())?;
+
+    let root = find_model_root(staging.path())
+        .ok_or_else(|| format!("'{}' does not contain a Vosk model", archive.display()))?;
+
+    let problems = validate_model(&root);
+    if !problems.is_empty() {
+        return Err(format!("Invalid Vosk model in '{}': {}", archive.display(), problems.join(", ")));
+    }
+
+    // folder name inside the archive, or the archive name
+    let name = if root != staging.path() {
+        root.file_name().and_then(|n| n.to_str()).unwrap_or_default().to_string()
+    } else {
+        archive_stem(archive)
+    };
+    if name.is_empty() {
+        return Err("Cannot determine model name".into());
+    }
+
+    let target = models_dir.join(&name);
+    if target.exists() {
+        return Err(format!("Vosk model '{}' already exists", name));
+    }
+
+    if read_manifest(&root)?.is_some() {
+        // archive ships its own manifest, the model must match it
+        verify_checksum(&load_model_info(&root))?;
+    } else {
+        let (language, size) = parse_model_name(&name);
+        write_manifest(&root, &VoskModelManifest {
+            language,
+            size,
+            sample_rate: read_sample_rate(&root).unwrap_or(config::VOSK_DEFAULT_SAMPLE_RATE),
+            checksum: file_sha256(&root.join(CHECKSUM_FILE))?,
+        })?;
+    }
+
+    fs::rename(&root, &target).map_err(|e| format!("Failed to move model into place: {}", e))?;
+    info!("Imported Vosk model '{}' from {}", name, archive.display());
+
+    Ok(load_model_info(&target))
+}
+
+// model files either at the archive root or inside a single top-level folder
+fn find_model_root(dir: &Path) -> Option<PathBuf> {
+    if is_vosk_model(dir) {
+        return Some(dir.to_path_buf());
+    }
+
+    let subdirs: Vec<PathBuf> = fs::read_dir(dir).ok()?
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.is_dir())
+        .collect();
+
+    match subdirs.as_slice() {
+        [single] if is_vosk_model(single) => Some(single.clone()),
+        _ => None,
+    }
+}
+
+fn archive_stem(archive: &Path) -> String {
+    let name = archive.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+    [".tar.gz", ".tgz", ".zip", ".tar"]
+        .iter()
+        .find_map(|ext| name.strip_suffix(ext))
+        .unwrap_or(name)
+        .to_string()
+}
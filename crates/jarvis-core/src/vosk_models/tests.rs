@@ -0,0 +1,88 @@
+use std::fs;
+use std::path::Path;
+
+use crate::vosk_models::{load_model_info, parse_model_name, pick_model, scan_dir, validate_model, verify_checksum};
+
+fn make_model(dir: &Path, name: &str) -> std::path::PathBuf {
+    let path = dir.join(name);
+    for f in ["am/final.mdl", "conf/model.conf", "graph/HCLr.fst", "graph/Gr.fst"] {
+        fs::create_dir_all(path.join(f).parent().unwrap()).unwrap();
+        fs::write(path.join(f), b"x").unwrap();
+    }
+    fs::write(path.join("conf/mfcc.conf"), "--use-energy=false\n--sample-frequency=8000\n").unwrap();
+    path
+}
+
+#[test]
+fn parses_vosk_folder_names() {
+    assert_eq!(parse_model_name("vosk-model-small-ru-0.22"), ("ru".into(), "small".into()));
+    assert_eq!(parse_model_name("vosk-model-en-us-0.22-lgraph"), ("en".into(), "lgraph".into()));
+    assert_eq!(parse_model_name("vosk-model-small-uk-v3-nano"), ("ua".into(), "small".into()));
+}
+
+#[test]
+fn reports_missing_files() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = make_model(dir.path(), "vosk-model-small-ru-0.22");
+    assert!(validate_model(&path).is_empty());
+
+    fs::remove_file(path.join("graph/Gr.fst")).unwrap();
+    fs::remove_file(path.join("am/final.mdl")).unwrap();
+    let problems = validate_model(&path);
+    assert_eq!(problems.len(), 2);
+    assert!(problems[0].contains("am/final.mdl"));
+}
+
+#[test]
+fn manifest_overrides_folder_name() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = make_model(dir.path(), "my-model");
+    fs::write(path.join("jarvis-model.toml"), "language = \"ua\"\nsize = \"small\"\n").unwrap();
+    make_model(dir.path(), "vosk-model-small-ru-0.22");
+
+    let models = scan_dir(dir.path());
+    assert_eq!(models.len(), 2);
+
+    let mine = models.iter().find(|m| m.name == "my-model").unwrap();
+    assert!(mine.has_manifest);
+    assert_eq!(mine.language, "ua");
+    assert_eq!(mine.sample_rate, 16000); // manifest default, not mfcc.conf
+
+    let ru = models.iter().find(|m| m.name != "my-model").unwrap();
+    assert!(!ru.has_manifest);
+    assert_eq!(ru.sample_rate, 8000);
+}
+
+#[test]
+fn picks_model_for_language() {
+    let dir = tempfile::tempdir().unwrap();
+    make_model(dir.path(), "vosk-model-small-en-us-0.15");
+    make_model(dir.path(), "vosk-model-small-ru-0.22");
+    let broken = make_model(dir.path(), "vosk-model-small-uk-v3");
+    fs::remove_file(broken.join("am/final.mdl")).unwrap();
+
+    let models = scan_dir(dir.path());
+
+    assert_eq!(pick_model(&models, "", "ru", dir.path()).unwrap().name, "vosk-model-small-ru-0.22");
+    // explicit choice wins over language
+    assert_eq!(pick_model(&models, "vosk-model-small-en-us-0.15", "ru", dir.path()).unwrap().name, "vosk-model-small-en-us-0.15");
+    // broken configured model falls back to auto-detect
+    assert_eq!(pick_model(&models, "vosk-model-small-uk-v3", "ru", dir.path()).unwrap().name, "vosk-model-small-ru-0.22");
+    assert!(pick_model(&[], "", "ru", dir.path()).is_err());
+    // no silent fallback to another language
+    assert!(pick_model(&models, "", "ua", dir.path()).unwrap_err().contains("only for other languages"));
+}
+
+#[test]
+fn verifies_manifest_checksum() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = make_model(dir.path(), "vosk-model-small-ru-0.22");
+    assert!(verify_checksum(&load_model_info(&path)).is_ok()); // no manifest, nothing to check
+
+    // sha256 of "x"
+    fs::write(path.join("jarvis-model.toml"), "language = \"ru\"\nchecksum = \"2d711642b726b04401627ca9fbac32f5c8530fb1903cc4db02258717921a4881\"\n").unwrap();
+    assert!(verify_checksum(&load_model_info(&path)).is_ok());
+
+    fs::write(path.join("am/final.mdl"), b"y").unwrap();
+    assert!(verify_checksum(&load_model_info(&path)).unwrap_err().contains("Checksum mismatch"));
+}
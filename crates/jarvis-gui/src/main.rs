@@ -92,10 +92,15 @@ fn main() {
 
             // vosk
             tauri_commands::list_vosk_models,
+            tauri_commands::import_vosk_model,
 
             // gliner
             tauri_commands::list_gliner_models,
 
+            // models registry
+            tauri_commands::list_models,
+            tauri_commands::install_model,
+
             // i18n
             tauri_commands::get_translations,
             tauri_commands::translate,
@@ -106,11 +111,18 @@ fn main() {
             // commands
             tauri_commands::get_commands_count,
             tauri_commands::get_commands_list,
+            tauri_commands::get_command_packs,
 
             // voices
             tauri_commands::list_voices,
             tauri_commands::get_voice,
             tauri_commands::preview_voice,
+
+            // wake-word diagnostics
+            tauri_commands::list_wake_samples,
+            tauri_commands::label_wake_sample,
+            tauri_commands::delete_wake_sample,
+            tauri_commands::get_wake_samples_dir,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -37,4 +37,7 @@ pub use commands::*;
 
 // import voices commands
 mod voices;
-pub use voices::*;
\ No newline at end of file
+pub use voices::*;
+// import diagnostics commands
+mod diagnostics;
+pub use diagnostics::*;
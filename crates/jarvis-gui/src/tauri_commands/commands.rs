@@ -1,4 +1,5 @@
-use jarvis_core::commands::{self, JCommand, JCommandsList};
+use jarvis_core::commands::{self, JCommand, JCommandsList, PackManifest};
+use serde::Serialize;
 use once_cell::sync::Lazy;
 
 static COMMANDS: Lazy<Vec<JCommandsList>> = Lazy::new(|| {
@@ -19,4 +20,33 @@ pub fn get_commands_list() -> Vec<JCommand> {
         .iter()
         .flat_map(|list| list.commands.clone())
         .collect()
+}
+
+#[derive(Serialize)]
+pub struct CommandPack {
+    pub path: String,
+    pub commands: usize,
+    pub manifest: Option<PackManifest>, // None for packs without a [pack] table
+    pub items: Vec<CommandItem>,
+}
+
+#[derive(Serialize)]
+pub struct CommandItem {
+    pub id: String,
+    pub description: String,
+}
+
+#[tauri::command]
+pub fn get_command_packs() -> Vec<CommandPack> {
+    COMMANDS
+        .iter()
+        .map(|list| CommandPack {
+            path: list.path.display().to_string(),
+            commands: list.commands.len(),
+            manifest: list.pack.clone(),
+            items: list.commands.iter()
+                .map(|c| CommandItem { id: c.id.clone(), description: c.description.clone() })
+                .collect(),
+        })
+        .collect()
 }
\ No newline at end of file
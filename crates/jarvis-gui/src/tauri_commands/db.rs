@@ -17,6 +17,40 @@ pub fn db_read(state: tauri::State<'_, AppState>, key: &str) -> String {
         "noise_suppression" => format!("{:?}", settings.noise_suppression),
         "vad" => format!("{:?}", settings.vad),
         "gain_normalizer" => settings.gain_normalizer.to_string(),
+        "agc_target_rms" => settings.agc.target_rms.to_string(),
+        "agc_min_gain" => settings.agc.min_gain.to_string(),
+        "agc_max_gain" => settings.agc.max_gain.to_string(),
+        "agc_attack_ms" => settings.agc.attack_ms.to_string(),
+        "agc_release_ms" => settings.agc.release_ms.to_string(),
+        "agc_gate_threshold" => settings.agc.gate_threshold.to_string(),
+        "agc_limiter_threshold" => settings.agc.limiter_threshold.to_string(),
+        "agc_profiles" => serde_json::to_string(&settings.agc_profiles).unwrap_or_default(),
+        "echo_cancellation" => format!("{:?}", settings.echo_cancellation),
+        "audio_pipeline" => serde_json::to_string(&settings.audio_pipeline).unwrap_or_default(),
+        "vad_energy_min_threshold" => settings.vad_settings.energy_min_threshold.to_string(),
+        "vad_energy_ratio" => settings.vad_settings.energy_ratio.to_string(),
+        "vad_noise_floor_adapt" => settings.vad_settings.noise_floor_adapt.to_string(),
+        "vad_attack_frames" => settings.vad_settings.attack_frames.to_string(),
+        "vad_hangover_frames" => settings.vad_settings.hangover_frames.to_string(),
+        "vad_wake_silence_ms" => settings.vad_settings.wake_silence_ms.to_string(),
+        "vad_command_silence_ms" => settings.vad_settings.command_silence_ms.to_string(),
+        "vad_command_timeout_ms" => settings.vad_settings.command_timeout_ms.to_string(),
+        "wake_diagnostics" => settings.wake_diagnostics.to_string(),
+        "telemetry_hz" => settings.telemetry_hz.to_string(),
+        "ducking_enabled" => settings.ducking.enabled.to_string(),
+        "early_intent" => settings.early_intent.to_string(),
+        "recognize_processed_audio" => settings.recognize_processed_audio.to_string(),
+        "command_grammar" => format!("{:?}", settings.command_grammar),
+        "ducking_level" => settings.ducking.level.to_string(),
+        "models_mirror" => settings.models_mirror.clone(),
+        // owned by the app (set_command_enabled over IPC), read the file for its latest state
+        "disabled_commands" => serde_json::to_string(&db::init_settings().disabled_commands).unwrap_or_default(),
+        "embedding_model" => settings.embedding.model.clone(),
+        "embedding_pooling" => format!("{:?}", settings.embedding.pooling),
+        "embedding_quantization" => format!("{:?}", settings.embedding.quantization),
+        "embedding_output_key" => settings.embedding.output_key.clone(),
+        "embedding_query_prefix" => settings.embedding.query_prefix.clone(),
+        "embedding_passage_prefix" => settings.embedding.passage_prefix.clone(),
         "language" => settings.language.to_string(),
         "api_key__picovoice" => settings.api_keys.picovoice.clone(),
         "api_key__openai" => settings.api_keys.openai.clone(),
@@ -81,6 +115,7 @@ pub fn db_write(state: tauri::State<'_, AppState>, key: &str, val: &str) -> bool
                     "none" => settings.vad = jarvis_core::config::structs::VadBackend::None,
                     "energy" => settings.vad = jarvis_core::config::structs::VadBackend::Energy,
                     "nnnoiseless" => settings.vad = jarvis_core::config::structs::VadBackend::Nnnoiseless,
+                    "silero" => settings.vad = jarvis_core::config::structs::VadBackend::Silero,
                     _ => return false,
                 }
             }
@@ -91,6 +126,165 @@ pub fn db_write(state: tauri::State<'_, AppState>, key: &str, val: &str) -> bool
                     _ => return false,
                 }
             }
+            "agc_target_rms" => match val.parse::<f32>() {
+                Ok(v) if v > 0.0 => settings.agc.target_rms = v,
+                _ => return false,
+            },
+            "agc_min_gain" => match val.parse::<f32>() {
+                Ok(v) if v >= 0.0 => settings.agc.min_gain = v,
+                _ => return false,
+            },
+            "agc_max_gain" => match val.parse::<f32>() {
+                Ok(v) if v >= settings.agc.min_gain => settings.agc.max_gain = v,
+                _ => return false,
+            },
+            "agc_attack_ms" => match val.parse::<f32>() {
+                Ok(v) if v >= 0.0 => settings.agc.attack_ms = v,
+                _ => return false,
+            },
+            "agc_release_ms" => match val.parse::<f32>() {
+                Ok(v) if v >= 0.0 => settings.agc.release_ms = v,
+                _ => return false,
+            },
+            "agc_gate_threshold" => match val.parse::<f32>() {
+                Ok(v) if v >= 0.0 => settings.agc.gate_threshold = v,
+                _ => return false,
+            },
+            "agc_limiter_threshold" => match val.parse::<f32>() {
+                Ok(v) if (0.1..=1.0).contains(&v) => settings.agc.limiter_threshold = v,
+                _ => return false,
+            },
+            "agc_profiles" => {
+                // json map: microphone name -> agc settings
+                match serde_json::from_str(val) {
+                    Ok(profiles) => settings.agc_profiles = profiles,
+                    Err(_) => return false,
+                }
+            }
+            "echo_cancellation" => {
+                match val.to_lowercase().as_str() {
+                    "none" => settings.echo_cancellation = jarvis_core::config::structs::EchoCancellation::None,
+                    "gate" => settings.echo_cancellation = jarvis_core::config::structs::EchoCancellation::Gate,
+                    "nlms" => settings.echo_cancellation = jarvis_core::config::structs::EchoCancellation::Nlms,
+                    _ => return false,
+                }
+            }
+            "audio_pipeline" => {
+                // json list of stages, empty = derived from the settings above
+                match serde_json::from_str(val) {
+                    Ok(stages) => settings.audio_pipeline = stages,
+                    Err(_) => return false,
+                }
+            }
+            "vad_energy_min_threshold" => match val.parse::<f32>() {
+                Ok(v) if v >= 0.0 => settings.vad_settings.energy_min_threshold = v,
+                _ => return false,
+            },
+            "vad_energy_ratio" => match val.parse::<f32>() {
+                Ok(v) if v >= 1.0 => settings.vad_settings.energy_ratio = v,
+                _ => return false,
+            },
+            "vad_noise_floor_adapt" => match val.parse::<f32>() {
+                Ok(v) if (0.0..=1.0).contains(&v) => settings.vad_settings.noise_floor_adapt = v,
+                _ => return false,
+            },
+            "vad_attack_frames" => match val.parse::<u32>() {
+                Ok(v) => settings.vad_settings.attack_frames = v,
+                _ => return false,
+            },
+            "vad_hangover_frames" => match val.parse::<u32>() {
+                Ok(v) => settings.vad_settings.hangover_frames = v,
+                _ => return false,
+            },
+            "vad_wake_silence_ms" => match val.parse::<u64>() {
+                Ok(v) => settings.vad_settings.wake_silence_ms = v,
+                _ => return false,
+            },
+            "vad_command_silence_ms" => match val.parse::<u64>() {
+                Ok(v) => settings.vad_settings.command_silence_ms = v,
+                _ => return false,
+            },
+            "vad_command_timeout_ms" => match val.parse::<u64>() {
+                Ok(v) => settings.vad_settings.command_timeout_ms = v,
+                _ => return false,
+            },
+            "telemetry_hz" => match val.parse::<u32>() {
+                Ok(v) if v <= 60 => settings.telemetry_hz = v,
+                _ => return false,
+            },
+            "models_mirror" => {
+                settings.models_mirror = val.trim().to_string();
+            }
+            "embedding_model" => {
+                settings.embedding.model = val.trim().to_string();
+            }
+            "embedding_pooling" => {
+                match val.to_lowercase().as_str() {
+                    "mean" => settings.embedding.pooling = jarvis_core::config::structs::EmbeddingPooling::Mean,
+                    "cls" => settings.embedding.pooling = jarvis_core::config::structs::EmbeddingPooling::Cls,
+                    _ => return false,
+                }
+            }
+            "embedding_quantization" => {
+                match val.to_lowercase().as_str() {
+                    "none" => settings.embedding.quantization = jarvis_core::config::structs::EmbeddingQuantization::None,
+                    "static" => settings.embedding.quantization = jarvis_core::config::structs::EmbeddingQuantization::Static,
+                    "dynamic" => settings.embedding.quantization = jarvis_core::config::structs::EmbeddingQuantization::Dynamic,
+                    _ => return false,
+                }
+            }
+            "embedding_output_key" => {
+                if val.trim().is_empty() {
+                    return false;
+                }
+                settings.embedding.output_key = val.trim().to_string();
+            }
+            "embedding_query_prefix" => {
+                settings.embedding.query_prefix = val.to_string();
+            }
+            "embedding_passage_prefix" => {
+                settings.embedding.passage_prefix = val.to_string();
+            }
+            "command_grammar" => {
+                match val.to_lowercase().as_str() {
+                    "off" => settings.command_grammar = jarvis_core::config::structs::CommandGrammarMode::Off,
+                    "alongside" => settings.command_grammar = jarvis_core::config::structs::CommandGrammarMode::Alongside,
+                    "only" => settings.command_grammar = jarvis_core::config::structs::CommandGrammarMode::Only,
+                    _ => return false,
+                }
+            }
+            "early_intent" => {
+                match val.to_lowercase().as_str() {
+                    "true" => settings.early_intent = true,
+                    "false" => settings.early_intent = false,
+                    _ => return false,
+                }
+            }
+            "recognize_processed_audio" => {
+                match val.to_lowercase().as_str() {
+                    "true" => settings.recognize_processed_audio = true,
+                    "false" => settings.recognize_processed_audio = false,
+                    _ => return false,
+                }
+            }
+            "ducking_enabled" => {
+                match val.to_lowercase().as_str() {
+                    "true" => settings.ducking.enabled = true,
+                    "false" => settings.ducking.enabled = false,
+                    _ => return false,
+                }
+            }
+            "ducking_level" => match val.parse::<f32>() {
+                Ok(v) if (0.0..=1.0).contains(&v) => settings.ducking.level = v,
+                _ => return false,
+            },
+            "wake_diagnostics" => {
+                match val.to_lowercase().as_str() {
+                    "true" => settings.wake_diagnostics = true,
+                    "false" => settings.wake_diagnostics = false,
+                    _ => return false,
+                }
+            }
             "language" => {
                 settings.language = val.to_string();
             }
@@ -103,6 +297,9 @@ pub fn db_write(state: tauri::State<'_, AppState>, key: &str, val: &str) -> bool
             _ => return false,
         }
 
+        // don't overwrite command toggles the app saved meanwhile
+        settings.disabled_commands = db::init_settings().disabled_commands;
+
         settings.clone()
     };
 
@@ -0,0 +1,21 @@
+use jarvis_core::diagnostics::{self, SampleLabel, SampleMeta};
+
+#[tauri::command]
+pub fn list_wake_samples() -> Vec<SampleMeta> {
+    diagnostics::list_samples()
+}
+
+#[tauri::command]
+pub fn label_wake_sample(id: String, label: String) -> Result<SampleMeta, String> {
+    diagnostics::label_sample(&id, label.parse::<SampleLabel>()?)
+}
+
+#[tauri::command]
+pub fn delete_wake_sample(id: String) -> Result<(), String> {
+    diagnostics::delete_sample(&id)
+}
+
+#[tauri::command]
+pub fn get_wake_samples_dir() -> String {
+    diagnostics::samples_dir().display().to_string()
+}
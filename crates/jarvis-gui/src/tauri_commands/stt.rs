@@ -1,4 +1,4 @@
-use jarvis_core::{vosk_models, gliner_models};
+use jarvis_core::{vosk_models, gliner_models, models};
 use serde::Serialize;
 
 #[derive(Serialize)]
@@ -6,6 +6,20 @@ pub struct VoskModel {
     pub name: String,
     pub language: String,
     pub size: String,
+    pub sample_rate: u32,
+    pub problems: Vec<String>,
+}
+
+impl From<vosk_models::VoskModelInfo> for VoskModel {
+    fn from(m: vosk_models::VoskModelInfo) -> Self {
+        Self {
+            name: m.name,
+            language: m.language,
+            size: m.size,
+            sample_rate: m.sample_rate,
+            problems: m.problems,
+        }
+    }
 }
 
 #[derive(Serialize)]
@@ -18,14 +32,15 @@ pub struct GlinerVariant {
 pub fn list_vosk_models() -> Vec<VoskModel> {
     vosk_models::scan_vosk_models()
         .into_iter()
-        .map(|m| VoskModel {
-            name: m.name,
-            language: m.language,
-            size: m.size,
-        })
+        .map(VoskModel::from)
         .collect()
 }
 
+#[tauri::command]
+pub fn import_vosk_model(path: String) -> Result<VoskModel, String> {
+    vosk_models::import_model_archive(std::path::Path::new(&path)).map(VoskModel::from)
+}
+
 #[tauri::command]
 pub fn list_gliner_models() -> Vec<GlinerVariant> {
     gliner_models::scan_gliner_variants()
@@ -36,3 +51,43 @@ pub fn list_gliner_models() -> Vec<GlinerVariant> {
         })
         .collect()
 }
+
+#[derive(Serialize)]
+pub struct RegistryModel {
+    pub id: String,
+    pub kind: models::ModelKind,
+    pub languages: Vec<String>,
+    pub installed: bool,
+}
+
+#[tauri::command]
+pub fn list_models() -> Vec<RegistryModel> {
+    models::registry()
+        .models
+        .iter()
+        .map(|m| RegistryModel {
+            id: m.id.clone(),
+            kind: m.kind,
+            languages: m.languages.clone(),
+            installed: m.is_installed(),
+        })
+        .collect()
+}
+
+// from a local archive if given, otherwise from the mirror.
+// downloading and unpacking takes a while, so it runs off the main thread
+#[tauri::command]
+pub async fn install_model(id: String, archive: Option<String>) -> Result<String, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let entry = models::find(&id).ok_or_else(|| format!("Unknown model '{}'", id))?;
+
+        let path = match archive {
+            Some(archive) => models::install_from_archive(entry, std::path::Path::new(&archive))?,
+            None => models::install_from_mirror(entry)?,
+        };
+
+        Ok(path.display().to_string())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
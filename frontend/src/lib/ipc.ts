@@ -9,8 +9,23 @@ export type JarvisState = "disconnected" | "idle" | "listening" | "processing"
 export const jarvisState = writable<JarvisState>("disconnected")
 export const ipcConnected = writable(false)
 export const lastRecognizedText = writable("")
+export const partialTranscript = writable("")  // cleared once the final text arrives
 export const lastExecutedCommand = writable("")
 export const lastError = writable("")
+export const pendingConfirmation = writable("")  // prompt while a command waits for yes / no
+export const commandToggles = writable<Record<string, boolean>>({})  // command id -> enabled, as confirmed by the app
+
+export type AudioLevel = {
+    rms: number
+    peak: number
+    is_voice: boolean
+    vad_confidence: number
+    vad_state: string
+    wake_score: number | null
+}
+
+// only updated while telemetry is enabled via setTelemetry()
+export const audioLevel = writable<AudioLevel | null>(null)
 
 // ### CONNECTION ###
 
@@ -92,6 +107,18 @@ export function disconnectIpc() {
 // ### EVENT HANDLING ###
 
 function handleEvent(data: any) {
+    // telemetry is too frequent to log
+    if (data.event === "audio_level") {
+        audioLevel.set(data)
+        return
+    }
+
+    // partials are too frequent to log as well
+    if (data.event === "partial_transcript") {
+        partialTranscript.set(data.text || "")
+        return
+    }
+
     console.log("IPC: Event", data.event, data)
 
     switch (data.event) {
@@ -102,15 +129,30 @@ function handleEvent(data: any) {
 
         case "speech_recognized":
             lastRecognizedText.set(data.text || "")
+            partialTranscript.set("")
             jarvisState.set("processing")
             break
 
+        case "confirmation_requested":
+            pendingConfirmation.set(data.prompt || "")
+            jarvisState.set("listening")
+            break
+
+        case "confirmation_resolved":
+            pendingConfirmation.set("")
+            break
+
+        case "command_enabled_changed":
+            commandToggles.update(t => ({ ...t, [data.id]: data.enabled }))
+            break
+
         case "command_executed":
             lastExecutedCommand.set(data.id || "")
             break
 
         case "idle":
             jarvisState.set("idle")
+            partialTranscript.set("")
             break
 
         case "error":
@@ -175,6 +217,17 @@ export function sendTextCommand(text: string): boolean {
     return sendAction("text_command", { text })
 }
 
+// opt in/out of live audio level + VAD telemetry
+export function setTelemetry(enabled: boolean): boolean {
+    if (!enabled) audioLevel.set(null)
+    return sendAction("set_telemetry", { enabled })
+}
+
+// switch a command on / off in the running app, it also persists the change
+export function setCommandEnabled(id: string, enabled: boolean): boolean {
+    return sendAction("set_command_enabled", { id, enabled })
+}
+
 async function revealWindow() {
     try {
         const window = getCurrentWindow()
@@ -6,8 +6,11 @@ export {
     jarvisState,
     ipcConnected,
     lastRecognizedText,
+    partialTranscript,
     lastExecutedCommand,
     lastError,
+    audioLevel,
+    commandToggles,
     connectIpc,
     enableIpc,
     disableIpc,
@@ -16,7 +19,9 @@ export {
     sendIpcMessage,
     sendTextCommand,
     stopJarvisApp,
-    reloadCommands
+    reloadCommands,
+    setTelemetry,
+    setCommandEnabled
 } from "./lib/ipc"
 
 // re-export i18n